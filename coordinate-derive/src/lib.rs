@@ -0,0 +1,247 @@
+//!`#[derive(Coordinate)]` for structs with named fields
+//!
+//!fields map to dimensions in declaration order, all sharing the scalar type of
+//!the first one; fields marked `#[coordinate(skip)]` are not dimensions and are
+//!filled with `Default::default()` by `gen`
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+#[proc_macro_derive(Coordinate, attributes(coordinate))]
+pub fn derive_coordinate(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(code) => code.parse().unwrap(),
+        Err(msg) => format!("compile_error!({:?});", msg).parse().unwrap(),
+    }
+}
+
+struct Field {
+    name: String,
+    ty: String,
+    skip: bool,
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut i = 0;
+    skip_attributes(&tokens, &mut i);
+    skip_visibility(&tokens, &mut i);
+
+    if !is_ident(tokens.get(i), "struct") {
+        return Err("Coordinate can only be derived for structs".into());
+    }
+    i += 1;
+    let name = match tokens.get(i) {
+        Some(TokenTree::Ident(id)) => id.to_string(),
+        _ => return Err("expected struct name".into()),
+    };
+    i += 1;
+
+    let mut generics = Vec::new();
+    if is_punct(tokens.get(i), '<') {
+        let mut depth = 0;
+        while let Some(tt) = tokens.get(i) {
+            i += 1;
+            if is_punct(Some(tt), '<') {
+                depth += 1;
+                if depth == 1 {
+                    continue;
+                }
+            } else if is_punct(Some(tt), '>') && !is_arrow(&tokens, i - 1) {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            generics.push(tt.clone());
+        }
+    }
+
+    let mut where_clause = Vec::new();
+    let body = loop {
+        match tokens.get(i) {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => break g.stream(),
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                return Err("Coordinate can only be derived for structs with named fields".into())
+            }
+            Some(tt) => where_clause.push(tt.clone()),
+            None => {
+                return Err("Coordinate can only be derived for structs with named fields".into())
+            }
+        }
+        i += 1;
+    };
+
+    let fields = parse_fields(body)?;
+    let dims: Vec<&Field> = fields.iter().filter(|f| !f.skip).collect();
+    let scalar = match dims.first() {
+        Some(f) => f.ty.clone(),
+        None => return Err("Coordinate needs at least one non skipped field".into()),
+    };
+    if let Some(f) = dims.iter().find(|f| f.ty != scalar) {
+        return Err(format!(
+            "field `{}` has type `{}`, coordinate fields must all be `{}`",
+            f.name, f.ty, scalar
+        ));
+    }
+
+    let params = split_top_level(&generics);
+    let type_args: Vec<String> = params.iter().filter_map(|p| param_name(p)).collect();
+    let impl_generics = stream(&generics);
+    let where_clause = stream(&where_clause);
+
+    let ctor: Vec<String> = {
+        let mut k = 0;
+        fields
+            .iter()
+            .map(|f| {
+                if f.skip {
+                    format!("{}: ::core::default::Default::default()", f.name)
+                } else {
+                    k += 1;
+                    format!("{}: val_fn({})", f.name, k - 1)
+                }
+            })
+            .collect()
+    };
+    let arms = |prefix: &str| -> String {
+        dims.iter()
+            .enumerate()
+            .map(|(k, f)| format!("{} => {}self.{},", k, prefix, f.name))
+            .collect()
+    };
+
+    Ok(format!(
+        "impl<{g}> ::coordinate::Coordinate for {name}<{a}> {w} {{
+            type Scalar = {s};
+            const DIM: usize = {dim};
+
+            fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {{
+                {name} {{ {ctor} }}
+            }}
+
+            fn val(&self, i: usize) -> Self::Scalar {{
+                match i {{ {val} _ => unreachable!() }}
+            }}
+
+            fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {{
+                match i {{ {val_mut} _ => unreachable!() }}
+            }}
+        }}",
+        g = impl_generics,
+        name = name,
+        a = type_args.join(", "),
+        w = where_clause,
+        s = scalar,
+        dim = dims.len(),
+        ctor = ctor.join(", "),
+        val = arms(""),
+        val_mut = arms("&mut "),
+    ))
+}
+
+fn parse_fields(body: TokenStream) -> Result<Vec<Field>, String> {
+    let tokens: Vec<TokenTree> = body.into_iter().collect();
+    let mut fields = Vec::new();
+    for part in split_top_level(&tokens) {
+        let mut i = 0;
+        let mut skip = false;
+        while is_punct(part.get(i), '#') {
+            if let Some(TokenTree::Group(g)) = part.get(i + 1) {
+                skip |= is_skip_attribute(g.stream())?;
+            }
+            i += 2;
+        }
+        skip_visibility(&part, &mut i);
+        let name = match part.get(i) {
+            Some(TokenTree::Ident(id)) => id.to_string(),
+            _ => return Err("expected field name".into()),
+        };
+        if !is_punct(part.get(i + 1), ':') {
+            return Err(format!("expected `:` after field `{}`", name));
+        }
+        let ty = stream(&part[i + 2..]);
+        fields.push(Field { name, ty, skip });
+    }
+    Ok(fields)
+}
+
+///checks the contents of `#[...]` for `coordinate(skip)`
+fn is_skip_attribute(attr: TokenStream) -> Result<bool, String> {
+    let tokens: Vec<TokenTree> = attr.into_iter().collect();
+    if !is_ident(tokens.first(), "coordinate") {
+        return Ok(false);
+    }
+    match tokens.get(1) {
+        Some(TokenTree::Group(g)) if g.stream().to_string().trim() == "skip" => Ok(true),
+        _ => Err("expected `#[coordinate(skip)]`".into()),
+    }
+}
+
+fn skip_attributes(tokens: &[TokenTree], i: &mut usize) {
+    while is_punct(tokens.get(*i), '#') {
+        *i += 2;
+    }
+}
+
+fn skip_visibility(tokens: &[TokenTree], i: &mut usize) {
+    if is_ident(tokens.get(*i), "pub") {
+        *i += 1;
+        if let Some(TokenTree::Group(g)) = tokens.get(*i) {
+            if g.delimiter() == Delimiter::Parenthesis {
+                *i += 1;
+            }
+        }
+    }
+}
+
+///splits on commas outside of angle brackets, dropping empty parts
+fn split_top_level(tokens: &[TokenTree]) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0;
+    for (k, tt) in tokens.iter().enumerate() {
+        if is_punct(Some(tt), '<') {
+            depth += 1;
+        } else if is_punct(Some(tt), '>') && !is_arrow(tokens, k) {
+            depth -= 1;
+        } else if depth == 0 && is_punct(Some(tt), ',') {
+            parts.push(Vec::new());
+            continue;
+        }
+        parts.last_mut().unwrap().push(tt.clone());
+    }
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+///name of a generic parameter: `'a`, `T` or `N` of `const N: usize`
+fn param_name(param: &[TokenTree]) -> Option<String> {
+    match (param.first(), param.get(1)) {
+        (Some(TokenTree::Punct(p)), Some(lt)) if p.as_char() == '\'' => Some(format!("'{}", lt)),
+        (Some(TokenTree::Ident(id)), Some(n)) if id.to_string() == "const" => Some(n.to_string()),
+        (Some(TokenTree::Ident(id)), _) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+///`>` that closes a `->`
+fn is_arrow(tokens: &[TokenTree], k: usize) -> bool {
+    k > 0
+        && match &tokens[k - 1] {
+            TokenTree::Punct(p) => p.as_char() == '-' && p.spacing() == Spacing::Joint,
+            _ => false,
+        }
+}
+
+fn is_punct(tt: Option<&TokenTree>, ch: char) -> bool {
+    matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == ch)
+}
+
+fn is_ident(tt: Option<&TokenTree>, name: &str) -> bool {
+    matches!(tt, Some(TokenTree::Ident(id)) if id.to_string() == name)
+}
+
+fn stream(tokens: &[TokenTree]) -> String {
+    TokenStream::from_iter(tokens.iter().cloned()).to_string()
+}
@@ -0,0 +1,182 @@
+use super::ring::open_ring;
+use crate::{Bounds, Coordinate};
+use num_traits::{Float, One, Zero};
+
+///clips a ring (open or closed) to bounds on the first two dimensions
+///(Sutherland-Hodgman), later dimensions are interpolated along clipped edges;
+///the result is closed if the input is, empty if nothing remains.
+///Concave rings may keep zero-width slivers along the bounds
+pub fn clip_ring_to_bounds<C>(ring: &[C], bounds: &Bounds<C>) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let closed = ring.len() > 1 && ring[0] == ring[ring.len() - 1];
+    let mut out = open_ring(ring).to_vec();
+    for axis in 0..2 {
+        let (lo, hi) = (bounds.min.val(axis), bounds.max.val(axis));
+        out = clip_half_plane(&out, |p| p.val(axis) - lo);
+        out = clip_half_plane(&out, |p| hi - p.val(axis));
+    }
+    if closed && !out.is_empty() {
+        out.push(out[0]);
+    }
+    out
+}
+
+///part of segment a-b inside bounds over all dimensions, None if it misses;
+///outcodes accept or reject trivially, otherwise the parameter range is cut
+///down per axis (Liang-Barsky). The result keeps the a to b direction
+pub fn clip_segment_to_bounds<C>(a: &C, b: &C, bounds: &Bounds<C>) -> Option<(C, C)>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (ca, cb) = (outcodes(a, bounds), outcodes(b, bounds));
+    if ca.iter().zip(&cb).any(|(x, y)| *x != 0 && x == y) {
+        return None;
+    }
+    if ca.iter().chain(&cb).all(|&c| c == 0) {
+        return Some((*a, *b));
+    }
+    let (zero, one) = (C::Scalar::zero(), C::Scalar::one());
+    let d = b.sub(a);
+    let (mut t0, mut t1) = (zero, one);
+    for i in 0..C::DIM {
+        let (lo, hi) = (bounds.min.val(i), bounds.max.val(i));
+        let (p, di) = (a.val(i), d.val(i));
+        if di == zero {
+            if p < lo || p > hi {
+                return None;
+            }
+            continue;
+        }
+        let (mut enter, mut exit) = ((lo - p) / di, (hi - p) / di);
+        if enter > exit {
+            std::mem::swap(&mut enter, &mut exit);
+        }
+        t0 = t0.max(enter);
+        t1 = t1.min(exit);
+        if t0 > t1 {
+            return None;
+        }
+    }
+    Some((a.add(&d.mult(t0)), a.add(&d.mult(t1))))
+}
+
+///per axis outcode - -1 below min, 1 above max, 0 inside
+fn outcodes<C: Coordinate>(p: &C, bounds: &Bounds<C>) -> Vec<i8> {
+    (0..C::DIM)
+        .map(|i| {
+            if p.val(i) < bounds.min.val(i) {
+                -1
+            } else if p.val(i) > bounds.max.val(i) {
+                1
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+///keeps the part of an open ring where inside(p) >= 0
+fn clip_half_plane<C>(ring: &[C], inside: impl Fn(&C) -> C::Scalar) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let zero = C::Scalar::zero();
+    let mut out = Vec::with_capacity(ring.len() + 2);
+    for (i, cur) in ring.iter().enumerate() {
+        let prev = &ring[(i + ring.len() - 1) % ring.len()];
+        let (dp, dc) = (inside(prev), inside(cur));
+        if (dp >= zero) != (dc >= zero) {
+            let t = dp / (dp - dc);
+            out.push(prev.add(&cur.sub(prev).mult(t)));
+        }
+        if dc >= zero {
+            out.push(*cur);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_clip_segment_to_bounds() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let bounds = Bounds::new(p(0., 0.), p(4., 2.));
+        let (a, b) = (p(1., 1.), p(3., 1.5));
+        assert_eq!(clip_segment_to_bounds(&a, &b, &bounds), Some((a, b)));
+        assert_eq!(
+            clip_segment_to_bounds(&p(-2., 1.), &p(6., 1.), &bounds),
+            Some((p(0., 1.), p(4., 1.)))
+        );
+        assert_eq!(
+            clip_segment_to_bounds(&p(6., 1.), &p(2., -1.), &bounds),
+            Some((p(4., 0.), p(4., 0.)))
+        );
+        assert_eq!(
+            clip_segment_to_bounds(&p(-1., -1.), &p(3., 3.), &bounds),
+            Some((p(0., 0.), p(2., 2.)))
+        );
+        //trivial reject and a miss across a corner
+        assert_eq!(
+            clip_segment_to_bounds(&p(5., 0.), &p(6., 2.), &bounds),
+            None
+        );
+        assert_eq!(
+            clip_segment_to_bounds(&p(3., 3.), &p(5., 1.), &bounds),
+            None
+        );
+        assert_eq!(
+            clip_segment_to_bounds(&p(-1., 3.), &p(5., 3.), &bounds),
+            None
+        );
+
+        let b3 = Bounds::new(Coord([0., 0., 0.]), Coord([1., 1., 1.]));
+        let hit = clip_segment_to_bounds(&Coord([0.5, 0.5, -1.]), &Coord([0.5, 0.5, 3.]), &b3);
+        assert_eq!(hit, Some((Coord([0.5, 0.5, 0.]), Coord([0.5, 0.5, 1.]))));
+    }
+
+    #[test]
+    fn test_clip_ring_to_bounds() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let bounds = Bounds::new(p(0., 0.), p(2., 2.));
+        let tri = [p(-1., 0.), p(3., 0.), p(1., 2.)];
+        assert_eq!(
+            clip_ring_to_bounds(&tri, &bounds),
+            vec![p(0., 1.), p(0., 0.), p(2., 0.), p(2., 1.), p(1., 2.)]
+        );
+
+        let inside = [p(0.5, 0.5), p(1., 0.5), p(1., 1.), p(0.5, 0.5)];
+        assert_eq!(clip_ring_to_bounds(&inside, &bounds), inside.to_vec());
+
+        let outside = [p(3., 3.), p(4., 3.), p(4., 4.), p(3., 3.)];
+        assert!(clip_ring_to_bounds(&outside, &bounds).is_empty());
+
+        //bounds fully inside the ring
+        let big = [p(-5., -5.), p(5., -5.), p(5., 5.), p(-5., 5.), p(-5., -5.)];
+        let clipped = clip_ring_to_bounds(&big, &bounds);
+        assert_eq!(
+            clipped,
+            vec![p(0., 2.), p(0., 0.), p(2., 0.), p(2., 2.), p(0., 2.)]
+        );
+        assert!(clip_ring_to_bounds::<Pt<f64>>(&[], &bounds).is_empty());
+
+        let ring3 = [
+            Coord([-1., 1., 0.]),
+            Coord([3., 1., 4.]),
+            Coord([1., 3., 8.]),
+        ];
+        let b3 = Bounds::new(Coord([0., 0., -10.]), Coord([2., 2., 10.]));
+        let clipped = clip_ring_to_bounds(&ring3, &b3);
+        assert!(clipped.contains(&Coord([0., 1., 1.])));
+        assert!(clipped.iter().all(|c| b3.contains(c)));
+    }
+}
@@ -0,0 +1,217 @@
+use super::centroid;
+use crate::rng::XorShift;
+use crate::{Coordinate, SpatialHash};
+use num_traits::{Float, Zero};
+
+///k-means options
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct KMeansOptions<T> {
+    ///maximum number of Lloyd iterations
+    pub max_iter: usize,
+    ///stop once no center moves more than sqrt(tolerance)
+    pub square_tolerance: T,
+    ///seed of the k-means++ sampling
+    pub seed: u64,
+}
+
+impl<T: Float> Default for KMeansOptions<T> {
+    fn default() -> Self {
+        KMeansOptions {
+            max_iter: 100,
+            square_tolerance: T::epsilon(),
+            seed: 1,
+        }
+    }
+}
+
+fn nearest<C: Coordinate>(pt: &C, centers: &[C]) -> (usize, C::Scalar) {
+    let mut best = (0, pt.square_distance(&centers[0]));
+    for (i, c) in centers.iter().enumerate().skip(1) {
+        let d = pt.square_distance(c);
+        if d < best.1 {
+            best = (i, d);
+        }
+    }
+    best
+}
+
+///k-means++ seeding - first center uniform, then proportional to square distance
+fn seed_centers<C>(points: &[C], k: usize, rng: &mut XorShift) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut centers = vec![points[rng.below(points.len())]];
+    let mut dist: Vec<C::Scalar> = points
+        .iter()
+        .map(|p| p.square_distance(&centers[0]))
+        .collect();
+    while centers.len() < k {
+        let total = dist.iter().fold(C::Scalar::zero(), |acc, &d| acc + d);
+        if total <= C::Scalar::zero() {
+            //fewer distinct points than k
+            break;
+        }
+        let mut target = total * num_traits::cast(rng.next_f64()).unwrap();
+        let mut pick = points.len() - 1;
+        for (i, &d) in dist.iter().enumerate() {
+            if target < d {
+                pick = i;
+                break;
+            }
+            target = target - d;
+        }
+        let c = points[pick];
+        for (d, p) in dist.iter_mut().zip(points) {
+            *d = d.min(p.square_distance(&c));
+        }
+        centers.push(c);
+    }
+    centers
+}
+
+///k-means clustering with k-means++ seeding - returns (centers, label per point);
+///fewer than k centers are returned if there are fewer distinct points
+pub fn kmeans<C>(points: &[C], k: usize, opts: &KMeansOptions<C::Scalar>) -> (Vec<C>, Vec<usize>)
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.is_empty() || k == 0 {
+        return (Vec::new(), vec![0; points.len()]);
+    }
+    let mut rng = XorShift::new(opts.seed);
+    let mut centers = seed_centers(points, k, &mut rng);
+    let mut labels: Vec<usize> = points.iter().map(|p| nearest(p, &centers).0).collect();
+
+    for _ in 0..opts.max_iter {
+        let mut members: Vec<Vec<C>> = vec![Vec::new(); centers.len()];
+        for (p, &l) in points.iter().zip(&labels) {
+            members[l].push(*p);
+        }
+        let mut shift = C::Scalar::zero();
+        for (c, m) in centers.iter_mut().zip(&members) {
+            //empty clusters keep their center
+            if let Some(mean) = centroid(m) {
+                shift = shift.max(c.square_distance(&mean));
+                *c = mean;
+            }
+        }
+        for (l, p) in labels.iter_mut().zip(points) {
+            *l = nearest(p, &centers).0;
+        }
+        if shift <= opts.square_tolerance {
+            break;
+        }
+    }
+    (centers, labels)
+}
+
+///DBSCAN density clustering - cluster label per point, None for noise;
+///a point is a core point if at least min_pts points (itself included) are within eps
+pub fn dbscan<C>(points: &[C], eps: C::Scalar, min_pts: usize) -> Vec<Option<usize>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let hash = SpatialHash::from_points(points, eps);
+    let mut labels: Vec<Option<usize>> = vec![None; points.len()];
+    let mut visited = vec![false; points.len()];
+    let mut cluster = 0;
+
+    for i in 0..points.len() {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+        let neighbours = hash.within_radius(&points[i], eps);
+        if neighbours.len() < min_pts {
+            continue;
+        }
+        labels[i] = Some(cluster);
+        let mut queue = neighbours;
+        while let Some(j) = queue.pop() {
+            if labels[j].is_none() {
+                labels[j] = Some(cluster);
+            }
+            if visited[j] {
+                continue;
+            }
+            visited[j] = true;
+            let next = hash.within_radius(&points[j], eps);
+            if next.len() >= min_pts {
+                queue.extend(next);
+            }
+        }
+        cluster += 1;
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_kmeans() {
+        let mut pts = Vec::new();
+        for &(cx, cy) in [(0., 0.), (10., 10.), (-10., 10.)].iter() {
+            for i in 0..20 {
+                let t = i as f64;
+                pts.push(Pt {
+                    x: cx + t.cos(),
+                    y: cy + t.sin() * 0.5,
+                });
+            }
+        }
+        let (centers, labels) = kmeans(&pts, 3, &KMeansOptions::default());
+        assert_eq!(centers.len(), 3);
+        for group in labels.chunks(20) {
+            assert!(group.iter().all(|&l| l == group[0]));
+        }
+        assert_ne!(labels[0], labels[20]);
+        assert_ne!(labels[20], labels[40]);
+        assert_ne!(labels[0], labels[40]);
+        for (c, group) in [0, 20, 40]
+            .iter()
+            .map(|&i| (centers[labels[i]], &pts[i..i + 20]))
+        {
+            assert!(c.square_distance(&centroid(group).unwrap()) < 1e-20);
+        }
+
+        let same = [Pt { x: 1., y: 1. }; 5];
+        let (centers, labels) = kmeans(&same, 3, &KMeansOptions::default());
+        assert_eq!(centers, vec![Pt { x: 1., y: 1. }]);
+        assert_eq!(labels, vec![0; 5]);
+        assert_eq!(
+            kmeans::<Pt<f64>>(&[], 2, &KMeansOptions::default()),
+            (vec![], vec![])
+        );
+    }
+
+    #[test]
+    fn test_dbscan() {
+        let mut pts: Vec<_> = (0..10)
+            .map(|i| Pt {
+                x: i as f64 * 0.5,
+                y: 0.,
+            })
+            .collect();
+        pts.extend((0..5).map(|i| Pt {
+            x: 20.,
+            y: i as f64 * 0.5,
+        }));
+        pts.push(Pt { x: 10., y: 10. });
+        pts.push(Pt { x: 20., y: 2.5 });
+
+        let labels = dbscan(&pts, 0.6, 3);
+        assert!(labels[..10].iter().all(|&l| l == Some(0)));
+        assert!(labels[10..15].iter().all(|&l| l == Some(1)));
+        assert_eq!(labels[15], None);
+        //border point reachable from a core point
+        assert_eq!(labels[16], Some(1));
+        assert_eq!(dbscan(&pts, 0.6, 1)[15], Some(2));
+        assert!(dbscan::<Pt<f64>>(&[], 1., 2).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use super::delaunay;
+use crate::Coordinate;
+use num_traits::{Float, ToPrimitive};
+use std::collections::{HashMap, HashSet};
+
+///concave hull as an alpha shape on the first two dimensions - the outer boundary
+///(counter-clockwise indices, not closed) of the Delaunay triangles with circumradius
+///at most alpha; the largest piece if the shape splits, empty if no triangle is kept.
+///An infinite alpha gives the convex hull including collinear boundary points
+pub fn concave_hull<C>(points: &[C], alpha: C::Scalar) -> Vec<usize>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let xy = |i: usize| -> [f64; 2] {
+        let p = &points[i];
+        [
+            p.val(0).to_f64().unwrap_or(f64::NAN),
+            p.val(1).to_f64().unwrap_or(f64::NAN),
+        ]
+    };
+    let alpha = alpha.to_f64().unwrap_or(f64::NAN);
+    let sq = |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2);
+
+    //directed edges of kept triangles, interior on the left
+    let mut edges: HashSet<(usize, usize)> = HashSet::new();
+    for t in delaunay(points) {
+        let (a, b, c) = (xy(t[0]), xy(t[1]), xy(t[2]));
+        let cross = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        //R^2 = |ab|^2 |bc|^2 |ca|^2 / (4 cross^2)
+        let keep = alpha.is_infinite()
+            || sq(a, b) * sq(b, c) * sq(c, a) <= 4. * cross * cross * alpha * alpha;
+        if keep {
+            edges.extend([(t[0], t[1]), (t[1], t[2]), (t[2], t[0])]);
+        }
+    }
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut boundary: Vec<(usize, usize)> = edges
+        .iter()
+        .copied()
+        .filter(|&(a, b)| !edges.contains(&(b, a)))
+        .collect();
+    boundary.sort_unstable();
+    for &(a, b) in &boundary {
+        outgoing.entry(a).or_default().push(b);
+    }
+
+    //trace boundary loops, splitting at pinch vertices by taking the first
+    //outgoing edge clockwise from the reversed incoming edge
+    let mut used: HashSet<(usize, usize)> = HashSet::new();
+    let mut best: (f64, Vec<usize>) = (0., Vec::new());
+    for &start in &boundary {
+        if used.contains(&start) {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let (mut a, mut b) = start;
+        while used.insert((a, b)) {
+            ring.push(a);
+            let (pa, pb) = (xy(a), xy(b));
+            let back = (pa[1] - pb[1]).atan2(pa[0] - pb[0]);
+            let next = outgoing[&b]
+                .iter()
+                .copied()
+                .map(|c| {
+                    let pc = xy(c);
+                    let turn = (back - (pc[1] - pb[1]).atan2(pc[0] - pb[0]))
+                        .rem_euclid(std::f64::consts::TAU);
+                    let turn = if turn == 0. {
+                        std::f64::consts::TAU
+                    } else {
+                        turn
+                    };
+                    (turn, c)
+                })
+                .fold(
+                    (f64::INFINITY, b),
+                    |acc, tc| if tc.0 < acc.0 { tc } else { acc },
+                )
+                .1;
+            a = b;
+            b = next;
+        }
+        let area = (0..ring.len()).fold(0., |acc, i| {
+            let (p, q) = (xy(ring[i]), xy(ring[(i + 1) % ring.len()]));
+            acc + p[0] * q[1] - q[0] * p[1]
+        });
+        if area > best.0 {
+            best = (area, ring);
+        }
+    }
+    best.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::convex_hull;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_concave_hull() {
+        //U shape: 6 x 6 grid with x in [2, 3] cut out above y = 1
+        let pts: Vec<_> = (0..36)
+            .map(|i| Pt {
+                x: (i % 6) as f64,
+                y: (i / 6) as f64,
+            })
+            .filter(|p| !(p.y >= 2. && (2. ..=3.).contains(&p.x)))
+            .collect();
+        let ring = concave_hull(&pts, 0.8);
+        //all but the inner corners (1, 1) and (4, 1), cut off by notch diagonals
+        assert_eq!(ring.len(), pts.len() - 2);
+        let notch = pts.iter().position(|p| *p == Pt { x: 3., y: 1. }).unwrap();
+        assert!(ring.contains(&notch));
+        let start = ring.iter().position(|&i| i == 0).unwrap();
+        let ordered: Vec<_> = (0..ring.len())
+            .map(|k| pts[ring[(start + k) % ring.len()]])
+            .collect();
+        assert_eq!(ordered[1], Pt { x: 1., y: 0. });
+
+        let full = concave_hull(&pts, f64::INFINITY);
+        let hull = convex_hull(&pts);
+        assert!(hull.iter().all(|i| full.contains(i)));
+        assert!(!full.contains(&notch));
+
+        assert!(concave_hull(&pts, 0.1).is_empty());
+        assert!(concave_hull::<Pt<f64>>(&[], 1.).is_empty());
+    }
+
+    #[test]
+    fn test_concave_hull_pinch() {
+        //two triangles of different size touching at the origin
+        let pts = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 0. },
+            Pt { x: 0.5, y: 0.8 },
+            Pt { x: -2., y: 0. },
+            Pt { x: -1., y: -1.6 },
+        ];
+        let ring = concave_hull(&pts, 1.2);
+        let mut sorted = ring.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 3, 4]);
+        let mut small = concave_hull(&pts, 0.6);
+        small.sort_unstable();
+        assert_eq!(small, vec![0, 1, 2]);
+    }
+}
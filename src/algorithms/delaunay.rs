@@ -0,0 +1,197 @@
+use crate::predicates::{incircle, orient2d};
+use crate::Coordinate;
+use num_traits::ToPrimitive;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+///vertex of tri opposite to the directed edge (a, b)
+fn opposite(tri: &[usize; 3], a: usize, b: usize) -> usize {
+    tri.iter().copied().find(|&v| v != a && v != b).unwrap()
+}
+
+///2D Delaunay triangulation on the first two dimensions - counter-clockwise vertex
+///index triples; a sweep triangulation with exact predicates made Delaunay by edge
+///flips. Exact duplicates after the first occurrence and points with non-finite
+///components are left out, collinear input has no triangles
+pub fn delaunay<C>(points: &[C]) -> Vec<[usize; 3]>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    let pts: Vec<[f64; 2]> = points
+        .iter()
+        .map(|p| {
+            let xy = [p.val(0).to_f64(), p.val(1).to_f64()];
+            match xy {
+                [Some(x), Some(y)] if x.is_finite() && y.is_finite() => [x, y],
+                _ => [f64::NAN; 2],
+            }
+        })
+        .collect();
+    let mut order: Vec<usize> = (0..pts.len()).filter(|&i| !pts[i][0].is_nan()).collect();
+    order.sort_by(|&i, &j| {
+        pts[i]
+            .partial_cmp(&pts[j])
+            .unwrap_or(Ordering::Equal)
+            .then(i.cmp(&j))
+    });
+    order.dedup_by(|j, i| pts[*i] == pts[*j]);
+
+    let mut tris: Vec<[usize; 3]> = Vec::new();
+    //collinear prefix, then the counter-clockwise hull of the swept points
+    let mut chain: Vec<usize> = Vec::new();
+    let mut hull: Vec<usize> = Vec::new();
+    for &p in &order {
+        if hull.is_empty() {
+            let turn = if chain.len() < 2 {
+                0.
+            } else {
+                orient2d(&pts[chain[0]], &pts[chain[chain.len() - 1]], &pts[p])
+            };
+            if turn == 0. {
+                chain.push(p);
+                continue;
+            }
+            for w in chain.windows(2) {
+                tris.push(if turn > 0. {
+                    [w[0], w[1], p]
+                } else {
+                    [w[1], w[0], p]
+                });
+            }
+            if turn < 0. {
+                chain.reverse();
+            }
+            hull = std::mem::take(&mut chain);
+            hull.push(p);
+            continue;
+        }
+        let m = hull.len();
+        let visible: Vec<bool> = (0..m)
+            .map(|i| orient2d(&pts[hull[i]], &pts[hull[(i + 1) % m]], &pts[p]) < 0.)
+            .collect();
+        let start = match (0..m).find(|&i| visible[i] && !visible[(i + m - 1) % m]) {
+            Some(i) => i,
+            None => continue,
+        };
+        let run = (0..m).take_while(|&k| visible[(start + k) % m]).count();
+        for k in 0..run {
+            let (a, b) = (hull[(start + k) % m], hull[(start + k + 1) % m]);
+            tris.push([b, a, p]);
+        }
+        let mut next = Vec::with_capacity(m - run + 2);
+        for k in 0..=(m - run) {
+            next.push(hull[(start + run + k) % m]);
+        }
+        next.push(p);
+        hull = next;
+    }
+
+    //Lawson flips of non-Delaunay interior edges
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    for (t, tri) in tris.iter().enumerate() {
+        for k in 0..3 {
+            edges.insert((tri[k], tri[(k + 1) % 3]), t);
+        }
+    }
+    let mut stack: Vec<(usize, usize)> = edges.keys().copied().collect();
+    while let Some((a, b)) = stack.pop() {
+        let (t1, t2) = match (edges.get(&(a, b)), edges.get(&(b, a))) {
+            (Some(&t1), Some(&t2)) => (t1, t2),
+            _ => continue,
+        };
+        let c = opposite(&tris[t1], a, b);
+        let d = opposite(&tris[t2], b, a);
+        if incircle(&pts[a], &pts[b], &pts[c], &pts[d]) <= 0. {
+            continue;
+        }
+        tris[t1] = [a, d, c];
+        tris[t2] = [d, b, c];
+        edges.remove(&(a, b));
+        edges.remove(&(b, a));
+        edges.insert((a, d), t1);
+        edges.insert((d, c), t1);
+        edges.insert((c, a), t1);
+        edges.insert((d, b), t2);
+        edges.insert((b, c), t2);
+        edges.insert((c, d), t2);
+        stack.extend([(a, d), (d, b), (b, c), (c, a)]);
+    }
+    tris
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift;
+    use crate::tests::Pt;
+
+    fn assert_delaunay(pts: &[Pt<f64>], tris: &[[usize; 3]]) {
+        for t in tris {
+            assert!(orient2d(&pts[t[0]], &pts[t[1]], &pts[t[2]]) > 0.);
+            for (i, p) in pts.iter().enumerate() {
+                if !t.contains(&i) {
+                    let inside = incircle(&pts[t[0]], &pts[t[1]], &pts[t[2]], p);
+                    assert!(inside <= 0.);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delaunay() {
+        let square = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 0. },
+            Pt { x: 1., y: 1. },
+            Pt { x: 0., y: 1. },
+            Pt { x: 0.5, y: 0.4 },
+        ];
+        let tris = delaunay(&square);
+        assert_eq!(tris.len(), 4);
+        assert_delaunay(&square, &tris);
+
+        let mut rng = XorShift::new(7);
+        let pts: Vec<_> = (0..200)
+            .map(|_| Pt {
+                x: rng.next_f64(),
+                y: rng.next_f64(),
+            })
+            .collect();
+        let tris = delaunay(&pts);
+        assert_delaunay(&pts, &tris);
+        //euler: 2n - 2 - h triangles for n points with h hull vertices
+        let hull = crate::algorithms::convex_hull(&pts).len();
+        assert_eq!(tris.len(), 2 * pts.len() - 2 - hull);
+    }
+
+    #[test]
+    fn test_delaunay_degenerate() {
+        //integer grid - many cocircular and collinear points
+        let grid: Vec<_> = (0..16)
+            .map(|i| Pt {
+                x: (i % 4) as f64,
+                y: (i / 4) as f64,
+            })
+            .collect();
+        let tris = delaunay(&grid);
+        assert_eq!(tris.len(), 18);
+        assert_delaunay(&grid, &tris);
+
+        let line: Vec<_> = (0..5)
+            .map(|i| Pt {
+                x: i as f64,
+                y: 2. * i as f64,
+            })
+            .collect();
+        assert!(delaunay(&line).is_empty());
+        let mut dup = line.clone();
+        dup.push(Pt { x: 0., y: 1. });
+        dup.push(Pt { x: 0., y: 1. });
+        dup.push(Pt { x: f64::NAN, y: 1. });
+        let tris = delaunay(&dup);
+        assert_eq!(tris.len(), 4);
+        assert!(tris.iter().all(|t| !t.contains(&6) && !t.contains(&7)));
+        assert!(delaunay::<Pt<f64>>(&[]).is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Coordinate;
+use num_traits::{Float, ToPrimitive, Zero};
+
+///inserts evenly spaced points so that no segment is longer than max_segment_length;
+///original points are kept, a non-positive max length leaves the line unchanged
+pub fn densify<C>(points: &[C], max_segment_length: C::Scalar) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut out = Vec::with_capacity(points.len());
+    let valid = max_segment_length > C::Scalar::zero();
+    for (i, &pt) in points.iter().enumerate() {
+        if i > 0 && valid {
+            let a = points[i - 1];
+            let d = pt.sub(&a);
+            let parts = (d.square_length().sqrt() / max_segment_length).ceil();
+            let n = parts.to_usize().unwrap_or(1);
+            for k in 1..n {
+                let t = num_traits::cast::<_, C::Scalar>(k).unwrap() / parts;
+                out.push(a.add(&d.mult(t)));
+            }
+        }
+        out.push(pt);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_densify() {
+        let line = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 3., y: 0. },
+            Pt { x: 3., y: 1. },
+        ];
+        let out = densify(&line, 1.);
+        assert_eq!(
+            out,
+            vec![
+                Pt { x: 0., y: 0. },
+                Pt { x: 1., y: 0. },
+                Pt { x: 2., y: 0. },
+                Pt { x: 3., y: 0. },
+                Pt { x: 3., y: 1. },
+            ]
+        );
+        let out = densify(&line, 0.8);
+        assert_eq!(out.len(), 1 + 4 + 2);
+        assert!(out.windows(2).all(|w| w[0].square_distance(&w[1]) <= 0.64));
+        assert_eq!(densify(&line, 0.), line.to_vec());
+        assert_eq!(densify(&line[..1], 0.1), line[..1].to_vec());
+    }
+}
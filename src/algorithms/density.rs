@@ -0,0 +1,140 @@
+use crate::{Bounds, Coordinate};
+use num_traits::{Float, ToPrimitive};
+
+///dense N-dimensional grid of cell values - axis 0 varies fastest
+#[derive(Clone, PartialEq, Debug)]
+pub struct Grid<T> {
+    pub shape: Vec<usize>,
+    pub data: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    ///grid of the given shape filled with value
+    pub fn new(shape: Vec<usize>, value: T) -> Self {
+        let n = shape.iter().product();
+        Grid {
+            shape,
+            data: vec![value; n],
+        }
+    }
+
+    ///flat index of a cell, None if out of range
+    pub fn index(&self, cell: &[usize]) -> Option<usize> {
+        if cell.len() != self.shape.len() {
+            return None;
+        }
+        let mut index = 0;
+        for (&c, &n) in cell.iter().zip(&self.shape).rev() {
+            if c >= n {
+                return None;
+            }
+            index = index * n + c;
+        }
+        Some(index)
+    }
+
+    ///value of a cell
+    pub fn get(&self, cell: &[usize]) -> Option<&T> {
+        self.index(cell).map(|i| &self.data[i])
+    }
+}
+
+///flat grid index of the cell containing pt; points on the max boundary
+///fall in the last cell, points outside bounds are None
+fn cell_index<C>(
+    grid_shape: &[usize],
+    bounds: &Bounds<C>,
+    cell_size: C::Scalar,
+    pt: &C,
+) -> Option<usize>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if !bounds.contains(pt) {
+        return None;
+    }
+    let mut index = 0;
+    for i in (0..C::DIM).rev() {
+        let n = grid_shape[i];
+        let c = ((pt.val(i) - bounds.min.val(i)) / cell_size)
+            .floor()
+            .to_usize()?;
+        index = index * n + c.min(n - 1);
+    }
+    Some(index)
+}
+
+fn grid_shape<C>(bounds: &Bounds<C>, cell_size: C::Scalar) -> Vec<usize>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    (0..C::DIM)
+        .map(|i| {
+            let extent = bounds.max.val(i) - bounds.min.val(i);
+            (extent / cell_size).ceil().to_usize().unwrap_or(0).max(1)
+        })
+        .collect()
+}
+
+///counts of points per cell of a grid laid over bounds
+pub fn density_grid<C>(points: &[C], bounds: &Bounds<C>, cell_size: C::Scalar) -> Grid<u32>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut grid = Grid::new(grid_shape(bounds, cell_size), 0);
+    for pt in points {
+        if let Some(i) = cell_index(&grid.shape, bounds, cell_size, pt) {
+            grid.data[i] += 1;
+        }
+    }
+    grid
+}
+
+///sum of weights of points per cell of a grid laid over bounds
+pub fn density_grid_weighted<C>(
+    points: &[(C, f64)],
+    bounds: &Bounds<C>,
+    cell_size: C::Scalar,
+) -> Grid<f64>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut grid = Grid::new(grid_shape(bounds, cell_size), 0.);
+    for (pt, w) in points {
+        if let Some(i) = cell_index(&grid.shape, bounds, cell_size, pt) {
+            grid.data[i] += w;
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_density_grid() {
+        let bounds = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 3., y: 2. });
+        let pts = [
+            Pt { x: 0.5, y: 0.5 },
+            Pt { x: 0.7, y: 0.1 },
+            Pt { x: 2.5, y: 1.5 },
+            Pt { x: 3., y: 2. },
+            Pt { x: 3.5, y: 0. },
+        ];
+        let grid = density_grid(&pts, &bounds, 1.);
+        assert_eq!(grid.shape, vec![3, 2]);
+        assert_eq!(grid.data, vec![2, 0, 0, 0, 0, 2]);
+        assert_eq!(grid.get(&[2, 1]), Some(&2));
+        assert_eq!(grid.get(&[3, 0]), None);
+
+        let weighted: Vec<_> = pts.iter().map(|&p| (p, 0.5)).collect();
+        let grid = density_grid_weighted(&weighted, &bounds, 1.);
+        assert_eq!(grid.data, vec![1., 0., 0., 0., 0., 1.]);
+    }
+}
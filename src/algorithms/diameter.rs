@@ -0,0 +1,91 @@
+use super::{convex_hull, cross2d};
+use crate::Coordinate;
+use bs_num::Zero;
+use std::cmp::{max, min};
+
+///farthest pair of points as (i, j, square distance) with i <= j, None if empty;
+///rotating calipers over the convex hull in 2D, pairwise scan otherwise
+pub fn diameter<C: Coordinate>(points: &[C]) -> Option<(usize, usize, C::Scalar)> {
+    if points.is_empty() {
+        return None;
+    }
+    if C::DIM != 2 {
+        return Some(farthest_pair_scan(points));
+    }
+
+    let hull = convex_hull(points);
+    let n = hull.len();
+    if n < 3 {
+        let (i, j) = (min(hull[0], hull[n - 1]), max(hull[0], hull[n - 1]));
+        return Some((i, j, points[i].square_distance(&points[j])));
+    }
+
+    let pt = |k: usize| &points[hull[k % n]];
+    let mut best = (hull[0], hull[0], Zero::zero());
+    let mut update = |a: usize, b: usize| {
+        let d = points[a].square_distance(&points[b]);
+        if d > best.2 {
+            best = (min(a, b), max(a, b), d);
+        }
+    };
+    let mut j = 1;
+    for i in 0..n {
+        //advance antipodal point while the triangle area on edge (i, i+1) grows
+        while cross2d(pt(i), pt(i + 1), pt(j + 1)) > cross2d(pt(i), pt(i + 1), pt(j)) {
+            j += 1;
+        }
+        update(hull[i], hull[j % n]);
+        update(hull[(i + 1) % n], hull[j % n]);
+    }
+    Some(best)
+}
+
+fn farthest_pair_scan<C: Coordinate>(points: &[C]) -> (usize, usize, C::Scalar) {
+    let mut best = (0, 0, Zero::zero());
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            let d = points[i].square_distance(&points[j]);
+            if d > best.2 {
+                best = (i, j, d);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_diameter() {
+        let pts: Vec<_> = (0..40)
+            .map(|i| {
+                let t = i as f64 * 0.7;
+                Pt {
+                    x: 3. * t.cos() + (i % 3) as f64 * 0.1,
+                    y: t.sin(),
+                }
+            })
+            .collect();
+        let (i, j, d) = diameter(&pts).unwrap();
+        let (si, sj, sd) = farthest_pair_scan(&pts);
+        assert_eq!(d, sd);
+        assert_eq!(
+            pts[i].square_distance(&pts[j]),
+            pts[si].square_distance(&pts[sj])
+        );
+
+        let square = [
+            Pt { x: 0, y: 0 },
+            Pt { x: 2, y: 0 },
+            Pt { x: 1, y: 1 },
+            Pt { x: 2, y: 3 },
+        ];
+        assert_eq!(diameter(&square), Some((0, 3, 13)));
+        assert_eq!(diameter(&square[..1]), Some((0, 0, 0)));
+        assert_eq!(diameter(&square[..2]), Some((0, 1, 4)));
+        assert_eq!(diameter::<Pt<i32>>(&[]), None);
+    }
+}
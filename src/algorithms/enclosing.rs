@@ -0,0 +1,160 @@
+use super::solve;
+use crate::rng::XorShift;
+use crate::{Ball, Coordinate};
+use num_traits::{Float, One, Zero};
+
+///smallest ball enclosing all points (Welzl), None if empty;
+///expected linear time for the low dimensions (DIM <= 3) it is intended for
+pub fn min_enclosing_ball<C>(points: &[C]) -> Option<Ball<C>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.is_empty() {
+        return None;
+    }
+    //deterministic shuffle guards against adversarial input order
+    let mut pts = points.to_vec();
+    XorShift::new(0x9e37_79b9_7f4a_7c15).shuffle(&mut pts);
+    let mut boundary = Vec::with_capacity(C::DIM + 1);
+    Some(welzl(&pts, &mut boundary))
+}
+
+//recursion depth is bounded by the boundary size - DIM + 1
+fn welzl<C>(points: &[C], boundary: &mut Vec<C>) -> Ball<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut ball = ball_on(boundary);
+    if boundary.len() == C::DIM + 1 {
+        return ball;
+    }
+    for i in 0..points.len() {
+        if !loosely_contains(&ball, &points[i]) {
+            boundary.push(points[i]);
+            ball = welzl(&points[..i], boundary);
+            boundary.pop();
+        }
+    }
+    ball
+}
+
+fn loosely_contains<C>(ball: &Ball<C>, pt: &C) -> bool
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let r2 = ball.square_radius();
+    let tol = C::Scalar::epsilon() * num_traits::cast(1024).unwrap();
+    ball.center.square_distance(pt) <= r2 + tol * (r2 + C::Scalar::one())
+}
+
+///smallest ball with all boundary points on its surface
+fn ball_on<C>(boundary: &[C]) -> Ball<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let zero = C::Scalar::zero();
+    match boundary.len() {
+        0 => return Ball::new(C::new_origin(), zero),
+        1 => return Ball::new(boundary[0], zero),
+        _ => {}
+    }
+    //center = p0 + sum(l_i * v_i), v_i = p_i - p0 with |center - p_i| equal,
+    //i.e. sum_j 2(v_i.v_j) l_j = v_i.v_i
+    let p0 = boundary[0];
+    let v: Vec<C> = boundary[1..].iter().map(|p| p.sub(&p0)).collect();
+    let k = v.len();
+    let dot = |a: &C, b: &C| {
+        a.component_wise(b, |x, y| x * y)
+            .fold(zero, |acc, x| acc + x)
+    };
+    let mut m = vec![vec![zero; k + 1]; k];
+    for i in 0..k {
+        for j in 0..k {
+            let d = dot(&v[i], &v[j]);
+            m[i][j] = d + d;
+        }
+        m[i][k] = dot(&v[i], &v[i]);
+    }
+    match solve(m) {
+        Some(lambda) => {
+            let mut center = p0;
+            for (l, vi) in lambda.iter().zip(&v) {
+                center = center.add(&vi.mult(*l));
+            }
+            Ball::new(center, center.square_distance(&p0).sqrt())
+        }
+        //degenerate (e.g. collinear) boundary: ball over its farthest pair
+        None => {
+            let half = C::Scalar::one() / (C::Scalar::one() + C::Scalar::one());
+            let mut ball = Ball::new(p0, zero);
+            for i in 0..boundary.len() {
+                for j in (i + 1)..boundary.len() {
+                    let (a, b) = (boundary[i], boundary[j]);
+                    let r = a.square_distance(&b).sqrt() * half;
+                    if r > ball.radius {
+                        ball = Ball::new(a.add(&b).mult(half), r);
+                    }
+                }
+            }
+            ball
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_min_enclosing_ball() {
+        let pts = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 4., y: 0. },
+            Pt { x: 2., y: 1. },
+            Pt { x: 1., y: 0.5 },
+        ];
+        let ball = min_enclosing_ball(&pts).unwrap();
+        assert!((ball.center.x - 2.).abs() < 1e-12 && ball.center.y.abs() < 1e-12);
+        assert!((ball.radius - 2.).abs() < 1e-12);
+
+        //equilateral triangle - circumcircle
+        let h = 3f64.sqrt();
+        let tri = [
+            Pt { x: -1., y: 0. },
+            Pt { x: 1., y: 0. },
+            Pt { x: 0., y: h },
+        ];
+        let ball = min_enclosing_ball(&tri).unwrap();
+        assert!((ball.center.y - h / 3.).abs() < 1e-12);
+        assert!((ball.radius - 2. * h / 3.).abs() < 1e-12);
+
+        let ring: Vec<_> = (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.1;
+                Pt {
+                    x: 5. + 3. * t.cos() * (1. - (i % 7) as f64 * 0.01),
+                    y: -2. + 3. * t.sin(),
+                }
+            })
+            .collect();
+        let ball = min_enclosing_ball(&ring).unwrap();
+        assert!(ring.iter().all(|p| loosely_contains(&ball, p)));
+        assert!(ball.radius <= 3. + 1e-9);
+
+        let collinear = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 1. },
+            Pt { x: 3., y: 3. },
+        ];
+        let ball = min_enclosing_ball(&collinear).unwrap();
+        assert!((ball.center.x - 1.5).abs() < 1e-12 && (ball.center.y - 1.5).abs() < 1e-12);
+
+        assert_eq!(min_enclosing_ball(&pts[..1]), Some(Ball::new(pts[0], 0.)));
+        assert_eq!(min_enclosing_ball::<Pt<f64>>(&[]), None);
+    }
+}
@@ -0,0 +1,304 @@
+use super::{centroid, solve};
+use crate::{Ball, Coordinate};
+use num_traits::{Float, One, Zero};
+use std::cmp::Ordering;
+
+///covariance (scatter) matrix of points about their centroid
+fn scatter<C>(points: &[C], center: &C) -> Vec<Vec<C::Scalar>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut m = vec![vec![C::Scalar::zero(); C::DIM]; C::DIM];
+    for pt in points {
+        let d = pt.sub(center);
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = *v + d.val(i) * d.val(j);
+            }
+        }
+    }
+    m
+}
+
+///eigen decomposition of a symmetric matrix (cyclic Jacobi) - eigenvalues
+///and the matching unit eigenvectors, sorted by ascending eigenvalue
+fn symmetric_eigen<T: Float>(mut a: Vec<Vec<T>>) -> Vec<(T, Vec<T>)> {
+    let n = a.len();
+    let mut v: Vec<Vec<T>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { T::one() } else { T::zero() })
+                .collect()
+        })
+        .collect();
+    let two = T::one() + T::one();
+    for _ in 0..64 {
+        let off = (0..n)
+            .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+            .fold(T::zero(), |acc, (i, j)| acc + a[i][j] * a[i][j]);
+        let diag = (0..n).fold(T::zero(), |acc, i| acc + a[i][i] * a[i][i]);
+        if off <= T::epsilon() * T::epsilon() * diag || off == T::zero() {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q] == T::zero() {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (two * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                let c = (t * t + T::one()).sqrt().recip();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (top, bottom) = a.split_at_mut(q);
+                for (apk, aqk) in top[p].iter_mut().zip(bottom[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    let mut pairs: Vec<(T, Vec<T>)> = (0..n)
+        .map(|j| (a[j][j], v.iter().map(|row| row[j]).collect()))
+        .collect();
+    pairs.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+    pairs
+}
+
+///unit coordinate from a vector, sign fixed so the first non-zero component is positive
+fn unit_axis<C>(vals: &[C::Scalar]) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let axis = C::gen(|i| vals[i]);
+    let len = axis.square_length().sqrt();
+    let neg = vals
+        .iter()
+        .find(|v| **v != C::Scalar::zero())
+        .is_some_and(|v| *v < C::Scalar::zero());
+    let k = if neg { -len.recip() } else { len.recip() };
+    axis.mult(k)
+}
+
+///total least squares line fit (major principal axis) - a point on the line (centroid)
+///and a unit direction; None if there are fewer than two distinct points
+pub fn fit_line<C>(points: &[C]) -> Option<(C, C)>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let center = centroid(points)?;
+    let (value, axis) = symmetric_eigen(scatter(points, &center)).pop()?;
+    if value.is_nan() || value <= C::Scalar::zero() {
+        return None;
+    }
+    Some((center, unit_axis(&axis)))
+}
+
+///least squares plane (hyperplane in N dimensions) fit - the centroid and a unit normal
+///(minor principal axis); None if the points do not span a plane
+pub fn fit_plane<C>(points: &[C]) -> Option<(C, C)>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let center = centroid(points)?;
+    let eigen = symmetric_eigen(scatter(points, &center));
+    let spread = eigen.get(1)?.0;
+    let tiny = eigen[eigen.len() - 1].0 * C::Scalar::epsilon() * num_traits::cast(64).unwrap();
+    if spread.is_nan() || spread <= tiny {
+        return None;
+    }
+    Some((center, unit_axis(&eigen[0].1)))
+}
+
+///algebraic (Kasa) least squares circle fit (sphere in 3D); None if there are too few
+///points or they are collinear (coplanar in 3D)
+pub fn fit_circle<C>(points: &[C]) -> Option<Ball<C>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.len() <= C::DIM {
+        return None;
+    }
+    //x.x + d.x + f = 0 about the centroid, normal equations over rows [x, 1 | -x.x];
+    //points are divided by their rms radius so the matrix is well scaled at any size
+    let center = centroid(points)?;
+    let count: C::Scalar = num_traits::cast(points.len())?;
+    let rms = (points.iter().fold(C::Scalar::zero(), |acc, pt| {
+        acc + pt.square_distance(&center)
+    }) / count)
+        .sqrt();
+    if !rms.is_finite() || rms <= C::Scalar::zero() {
+        return None;
+    }
+    let k = C::DIM + 1;
+    let mut m = vec![vec![C::Scalar::zero(); k + 1]; k];
+    let mut row = vec![C::Scalar::one(); k + 1];
+    for pt in points {
+        let d = pt.sub(&center).mult(C::Scalar::one() / rms);
+        for (i, v) in row.iter_mut().enumerate().take(C::DIM) {
+            *v = d.val(i);
+        }
+        row[k] = -d.square_length();
+        for (i, mrow) in m.iter_mut().enumerate() {
+            for (v, r) in mrow.iter_mut().zip(&row) {
+                *v = *v + row[i] * *r;
+            }
+        }
+    }
+    let x = solve(m)?;
+    let two = C::Scalar::one() + C::Scalar::one();
+    let offset = C::gen(|i| -x[i] / two);
+    let square_radius = offset.square_length() - x[C::DIM];
+    if square_radius.is_nan() || square_radius < C::Scalar::zero() {
+        return None;
+    }
+    Some(Ball::new(
+        center.add(&offset.mult(rms)),
+        square_radius.sqrt() * rms,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    fn close<C: Coordinate<Scalar = f64>>(a: &C, b: &C) -> bool {
+        a.square_distance(b) < 1e-18
+    }
+
+    #[test]
+    fn test_fit_line() {
+        //y = 2x + 1 with alternating noise; orthogonal fit is not y-on-x
+        let pts: Vec<_> = (0..10)
+            .map(|i| {
+                let x = i as f64;
+                Pt {
+                    x,
+                    y: 2. * x + 1. + if i % 2 == 0 { 0.1 } else { -0.1 },
+                }
+            })
+            .collect();
+        let (p, d) = fit_line(&pts).unwrap();
+        assert!((p.x - 4.5).abs() < 1e-12 && (p.y - 10.).abs() < 1e-12);
+        assert!((d.y / d.x - 2.).abs() < 0.01);
+        assert!((d.square_length() - 1.).abs() < 1e-12);
+
+        let vertical = [Pt { x: 3., y: 5. }, Pt { x: 3., y: -1. }];
+        let (p, d) = fit_line(&vertical).unwrap();
+        assert!(close(&p, &Pt { x: 3., y: 2. }) && close(&d, &Pt { x: 0., y: 1. }));
+
+        let (_, d) = fit_line(&[Coord([0., 0., 0.]), Coord([-1., -1., -1.])]).unwrap();
+        let k = 1. / 3f64.sqrt();
+        assert!(close(&d, &Coord([k, k, k])));
+
+        assert!(fit_line(&[Pt { x: 1., y: 1. }; 3]).is_none());
+        assert!(fit_line::<Pt<f64>>(&[]).is_none());
+    }
+
+    #[test]
+    fn test_fit_plane() {
+        //z = 0.5x - y + 2 sampled on a grid with symmetric noise
+        let pts: Vec<_> = (0..25)
+            .map(|i| {
+                let (x, y) = ((i % 5) as f64, (i / 5) as f64);
+                let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+                Coord([x, y, 0.5 * x - y + 2. + noise])
+            })
+            .collect();
+        let (p, n) = fit_plane(&pts).unwrap();
+        assert!((p.0[2] - (0.5 * 2. - 2. + 2.)).abs() < 1e-2);
+        let expect = Coord([0.5, -1., -1.]);
+        let expect = expect.mult(1. / expect.square_length().sqrt());
+        assert!(n.square_distance(&expect) < 1e-4);
+
+        let ground = [
+            Coord([0., 0., 1.]),
+            Coord([4., 0., 1.]),
+            Coord([0., 3., 1.]),
+            Coord([5., 5., 1.]),
+        ];
+        let (p, n) = fit_plane(&ground).unwrap();
+        assert!((p.0[2] - 1.).abs() < 1e-12 && close(&n, &Coord([0., 0., 1.])));
+
+        let line = [
+            Coord([0., 0., 0.]),
+            Coord([1., 1., 1.]),
+            Coord([2., 2., 2.]),
+        ];
+        assert!(fit_plane(&line).is_none());
+        assert!(fit_plane::<Coord<f64, 3>>(&[]).is_none());
+    }
+
+    #[test]
+    fn test_fit_circle() {
+        let pts: Vec<_> = (0..12)
+            .map(|i| {
+                let t = i as f64 * std::f64::consts::PI / 6.;
+                let r = if i % 2 == 0 { 2.01 } else { 1.99 };
+                Pt {
+                    x: 5. + r * t.cos(),
+                    y: -3. + r * t.sin(),
+                }
+            })
+            .collect();
+        let ball = fit_circle(&pts).unwrap();
+        assert!(ball.center.square_distance(&Pt { x: 5., y: -3. }) < 1e-6);
+        assert!((ball.radius - 2.).abs() < 1e-3);
+
+        //partial arc
+        let arc = [
+            Pt { x: 1., y: 0. },
+            Pt { x: 0., y: 1. },
+            Pt { x: -1., y: 0. },
+        ];
+        let ball = fit_circle(&arc).unwrap();
+        assert!(close(&ball.center, &Pt { x: 0., y: 0. }) && (ball.radius - 1.).abs() < 1e-12);
+
+        //tiny and huge circles fit to relative precision
+        for &(r, c) in &[(1e-6, 1e-3), (1e7, 3e9)] {
+            let pts: Vec<_> = (0..8)
+                .map(|i| {
+                    let t = i as f64 * std::f64::consts::PI / 4.;
+                    Pt {
+                        x: c + r * t.cos(),
+                        y: -c + r * t.sin(),
+                    }
+                })
+                .collect();
+            let ball = fit_circle(&pts).unwrap();
+            assert!((ball.radius - r).abs() < r * 1e-6);
+            assert!(ball.center.square_distance(&Pt { x: c, y: -c }).sqrt() < r * 1e-6);
+        }
+
+        let sphere = [
+            Coord([1., 0., 0.]),
+            Coord([-1., 0., 0.]),
+            Coord([0., 1., 0.]),
+            Coord([0., 0., 1.]),
+            Coord([0., 0., -1.]),
+        ];
+        let ball = fit_circle(&sphere).unwrap();
+        assert!(close(&ball.center, &Coord([0., 0., 0.])) && (ball.radius - 1.).abs() < 1e-12);
+
+        let line: Vec<_> = (0..4).map(|i| Pt { x: i as f64, y: 0. }).collect();
+        assert!(fit_circle(&line).is_none());
+        assert!(fit_circle(&arc[..2]).is_none());
+    }
+}
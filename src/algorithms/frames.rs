@@ -0,0 +1,148 @@
+use crate::{Coordinate, Coordinate3, CoordinateFloat};
+use num_traits::{Float, One, Zero};
+
+///rotation minimizing (tangent, normal, binormal) frames at each point of a 3D polyline;
+///tangents bisect the adjacent segments and normals are carried along by double
+///reflection (Wang et al. 2008), so they do not flip at inflections the way Frenet
+///normals do. Empty if the points are all equal
+pub fn parallel_transport_frames<C>(points: &[C]) -> Vec<(C, C, C)>
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    let tangents = match polyline_tangents(points) {
+        Some(t) => t,
+        None => return Vec::new(),
+    };
+    let mut normal = initial_normal(&tangents[0]);
+    let mut frames = Vec::with_capacity(points.len());
+    frames.push((tangents[0], normal, tangents[0].cross(&normal)));
+    for i in 1..points.len() {
+        let v1 = points[i].sub(&points[i - 1]);
+        let (r, t) = (reflect(&normal, &v1), reflect(&tangents[i - 1], &v1));
+        let v2 = tangents[i].sub(&t);
+        normal = reflect(&r, &v2).normalize().unwrap_or(r);
+        frames.push((tangents[i], normal, tangents[i].cross(&normal)));
+    }
+    frames
+}
+
+///unit tangents bisecting the segments at each point, repeated points skipped;
+///None if there is no segment of non zero length
+fn polyline_tangents<C>(points: &[C]) -> Option<Vec<C>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let dirs: Vec<Option<C>> = points
+        .windows(2)
+        .map(|w| w[1].sub(&w[0]).normalize())
+        .collect();
+    dirs.iter().find_map(|d| *d)?;
+    //nearest non zero segment direction before and after each point
+    let mut incoming = vec![None; points.len()];
+    for i in 1..points.len() {
+        incoming[i] = dirs[i - 1].or(incoming[i - 1]);
+    }
+    let mut outgoing = vec![None; points.len()];
+    for i in (0..dirs.len()).rev() {
+        outgoing[i] = dirs[i].or(outgoing[i + 1]);
+    }
+    let tangents = incoming
+        .into_iter()
+        .zip(outgoing)
+        .map(|(a, b)| match (a, b) {
+            (Some(a), Some(b)) => a.add(&b).normalize().unwrap_or(a),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => unreachable!(),
+        })
+        .collect();
+    Some(tangents)
+}
+
+///unit vector perpendicular to t, towards the axis t is least aligned with
+fn initial_normal<C>(t: &C) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let axis = (1..3).fold(0, |k, i| {
+        if t.val(i).abs() < t.val(k).abs() {
+            i
+        } else {
+            k
+        }
+    });
+    let e = C::gen(|i| {
+        if i == axis {
+            C::Scalar::one()
+        } else {
+            C::Scalar::zero()
+        }
+    });
+    e.sub(&t.mult(t.dot(&e))).normalize().unwrap_or(e)
+}
+
+///reflection of v in the plane through the origin with normal n
+fn reflect<C>(v: &C, n: &C) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let nn = n.square_length();
+    if nn == C::Scalar::zero() {
+        return *v;
+    }
+    let two = C::Scalar::one() + C::Scalar::one();
+    v.sub(&n.mult(two * v.dot(n) / nn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn near(a: &Coord<f64, 3>, b: &Coord<f64, 3>) -> bool {
+        a.square_distance(b) < 1e-20
+    }
+
+    #[test]
+    fn test_parallel_transport_frames() {
+        //planar path - the binormal stays on the plane normal throughout
+        let path = [
+            Coord([0., 0., 0.]),
+            Coord([1., 0., 0.]),
+            Coord([1., 0., 0.]),
+            Coord([1., 1., 0.]),
+            Coord([2., 2., 0.]),
+            Coord([3., 2., 0.]),
+        ];
+        let frames = parallel_transport_frames(&path);
+        assert_eq!(frames.len(), path.len());
+        assert!(near(&frames[0].1, &Coord([0., 1., 0.])));
+        assert!(frames.iter().all(|f| near(&f.2, &Coord([0., 0., 1.]))));
+        let s = 0.5f64.sqrt();
+        assert!(near(&frames[1].0, &Coord([s, s, 0.])));
+        assert!(near(&frames[1].1, &Coord([-s, s, 0.])));
+        assert!(near(&frames[2].0, &frames[1].0));
+        assert!(near(&frames[5].0, &Coord([1., 0., 0.])));
+        assert!(near(&frames[5].1, &Coord([0., 1., 0.])));
+
+        //helix - frames stay orthonormal and normals turn smoothly
+        let helix: Vec<_> = (0..60)
+            .map(|i| {
+                let a = i as f64 * 0.2;
+                Coord([a.cos(), a.sin(), 0.1 * a])
+            })
+            .collect();
+        let frames = parallel_transport_frames(&helix);
+        for (t, n, b) in &frames {
+            assert!((t.length() - 1.).abs() < 1e-12 && (n.length() - 1.).abs() < 1e-12);
+            assert!(t.dot(n).abs() < 1e-12 && (b.length() - 1.).abs() < 1e-12);
+        }
+        assert!(frames.windows(2).all(|w| w[0].1.dot(&w[1].1) > 0.97));
+
+        assert!(parallel_transport_frames(&[Coord([1., 2., 3.]); 3]).is_empty());
+        assert!(parallel_transport_frames::<Coord<f64, 3>>(&[]).is_empty());
+    }
+}
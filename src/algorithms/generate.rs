@@ -0,0 +1,167 @@
+use crate::Coordinate;
+use num_traits::{Float, FloatConst, One, ToPrimitive, Zero};
+
+fn on_circle<C>(center: &C, radius: C::Scalar, angle: C::Scalar) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (sin, cos) = angle.sin_cos();
+    let mut pt = *center;
+    *pt.val_mut(0) = center.val(0) + radius * cos;
+    *pt.val_mut(1) = center.val(1) + radius * sin;
+    pt
+}
+
+///n points evenly spaced counter-clockwise on a circle, starting at angle 0
+pub fn points_on_circle<C>(center: C, radius: C::Scalar, n: usize) -> impl Iterator<Item = C>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let step = C::Scalar::PI() * num_traits::cast(2.0 / n as f64).unwrap();
+    (0..n).map(move |i| on_circle(&center, radius, step * num_traits::cast(i).unwrap()))
+}
+
+///n points spread with uniform density over the annulus inner <= r <= outer
+///(Vogel sunflower spiral - deterministic)
+pub fn points_in_annulus<C>(
+    center: C,
+    inner: C::Scalar,
+    outer: C::Scalar,
+    n: usize,
+) -> impl Iterator<Item = C>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    //golden angle = pi * (3 - sqrt(5))
+    let five: C::Scalar = num_traits::cast(5).unwrap();
+    let three: C::Scalar = num_traits::cast(3).unwrap();
+    let golden = C::Scalar::PI() * (three - five.sqrt());
+    let (r2_in, r2_out) = (inner * inner, outer * outer);
+    (0..n).map(move |i| {
+        let t: C::Scalar = num_traits::cast((i as f64 + 0.5) / n as f64).unwrap();
+        let r = (r2_in + t * (r2_out - r2_in)).sqrt();
+        on_circle(&center, r, golden * num_traits::cast(i).unwrap())
+    })
+}
+
+///most chords `arc_points` splits an arc into
+pub const MAX_ARC_SEGMENTS: usize = 1 << 16;
+
+///points along the arc from start_angle to end_angle (counter-clockwise if end is
+///greater), end points included, spaced so that no chord strays more than
+///max_deviation from the arc; a full turn gives a closed ring. The chords are
+///capped at `MAX_ARC_SEGMENTS`; a non-positive max_deviation leaves just the end points
+pub fn arc_points<C>(
+    center: C,
+    radius: C::Scalar,
+    start_angle: C::Scalar,
+    end_angle: C::Scalar,
+    max_deviation: C::Scalar,
+) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let one = C::Scalar::one();
+    let two = one + one;
+    let sweep = end_angle - start_angle;
+    //sagitta r (1 - cos(step / 2)) = 2r sin²(step / 4) <= max_deviation, without
+    //the cancellation of 1 - max_deviation / r for small deviations
+    let ratio = (max_deviation / (two * radius.abs())).min(one);
+    let half = two * ratio.sqrt().asin();
+    let n = if half > C::Scalar::zero() {
+        (sweep.abs() / (half + half))
+            .ceil()
+            .to_usize()
+            .unwrap_or(1)
+            .clamp(1, MAX_ARC_SEGMENTS)
+    } else {
+        1
+    };
+    let step = sweep / num_traits::cast(n).unwrap();
+    let mut pts: Vec<C> = (0..n)
+        .map(|i| {
+            on_circle(
+                &center,
+                radius,
+                start_angle + step * num_traits::cast(i).unwrap(),
+            )
+        })
+        .collect();
+    pts.push(on_circle(&center, radius, end_angle));
+    pts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_points_on_circle() {
+        let pts: Vec<_> = points_on_circle(Pt { x: 1., y: -1. }, 2., 4).collect();
+        let expect = [
+            Pt { x: 3., y: -1. },
+            Pt { x: 1., y: 1. },
+            Pt { x: -1., y: -1. },
+            Pt { x: 1., y: -3. },
+        ];
+        assert_eq!(pts.len(), 4);
+        for (p, e) in pts.iter().zip(expect.iter()) {
+            assert!(p.square_distance(e) < 1e-24);
+        }
+        assert_eq!(points_on_circle(Pt { x: 0., y: 0. }, 1., 0).count(), 0);
+    }
+
+    #[test]
+    fn test_points_in_annulus() {
+        let center = Pt { x: 5., y: 5. };
+        let pts: Vec<_> = points_in_annulus(center, 1., 3., 500).collect();
+        assert_eq!(pts.len(), 500);
+        assert!(pts.iter().all(|p| {
+            let d2 = p.square_distance(&center);
+            (1. ..=9.).contains(&d2)
+        }));
+        //uniform density - inner radius sqrt(5) splits the area in half
+        let inside = pts
+            .iter()
+            .filter(|p| p.square_distance(&center) < 5.)
+            .count();
+        assert!((inside as i32 - 250).abs() <= 1);
+    }
+
+    #[test]
+    fn test_arc_points() {
+        let center = Pt { x: 1., y: 1. };
+        let quarter = arc_points(center, 2., 0., f64::FRAC_PI_2(), 0.1);
+        //a step of 2 acos(0.95) ~ 0.635 needs 3 chords for a quarter turn
+        assert_eq!(quarter.len(), 4);
+        assert_eq!(quarter[0], Pt { x: 3., y: 1. });
+        assert_eq!(quarter[3], on_circle(&center, 2., f64::FRAC_PI_2()));
+        assert!(quarter
+            .iter()
+            .all(|p| (p.square_distance(&center) - 4.).abs() < 1e-12));
+        for w in quarter.windows(2) {
+            let mid = w[0].add(&w[1]).mult(0.5);
+            assert!(2. - mid.square_distance(&center).sqrt() <= 0.1);
+        }
+
+        //clockwise full turn closes the ring
+        let ring = arc_points(center, 2., f64::PI(), -f64::PI(), 0.01);
+        assert!(ring.len() > 8);
+        assert!(ring[0].square_distance(&ring[ring.len() - 1]) < 1e-24);
+        assert!(ring[1].y > center.y);
+
+        assert_eq!(arc_points(center, 2., 0., 1., 0.).len(), 2);
+        assert_eq!(arc_points(center, 2., 1., 1., 0.1).len(), 2);
+
+        //tighter tolerances never give coarser arcs, down to the cap
+        let fine = arc_points(center, 1., 0., f64::FRAC_PI_2(), 1e-9);
+        assert_eq!(fine.len(), 17564);
+        let tight = arc_points(center, 1., 0., f64::FRAC_PI_2(), 1e-17);
+        assert_eq!(tight.len(), MAX_ARC_SEGMENTS + 1);
+    }
+}
@@ -0,0 +1,70 @@
+use super::cross2d;
+use crate::Coordinate;
+use bs_num::Zero;
+use std::cmp::Ordering;
+
+///indices of the 2D convex hull in counter-clockwise order starting at the
+///lowest-leftmost point (Andrew's monotone chain); collinear points are dropped
+pub fn convex_hull<C: Coordinate>(points: &[C]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (pa, pb) = (&points[a], &points[b]);
+        pa.val(0)
+            .partial_cmp(&pb.val(0))
+            .unwrap_or(Ordering::Equal)
+            .then(pa.val(1).partial_cmp(&pb.val(1)).unwrap_or(Ordering::Equal))
+    });
+    order.dedup_by(|a, b| points[*a] == points[*b]);
+    if order.len() < 3 {
+        return order;
+    }
+
+    let mut hull: Vec<usize> = Vec::with_capacity(2 * order.len());
+    let turns_left = |hull: &Vec<usize>, i: usize| {
+        let n = hull.len();
+        cross2d(&points[hull[n - 2]], &points[hull[n - 1]], &points[i]) > Zero::zero()
+    };
+    //lower chain
+    for &i in &order {
+        while hull.len() >= 2 && !turns_left(&hull, i) {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    //upper chain
+    let lower = hull.len() + 1;
+    for &i in order.iter().rev().skip(1) {
+        while hull.len() >= lower && !turns_left(&hull, i) {
+            hull.pop();
+        }
+        hull.push(i);
+    }
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_convex_hull() {
+        let pts = [
+            Pt { x: 0, y: 0 },
+            Pt { x: 2, y: 1 },
+            Pt { x: 4, y: 0 },
+            Pt { x: 4, y: 4 },
+            Pt { x: 2, y: 4 },
+            Pt { x: 0, y: 4 },
+            Pt { x: 1, y: 2 },
+            Pt { x: 4, y: 0 },
+        ];
+        assert_eq!(convex_hull(&pts), vec![0, 2, 3, 5]);
+        assert_eq!(convex_hull(&pts[..2]), vec![0, 1]);
+        assert_eq!(convex_hull(&[pts[2], pts[2]]), vec![0]);
+        let line = [Pt { x: 0, y: 0 }, Pt { x: 2, y: 2 }, Pt { x: 1, y: 1 }];
+        assert_eq!(convex_hull(&line), vec![0, 1]);
+        assert!(convex_hull::<Pt<i32>>(&[]).is_empty());
+    }
+}
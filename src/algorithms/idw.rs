@@ -0,0 +1,96 @@
+use crate::{Coordinate, SpatialHash};
+use num_traits::{Float, ToPrimitive};
+
+///inverse distance weighted interpolator over scattered samples,
+///only samples within radius of a query contribute
+pub struct Idw<C: Coordinate> {
+    hash: SpatialHash<C>,
+    values: Vec<f64>,
+    radius: C::Scalar,
+}
+
+impl<C> Idw<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///indexes samples for queries with the given search radius
+    pub fn new(samples: &[(C, f64)], radius: C::Scalar) -> Self {
+        let mut hash = SpatialHash::new(radius);
+        let mut values = Vec::with_capacity(samples.len());
+        for (pt, v) in samples {
+            hash.insert(*pt);
+            values.push(*v);
+        }
+        Idw {
+            hash,
+            values,
+            radius,
+        }
+    }
+
+    ///weighted mean of sample values with weights 1 / d^power,
+    ///the exact value at a sample location, None without samples in radius
+    pub fn interpolate(&self, query: &C, power: f64) -> Option<f64> {
+        let (mut sum, mut weights) = (0., 0.);
+        for i in self.hash.within_radius(query, self.radius) {
+            let d2 = self.hash.point(i).square_distance(query).to_f64()?;
+            if d2 == 0. {
+                return Some(self.values[i]);
+            }
+            let w = d2.powf(-power / 2.);
+            sum += w * self.values[i];
+            weights += w;
+        }
+        if weights > 0. {
+            Some(sum / weights)
+        } else {
+            None
+        }
+    }
+}
+
+///inverse distance weighted value at query from samples within radius,
+///see `Idw` to reuse the neighbour index across queries
+pub fn idw_interpolate<C>(
+    samples: &[(C, f64)],
+    query: &C,
+    power: f64,
+    radius: C::Scalar,
+) -> Option<f64>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    Idw::new(samples, radius).interpolate(query, power)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_idw_interpolate() {
+        let samples = [
+            (Pt { x: 0., y: 0. }, 1.),
+            (Pt { x: 2., y: 0. }, 3.),
+            (Pt { x: 10., y: 10. }, 100.),
+        ];
+        //equidistant samples - plain mean, far sample out of radius
+        assert_eq!(
+            idw_interpolate(&samples, &Pt { x: 1., y: 0. }, 2., 3.),
+            Some(2.)
+        );
+        assert_eq!(
+            idw_interpolate(&samples, &Pt { x: 2., y: 0. }, 2., 3.),
+            Some(3.)
+        );
+
+        let idw = Idw::new(&samples, 3.);
+        //distances 0.5 and 1.5 give weights 1/0.25 and 1/2.25
+        let v = idw.interpolate(&Pt { x: 0.5, y: 0. }, 2.).unwrap();
+        assert!((v - (1. / 0.25 + 3. / 2.25) / (1. / 0.25 + 1. / 2.25)).abs() < 1e-12);
+        assert_eq!(idw.interpolate(&Pt { x: 5., y: 5. }, 2.), None);
+    }
+}
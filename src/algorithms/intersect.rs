@@ -0,0 +1,215 @@
+use super::ring::open_ring;
+use crate::Coordinate;
+use num_traits::{Float, One, Zero};
+use std::cmp::Ordering;
+
+fn perp_dot<C: Coordinate>(u: &C, v: &C) -> C::Scalar {
+    u.val(0) * v.val(1) - u.val(1) * v.val(0)
+}
+
+fn dot2<C: Coordinate>(u: &C, v: &C) -> C::Scalar {
+    u.val(0) * v.val(0) + u.val(1) * v.val(1)
+}
+
+///intersection of segments a0-a1 and b0-b1 on the first two dimensions, None if
+///disjoint; collinear overlaps give the overlap end nearest a0. The point lies on
+///segment a - later dimensions are interpolated along it
+pub fn segment_intersection<C>(a0: &C, a1: &C, b0: &C, b1: &C) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (zero, one) = (C::Scalar::zero(), C::Scalar::one());
+    let (da, db, r) = (a1.sub(a0), b1.sub(b0), b0.sub(a0));
+    let denom = perp_dot(&da, &db);
+    if denom != zero {
+        let t = perp_dot(&r, &db) / denom;
+        let u = perp_dot(&r, &da) / denom;
+        if t >= zero && t <= one && u >= zero && u <= one {
+            return Some(a0.add(&da.mult(t)));
+        }
+        return None;
+    }
+    if perp_dot(&r, &da) != zero || perp_dot(&r, &db) != zero {
+        return None;
+    }
+    //collinear (or degenerate) - overlap of parameter ranges along a
+    let len2 = dot2(&da, &da);
+    if len2 == zero {
+        let on_b = if dot2(&db, &db) == zero {
+            dot2(&r, &r) == zero
+        } else {
+            point_on_collinear(a0, b0, b1)
+        };
+        return if on_b { Some(*a0) } else { None };
+    }
+    let t0 = dot2(&r, &da) / len2;
+    let t1 = t0 + dot2(&db, &da) / len2;
+    let lo = t0.min(t1).max(zero);
+    let hi = t0.max(t1).min(one);
+    if lo <= hi {
+        Some(a0.add(&da.mult(lo)))
+    } else {
+        None
+    }
+}
+
+//p collinear with segment b0-b1 - checks it lies within it
+fn point_on_collinear<C>(p: &C, b0: &C, b1: &C) -> bool
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let db = b1.sub(b0);
+    let len2 = dot2(&db, &db);
+    let t = dot2(&p.sub(b0), &db);
+    len2 > Zero::zero() && t >= Zero::zero() && t <= len2
+}
+
+///crossings and touches between edges of a ring (open or closed) on the first
+///two dimensions as (i, j, point) with edge i from ring[i] to ring[i + 1], i < j;
+///neighbouring edges only report a fold back along each other.
+///Edges are swept in x order and only tested while their x ranges overlap
+pub fn find_self_intersections<C>(ring: &[C]) -> Vec<(usize, usize, C)>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let ring = open_ring(ring);
+    let n = ring.len();
+    let mut found = Vec::new();
+    if n < 3 {
+        return found;
+    }
+    let edge = |i: usize| (&ring[i], &ring[(i + 1) % n]);
+    let x_range = |i: usize| {
+        let (a, b) = edge(i);
+        (a.val(0).min(b.val(0)), a.val(0).max(b.val(0)))
+    };
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        x_range(a)
+            .0
+            .partial_cmp(&x_range(b).0)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut active: Vec<usize> = Vec::new();
+    for &e in &order {
+        let lo = x_range(e).0;
+        active.retain(|&a| x_range(a).1 >= lo);
+        for &a in &active {
+            let (i, j) = (a.min(e), a.max(e));
+            let hit = if j == i + 1 || (i == 0 && j == n - 1) {
+                let (first, second) = if j == i + 1 { (i, j) } else { (j, i) };
+                fold_back(edge(first).0, edge(first).1, edge(second).1)
+            } else {
+                let ((a0, a1), (b0, b1)) = (edge(i), edge(j));
+                segment_intersection(a0, a1, b0, b1)
+            };
+            if let Some(pt) = hit {
+                found.push((i, j, pt));
+            }
+        }
+        active.push(e);
+    }
+    found.sort_by_key(|a| (a.0, a.1));
+    found
+}
+
+//edges p-s and s-q overlapping beyond the shared vertex s - the end of the
+//overlap nearest s
+fn fold_back<C>(p: &C, s: &C, q: &C) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (u, v) = (p.sub(s), q.sub(s));
+    if perp_dot(&u, &v) != Zero::zero() || dot2(&u, &v) <= Zero::zero() {
+        return None;
+    }
+    if dot2(&u, &u) <= dot2(&v, &v) {
+        Some(*p)
+    } else {
+        Some(*q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_segment_intersection() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let (a0, a1) = (p(0., 0.), p(4., 4.));
+        assert_eq!(
+            segment_intersection(&a0, &a1, &p(0., 4.), &p(4., 0.)),
+            Some(p(2., 2.))
+        );
+        assert_eq!(segment_intersection(&a0, &a1, &p(0., 4.), &p(1., 3.)), None);
+        //touch at an endpoint
+        assert_eq!(
+            segment_intersection(&a0, &a1, &p(4., 4.), &p(5., 0.)),
+            Some(p(4., 4.))
+        );
+        //parallel and collinear
+        assert_eq!(segment_intersection(&a0, &a1, &p(0., 1.), &p(4., 5.)), None);
+        assert_eq!(
+            segment_intersection(&a0, &a1, &p(6., 6.), &p(3., 3.)),
+            Some(p(3., 3.))
+        );
+        assert_eq!(segment_intersection(&a0, &a1, &p(5., 5.), &p(6., 6.)), None);
+        assert_eq!(
+            segment_intersection(&p(1., 1.), &p(1., 1.), &a0, &a1),
+            Some(p(1., 1.))
+        );
+
+        let hit = segment_intersection(
+            &Coord([0., 0., 0.]),
+            &Coord([2., 0., 4.]),
+            &Coord([1., -1., 9.]),
+            &Coord([1., 1., 9.]),
+        );
+        assert_eq!(hit, Some(Coord([1., 0., 2.])));
+    }
+
+    #[test]
+    fn test_self_intersections() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let square = [p(0., 0.), p(2., 0.), p(2., 2.), p(0., 2.), p(0., 0.)];
+        assert!(find_self_intersections(&square).is_empty());
+
+        let bowtie = [p(0., 0.), p(2., 2.), p(2., 0.), p(0., 2.)];
+        assert_eq!(find_self_intersections(&bowtie), vec![(0, 2, p(1., 1.))]);
+
+        //figure eight touching at a vertex
+        let eight = [
+            p(0., 0.),
+            p(2., 1.),
+            p(4., 0.),
+            p(4., 2.),
+            p(2., 1.),
+            p(0., 2.),
+        ];
+        assert_eq!(
+            find_self_intersections(&eight),
+            vec![
+                (0, 3, p(2., 1.)),
+                (0, 4, p(2., 1.)),
+                (1, 3, p(2., 1.)),
+                (1, 4, p(2., 1.)),
+            ]
+        );
+
+        //spike folding back along itself, its tip (3, 1) then touches edge 1
+        let spike = [p(0., 0.), p(3., 0.), p(3., 3.), p(3., 1.), p(0., 3.)];
+        assert_eq!(
+            find_self_intersections(&spike),
+            vec![(1, 2, p(3., 1.)), (1, 3, p(3., 1.))]
+        );
+        assert!(find_self_intersections(&square[..2]).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//!point set algorithms over `Coordinate` types
+use crate::Coordinate;
+use num_traits::Float;
+use std::cmp::Ordering;
+
+mod clip;
+mod cluster;
+#[cfg(feature = "delaunay")]
+mod concave;
+#[cfg(feature = "delaunay")]
+mod delaunay;
+mod densify;
+mod density;
+mod diameter;
+mod enclosing;
+mod fit;
+mod frames;
+mod generate;
+mod hull;
+mod idw;
+mod intersect;
+mod obb;
+mod offset;
+mod outliers;
+mod ransac;
+mod raster;
+mod ring;
+mod sample;
+mod sequence;
+mod simplex;
+
+pub use clip::{clip_ring_to_bounds, clip_segment_to_bounds};
+pub use cluster::{dbscan, kmeans, KMeansOptions};
+#[cfg(feature = "delaunay")]
+pub use concave::concave_hull;
+#[cfg(feature = "delaunay")]
+pub use delaunay::delaunay;
+pub use densify::densify;
+pub use density::{density_grid, density_grid_weighted, Grid};
+pub use diameter::diameter;
+pub use enclosing::min_enclosing_ball;
+pub use fit::{fit_circle, fit_line, fit_plane};
+pub use frames::parallel_transport_frames;
+pub use generate::{arc_points, points_in_annulus, points_on_circle, MAX_ARC_SEGMENTS};
+pub use hull::convex_hull;
+pub use idw::{idw_interpolate, Idw};
+pub use intersect::{find_self_intersections, segment_intersection};
+pub use obb::{oriented_bounds_2d, ObB2};
+pub use offset::{offset_polyline, JoinStyle};
+pub use outliers::remove_statistical_outliers;
+pub use ransac::{ransac, RansacOptions};
+pub use raster::{cells_on_ring, cells_on_segment, CellTraversal};
+pub use ring::{
+    ensure_closed, interior_angles, is_closed, is_convex, reverse_ring, winding_order, WindingOrder,
+};
+pub use sample::{farthest_point_sample, progressive_order, voxel_downsample};
+pub use sequence::{concat_snapped, reverse, rotate_start_to};
+pub use simplex::{
+    barycentric, circumcenter, from_barycentric, incenter, simplex_volume, triangle_circumcenter,
+};
+
+///z of (a - o) x (b - o) on the first two dimensions,
+///positive if o, a, b turn counter-clockwise
+pub(crate) fn cross2d<C: Coordinate>(o: &C, a: &C, b: &C) -> C::Scalar {
+    (a.val(0) - o.val(0)) * (b.val(1) - o.val(1)) - (a.val(1) - o.val(1)) * (b.val(0) - o.val(0))
+}
+
+///arithmetic mean of points, None if empty
+pub fn centroid<C>(points: &[C]) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.is_empty() {
+        return None;
+    }
+    let sum = points.iter().fold(C::new_origin(), |acc, p| acc.add(p));
+    let n: C::Scalar = num_traits::cast(points.len())?;
+    Some(sum.mult(n.recip()))
+}
+
+///gaussian elimination with partial pivoting on an augmented k x (k+1) matrix
+pub(crate) fn solve<T: Float>(mut m: Vec<Vec<T>>) -> Option<Vec<T>> {
+    let k = m.len();
+    let scale = m
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(T::zero(), |acc, v| acc.max(v.abs()));
+    let tiny = scale * T::epsilon() * num_traits::cast(1024).unwrap();
+    for col in 0..k {
+        let pivot = (col..k).max_by(|&a, &b| {
+            m[a][col]
+                .abs()
+                .partial_cmp(&m[b][col].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if m[pivot][col].is_nan() || m[pivot][col].abs() <= tiny {
+            return None;
+        }
+        m.swap(col, pivot);
+        for row in (col + 1)..k {
+            let f = m[row][col] / m[col][col];
+            let pivot_row = m[col].clone();
+            for (dst, src) in m[row].iter_mut().zip(pivot_row).skip(col) {
+                *dst = *dst - f * src;
+            }
+        }
+    }
+    let mut x = vec![T::zero(); k];
+    for row in (0..k).rev() {
+        let mut acc = m[row][k];
+        for c in (row + 1)..k {
+            acc = acc - m[row][c] * x[c];
+        }
+        x[row] = acc / m[row][row];
+    }
+    Some(x)
+}
@@ -0,0 +1,164 @@
+use super::convex_hull;
+use crate::Coordinate;
+use num_traits::{Float, One, Zero};
+
+///2D oriented bounding box - half extents are along the box axes,
+///the first axis rotated by angle (radians, counter-clockwise) from x
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ObB2<C: Coordinate> {
+    pub center: C,
+    pub half_extents: C,
+    pub angle: C::Scalar,
+}
+
+impl<C> ObB2<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///unit box axes (u, v)
+    pub fn axes(&self) -> (C, C) {
+        let (sin, cos) = self.angle.sin_cos();
+        (axis2(cos, sin), axis2(-sin, cos))
+    }
+
+    ///corners in counter-clockwise order
+    pub fn corners(&self) -> [C; 4] {
+        let (u, v) = self.axes();
+        let u = u.mult(self.half_extents.val(0));
+        let v = v.mult(self.half_extents.val(1));
+        [
+            self.center.sub(&u).sub(&v),
+            self.center.add(&u).sub(&v),
+            self.center.add(&u).add(&v),
+            self.center.sub(&u).add(&v),
+        ]
+    }
+
+    ///width * height
+    pub fn area(&self) -> C::Scalar {
+        let two = C::Scalar::one() + C::Scalar::one();
+        two * self.half_extents.val(0) * two * self.half_extents.val(1)
+    }
+}
+
+fn axis2<C: Coordinate>(x: C::Scalar, y: C::Scalar) -> C {
+    C::gen(|i| match i {
+        0 => x,
+        1 => y,
+        _ => C::Scalar::zero(),
+    })
+}
+
+fn dot2<C: Coordinate>(a: &C, b: &C) -> C::Scalar {
+    a.val(0) * b.val(0) + a.val(1) * b.val(1)
+}
+
+///minimum area oriented bounding box of 2D points, None if empty;
+///rotating calipers over the convex hull
+pub fn oriented_bounds_2d<C>(points: &[C]) -> Option<ObB2<C>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let zero = C::Scalar::zero();
+    let half = C::Scalar::one() / (C::Scalar::one() + C::Scalar::one());
+    let hull: Vec<C> = convex_hull(points).into_iter().map(|i| points[i]).collect();
+    let n = hull.len();
+    match n {
+        0 => return None,
+        1 => {
+            return Some(ObB2 {
+                center: hull[0],
+                half_extents: C::new_origin(),
+                angle: zero,
+            })
+        }
+        2 => {
+            let d = hull[1].sub(&hull[0]);
+            return Some(ObB2 {
+                center: hull[0].add(&d.mult(half)),
+                half_extents: axis2(dot2(&d, &d).sqrt() * half, zero),
+                angle: d.val(1).atan2(d.val(0)),
+            });
+        }
+        _ => {}
+    }
+
+    let pt = |k: usize| &hull[k % n];
+    let (mut right, mut far, mut left) = (1, 1, 1);
+    let mut best_area = C::Scalar::infinity();
+    let mut best = None;
+    for i in 0..n {
+        let origin = pt(i);
+        let edge = pt(i + 1).sub(origin);
+        let u = edge.mult(dot2(&edge, &edge).sqrt().recip());
+        //inward normal of a counter-clockwise hull
+        let v: C = axis2(-u.val(1), u.val(0));
+        let along = |k: usize| dot2(&pt(k).sub(origin), &u);
+        let height = |k: usize| dot2(&pt(k).sub(origin), &v);
+
+        right = right.max(i + 1);
+        while along(right + 1) > along(right) {
+            right += 1;
+        }
+        far = far.max(right);
+        while height(far + 1) > height(far) {
+            far += 1;
+        }
+        left = left.max(far);
+        while along(left + 1) < along(left) {
+            left += 1;
+        }
+
+        let (lo, hi, h) = (along(left), along(right), height(far));
+        let area = (hi - lo) * h;
+        if area < best_area {
+            let center = origin.add(&u.mult((lo + hi) * half)).add(&v.mult(h * half));
+            best_area = area;
+            best = Some(ObB2 {
+                center,
+                half_extents: axis2((hi - lo) * half, h * half),
+                angle: u.val(1).atan2(u.val(0)),
+            });
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_oriented_bounds_2d() {
+        //rectangle 4 x 2 rotated by 30 degrees about (1, 2)
+        let (s, c) = 30f64.to_radians().sin_cos();
+        let rot = |x: f64, y: f64| Pt {
+            x: 1. + x * c - y * s,
+            y: 2. + x * s + y * c,
+        };
+        let mut pts = vec![rot(-2., -1.), rot(2., -1.), rot(2., 1.), rot(-2., 1.)];
+        pts.push(rot(0.5, 0.3));
+        pts.push(rot(-1.5, 0.9));
+
+        let obb = oriented_bounds_2d(&pts).unwrap();
+        assert!((obb.area() - 8.).abs() < 1e-9);
+        assert!((obb.center.x - 1.).abs() < 1e-9 && (obb.center.y - 2.).abs() < 1e-9);
+        let (hx, hy) = (obb.half_extents.x, obb.half_extents.y);
+        assert!(
+            ((hx - 2.).abs() < 1e-9 && (hy - 1.).abs() < 1e-9)
+                || ((hx - 1.).abs() < 1e-9 && (hy - 2.).abs() < 1e-9)
+        );
+        for corner in obb.corners().iter() {
+            assert!(pts[..4].iter().any(|p| p.square_distance(corner) < 1e-12));
+        }
+
+        let seg = oriented_bounds_2d(&[Pt { x: 0., y: 0. }, Pt { x: 3., y: 4. }]).unwrap();
+        assert_eq!(seg.center, Pt { x: 1.5, y: 2. });
+        assert_eq!(seg.half_extents, Pt { x: 2.5, y: 0. });
+        assert_eq!(seg.area(), 0.);
+        assert_eq!(oriented_bounds_2d::<Pt<f64>>(&[]), None);
+    }
+}
@@ -0,0 +1,178 @@
+use super::cross2d;
+use crate::Coordinate;
+use num_traits::{Float, FloatConst, One, ToPrimitive, Zero};
+
+///corner treatment on the outer side of a turn
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum JoinStyle<T> {
+    ///sharp corner, bevelled where the miter tip is further than limit * distance
+    Miter(T),
+    ///arc around the vertex with this many segments per quarter turn
+    Round(usize),
+    ///straight cut across the corner
+    Bevel,
+}
+
+///offset curve of a polyline at distance on the first two dimensions - positive
+///to the left of the direction of travel, negative to the right; later dimensions
+///are kept from the vertex. Inner corners meet at the offset lines intersection,
+///repeated points are skipped and fewer than two distinct points give an empty line
+pub fn offset_polyline<C>(
+    points: &[C],
+    distance: C::Scalar,
+    join_style: JoinStyle<C::Scalar>,
+) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let mut pts: Vec<C> = Vec::with_capacity(points.len());
+    for p in points {
+        if pts.last().map_or(true, |q| !same_xy(q, p)) {
+            pts.push(*p);
+        }
+    }
+    if pts.len() < 2 {
+        return Vec::new();
+    }
+    let normals: Vec<_> = pts.windows(2).map(|w| left_normal(&w[0], &w[1])).collect();
+    let mut out = Vec::with_capacity(pts.len() + 2);
+    out.push(shift(&pts[0], normals[0], distance));
+    for k in 1..pts.len() - 1 {
+        let (na, nb) = (normals[k - 1], normals[k]);
+        let turn = cross2d(&pts[k - 1], &pts[k], &pts[k + 1]);
+        let cos = na.0 * nb.0 + na.1 * nb.1;
+        let outer = turn * distance < C::Scalar::zero()
+            || (turn == C::Scalar::zero() && cos < C::Scalar::zero());
+        if !outer {
+            out.push(shift(&pts[k], miter(na, nb), distance));
+            continue;
+        }
+        match join_style {
+            JoinStyle::Miter(limit)
+                if (C::Scalar::one() + cos) * limit * limit >= num_traits::cast(2).unwrap() =>
+            {
+                out.push(shift(&pts[k], miter(na, nb), distance));
+            }
+            JoinStyle::Round(segments) => {
+                //outer turns bend away from the offset side, reversals included
+                let sweep = -distance.signum() * (na.0 * nb.1 - na.1 * nb.0).abs().atan2(cos);
+                let quarter = C::Scalar::FRAC_PI_2() / num_traits::cast(segments.max(1)).unwrap();
+                let n = (sweep.abs() / quarter)
+                    .ceil()
+                    .to_usize()
+                    .unwrap_or(1)
+                    .max(1);
+                let step = sweep / num_traits::cast(n).unwrap();
+                out.push(shift(&pts[k], na, distance));
+                for i in 1..n {
+                    let (sin, cos) = (step * num_traits::cast(i).unwrap()).sin_cos();
+                    let rot = (na.0 * cos - na.1 * sin, na.0 * sin + na.1 * cos);
+                    out.push(shift(&pts[k], rot, distance));
+                }
+                out.push(shift(&pts[k], nb, distance));
+            }
+            _ => {
+                out.push(shift(&pts[k], na, distance));
+                out.push(shift(&pts[k], nb, distance));
+            }
+        }
+    }
+    out.push(shift(
+        &pts[pts.len() - 1],
+        normals[normals.len() - 1],
+        distance,
+    ));
+    out
+}
+
+fn same_xy<C: Coordinate>(a: &C, b: &C) -> bool {
+    a.val(0) == b.val(0) && a.val(1) == b.val(1)
+}
+
+///unit normal to the left of a->b
+fn left_normal<C>(a: &C, b: &C) -> (C::Scalar, C::Scalar)
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (dx, dy) = (b.val(0) - a.val(0), b.val(1) - a.val(1));
+    let len = dx.hypot(dy);
+    (-dy / len, dx / len)
+}
+
+///corner vector where the lines offset by unit normals na and nb meet
+fn miter<T: Float>(na: (T, T), nb: (T, T)) -> (T, T) {
+    let k = T::one() / (T::one() + na.0 * nb.0 + na.1 * nb.1);
+    ((na.0 + nb.0) * k, (na.1 + nb.1) * k)
+}
+
+fn shift<C: Coordinate>(p: &C, (nx, ny): (C::Scalar, C::Scalar), distance: C::Scalar) -> C {
+    let mut q = *p;
+    *q.val_mut(0) = p.val(0) + nx * distance;
+    *q.val_mut(1) = p.val(1) + ny * distance;
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    fn close(a: &[Pt<f64>], b: &[Pt<f64>]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(p, q)| p.square_distance(q) < 1e-20)
+    }
+
+    #[test]
+    fn test_offset_polyline() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        //right turn at (2, 0), so the left side is the outer one
+        let line = [p(0., 0.), p(2., 0.), p(2., 0.), p(2., -2.)];
+        assert!(close(
+            &offset_polyline(&line, 1., JoinStyle::Miter(2.)),
+            &[p(0., 1.), p(3., 1.), p(3., -2.)]
+        ));
+        //miter tip at sqrt(2) exceeds the limit
+        assert!(close(
+            &offset_polyline(&line, 1., JoinStyle::Miter(1.2)),
+            &[p(0., 1.), p(2., 1.), p(3., 0.), p(3., -2.)]
+        ));
+        assert!(close(
+            &offset_polyline(&line, 1., JoinStyle::Bevel),
+            &[p(0., 1.), p(2., 1.), p(3., 0.), p(3., -2.)]
+        ));
+        let round = offset_polyline(&line, 1., JoinStyle::Round(4));
+        assert_eq!(round.len(), 7);
+        assert!(round[1..6]
+            .iter()
+            .all(|q| (q.square_distance(&p(2., 0.)) - 1.).abs() < 1e-12));
+        assert!(close(
+            &round[2..3],
+            &[p(
+                2. + 22.5f64.to_radians().sin(),
+                22.5f64.to_radians().cos()
+            )]
+        ));
+
+        //inner side meets at the intersection whatever the join
+        assert!(close(
+            &offset_polyline(&line, -1., JoinStyle::Round(8)),
+            &[p(0., -1.), p(1., -1.), p(1., -2.)]
+        ));
+
+        assert!(offset_polyline(&[p(1., 1.), p(1., 1.)], 1., JoinStyle::Bevel).is_empty());
+        let straight = offset_polyline(
+            &[p(0., 0.), p(1., 1.), p(2., 2.)],
+            2f64.sqrt(),
+            JoinStyle::Bevel,
+        );
+        assert!(close(&straight, &[p(-1., 1.), p(0., 2.), p(1., 3.)]));
+
+        let line3 = [Coord([0., 0., 5.]), Coord([0., 4., 7.])];
+        assert_eq!(
+            offset_polyline(&line3, 1., JoinStyle::Bevel),
+            vec![Coord([-1., 0., 5.]), Coord([-1., 4., 7.])]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::Coordinate;
+use num_traits::{Float, Zero};
+use std::cmp::Ordering;
+
+///mean distance of each point to its k nearest neighbours (brute force)
+fn mean_knn_distances<C>(points: &[C], k: usize) -> Vec<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let kf: C::Scalar = num_traits::cast(k).unwrap();
+    let mut dists = Vec::with_capacity(points.len());
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            dists.clear();
+            dists.extend(
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, q)| p.square_distance(q)),
+            );
+            dists.select_nth_unstable_by(k - 1, |a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let sum = dists[..k]
+                .iter()
+                .fold(C::Scalar::zero(), |acc, d| acc + d.sqrt());
+            sum / kf
+        })
+        .collect()
+}
+
+///statistical outlier removal - drops points whose mean distance to their k nearest
+///neighbours exceeds the global mean of that distance by more than stddev_mult
+///standard deviations; points are returned unchanged if there are at most k of them
+pub fn remove_statistical_outliers<C>(points: &[C], k: usize, stddev_mult: C::Scalar) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if k == 0 || points.len() <= k {
+        return points.to_vec();
+    }
+    let means = mean_knn_distances(points, k);
+    let n: C::Scalar = num_traits::cast(means.len()).unwrap();
+    let mean = means.iter().fold(C::Scalar::zero(), |acc, &d| acc + d) / n;
+    let var = means
+        .iter()
+        .fold(C::Scalar::zero(), |acc, &d| acc + (d - mean) * (d - mean))
+        / n;
+    let limit = mean + stddev_mult * var.sqrt();
+    points
+        .iter()
+        .zip(means)
+        .filter(|&(_, d)| d <= limit)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_remove_statistical_outliers() {
+        let mut pts: Vec<_> = (0..25)
+            .map(|i| Pt {
+                x: (i % 5) as f64,
+                y: (i / 5) as f64,
+            })
+            .collect();
+        pts.push(Pt { x: 50., y: 50. });
+        pts.push(Pt { x: -40., y: 10. });
+
+        let kept = remove_statistical_outliers(&pts, 4, 1.);
+        assert_eq!(kept, pts[..25].to_vec());
+
+        let few = &pts[..3];
+        assert_eq!(remove_statistical_outliers(few, 3, 1.), few.to_vec());
+        assert_eq!(remove_statistical_outliers(&pts, 0, 1.), pts);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::rng::XorShift;
+use crate::Coordinate;
+
+///RANSAC options
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RansacOptions<T> {
+    ///points per random minimal sample (2 for lines, 3 for planes & circles)
+    pub sample_size: usize,
+    ///maximum point to model distance of an inlier
+    pub threshold: T,
+    ///number of random samples
+    pub max_iter: usize,
+    ///seed of the sampling
+    pub seed: u64,
+}
+
+impl<T> RansacOptions<T> {
+    ///options with 1000 iterations and a fixed seed
+    pub fn new(sample_size: usize, threshold: T) -> Self {
+        RansacOptions {
+            sample_size,
+            threshold,
+            max_iter: 1000,
+            seed: 1,
+        }
+    }
+}
+
+///robust model fitting - fits models to random minimal samples with fit,
+///keeps the one with most inliers (distance <= threshold) and refits it on them;
+///returns the model and ascending inlier indices, None if no sample could be fit
+pub fn ransac<C, M, F, D>(
+    points: &[C],
+    fit: F,
+    distance: D,
+    opts: &RansacOptions<C::Scalar>,
+) -> Option<(M, Vec<usize>)>
+where
+    C: Coordinate,
+    F: Fn(&[C]) -> Option<M>,
+    D: Fn(&M, &C) -> C::Scalar,
+{
+    let k = opts.sample_size;
+    if k == 0 || points.len() < k {
+        return None;
+    }
+    let inliers_of = |model: &M| -> Vec<usize> {
+        (0..points.len())
+            .filter(|&i| distance(model, &points[i]) <= opts.threshold)
+            .collect()
+    };
+    let mut rng = XorShift::new(opts.seed);
+    let mut indices: Vec<usize> = (0..points.len()).collect();
+    let mut sample = Vec::with_capacity(k);
+    let mut best: Option<(M, Vec<usize>)> = None;
+    for _ in 0..opts.max_iter {
+        //partial Fisher-Yates draw of k distinct points
+        sample.clear();
+        for i in 0..k {
+            let j = i + rng.below(indices.len() - i);
+            indices.swap(i, j);
+            sample.push(points[indices[i]]);
+        }
+        let model = match fit(&sample) {
+            Some(model) => model,
+            None => continue,
+        };
+        let inliers = inliers_of(&model);
+        let better = match &best {
+            Some((_, b)) => inliers.len() > b.len(),
+            None => true,
+        };
+        if better {
+            let all = inliers.len() == points.len();
+            best = Some((model, inliers));
+            if all {
+                break;
+            }
+        }
+    }
+    let (model, inliers) = best?;
+    let inlier_points: Vec<C> = inliers.iter().map(|&i| points[i]).collect();
+    match fit(&inlier_points) {
+        Some(refit) => {
+            let refit_inliers = inliers_of(&refit);
+            if refit_inliers.len() >= inliers.len() {
+                return Some((refit, refit_inliers));
+            }
+            Some((model, inliers))
+        }
+        None => Some((model, inliers)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{fit_circle, fit_line};
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_ransac_line() {
+        let mut pts: Vec<_> = (0..20)
+            .map(|i| Pt {
+                x: i as f64,
+                y: 1. + 0.5 * i as f64,
+            })
+            .collect();
+        pts.extend([
+            Pt { x: 3., y: 40. },
+            Pt { x: 10., y: -30. },
+            Pt { x: -5., y: 12. },
+        ]);
+        let dist = |(p, d): &(Pt<f64>, Pt<f64>), q: &Pt<f64>| {
+            let v = q.sub(p);
+            (v.x * d.y - v.y * d.x).abs()
+        };
+        let (model, inliers) = ransac(&pts, fit_line, dist, &RansacOptions::new(2, 0.01)).unwrap();
+        assert_eq!(inliers, (0..20).collect::<Vec<_>>());
+        assert!((model.1.y / model.1.x - 0.5).abs() < 1e-9);
+        assert!(dist(&model, &Pt { x: 100., y: 51. }) < 1e-9);
+
+        assert!(ransac(&pts[..1], fit_line, dist, &RansacOptions::new(2, 0.01)).is_none());
+    }
+
+    #[test]
+    fn test_ransac_circle() {
+        let mut pts: Vec<_> = (0..16)
+            .map(|i| {
+                let t = i as f64 * std::f64::consts::PI / 8.;
+                Pt {
+                    x: 3. * t.cos(),
+                    y: 3. * t.sin(),
+                }
+            })
+            .collect();
+        pts.push(Pt { x: 0.5, y: 0.5 });
+        pts.push(Pt { x: 9., y: 9. });
+        let (ball, inliers) = ransac(
+            &pts,
+            fit_circle,
+            |b, q| (b.center.square_distance(q).sqrt() - b.radius).abs(),
+            &RansacOptions::new(3, 1e-6),
+        )
+        .unwrap();
+        assert_eq!(inliers.len(), 16);
+        assert!((ball.radius - 3.).abs() < 1e-9);
+    }
+}
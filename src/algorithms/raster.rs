@@ -0,0 +1,359 @@
+use crate::Coordinate;
+use num_traits::{Float, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+///grid cells touched by the segment [a, b] in traversal order (Amanatides-Woo);
+///cell i spans the closed range [i * cell_size, (i + 1) * cell_size] on each axis, so
+///a segment through a grid corner or along a grid line visits every cell sharing it
+pub fn cells_on_segment<C, D>(a: &C, b: &C, cell_size: C::Scalar) -> CellTraversal<C::Scalar, D>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+    D: Coordinate<Scalar = i64>,
+{
+    debug_assert_eq!(C::DIM, D::DIM);
+    let mut axes = Vec::with_capacity(D::DIM);
+    for i in 0..D::DIM {
+        let (u, v) = (a.val(i) / cell_size, b.val(i) / cell_size);
+        let zero = C::Scalar::zero();
+        let d = b.val(i) - a.val(i);
+        //open interval index just after a and just before b along the direction of travel
+        let (first, last) = if d > zero {
+            (u.floor(), v.ceil() - C::Scalar::one())
+        } else if d < zero {
+            (u.ceil() - C::Scalar::one(), v.floor())
+        } else {
+            (u.floor(), u.floor())
+        };
+        let (first, last) = match (first.to_i64(), last.to_i64()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return CellTraversal::empty(),
+        };
+        let step = if d > zero {
+            1
+        } else if d < zero {
+            -1
+        } else {
+            0
+        };
+        //rounding in u, v can put last behind first on very short segments
+        let crossings = if (last - first) * step > 0 {
+            (last - first).unsigned_abs()
+        } else {
+            0
+        };
+        let (t_max, t_delta) = if step == 0 {
+            (C::Scalar::infinity(), C::Scalar::infinity())
+        } else {
+            let next = first + if step > 0 { 1 } else { 0 };
+            let boundary = num_traits::cast::<_, C::Scalar>(next).unwrap() * cell_size;
+            ((boundary - a.val(i)) / d, cell_size / d.abs())
+        };
+        axes.push(Axis {
+            cell: first,
+            step,
+            t_max,
+            t_delta,
+            crossings,
+            first_on_line: u == u.floor(),
+            last_on_line: v == v.floor(),
+        });
+    }
+    CellTraversal {
+        axes,
+        stage: Stage::Start,
+        pending: Vec::new(),
+        previous: Vec::new(),
+    }
+}
+
+///grid cells touched by the boundary of ring in traversal order, see `cells_on_segment`;
+///the ring is closed implicitly and cells at the vertex shared by consecutive edges appear once
+pub fn cells_on_ring<C, D>(ring: &[C], cell_size: C::Scalar) -> Vec<D>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+    D: Coordinate<Scalar = i64>,
+{
+    let mut n = ring.len();
+    if n > 1 && ring[0] == ring[n - 1] {
+        n -= 1;
+    }
+    let vertex = |pt: &C| cells_on_segment::<C, D>(pt, pt, cell_size).collect::<Vec<_>>();
+    let first = if n > 1 { vertex(&ring[0]) } else { Vec::new() };
+    let mut cells: Vec<D> = Vec::new();
+    for i in 0..n {
+        let (a, b) = (&ring[i], &ring[(i + 1) % n]);
+        //cells at the shared vertices were already emitted by the neighbouring edge
+        let shared = if i > 0 { vertex(a) } else { Vec::new() };
+        let closing = i == n - 1;
+        for cell in cells_on_segment(a, b, cell_size) {
+            if !shared.contains(&cell) && !(closing && first.contains(&cell)) {
+                cells.push(cell);
+            }
+        }
+    }
+    cells
+}
+
+struct Axis<T> {
+    cell: i64,
+    step: i64,
+    t_max: T,
+    t_delta: T,
+    crossings: u64,
+    first_on_line: bool,
+    last_on_line: bool,
+}
+
+impl<T> Axis<T> {
+    //cell indices along this axis touched at the current event, earliest first
+    fn touched(&self, on_line: bool) -> (i64, Option<i64>) {
+        match (on_line, self.step) {
+            (false, _) => (self.cell, None),
+            (true, 0) => (self.cell - 1, Some(self.cell)),
+            (true, step) => (self.cell, Some(self.cell + step)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Stage {
+    Start,
+    Crossing,
+    End,
+    Done,
+}
+
+///see `cells_on_segment`
+pub struct CellTraversal<T, D> {
+    axes: Vec<Axis<T>>,
+    stage: Stage,
+    //cells of the current event in reverse, and all cells of the event before it
+    pending: Vec<D>,
+    previous: Vec<D>,
+}
+
+impl<T, D> CellTraversal<T, D> {
+    fn empty() -> Self {
+        CellTraversal {
+            axes: Vec::new(),
+            stage: Stage::Done,
+            pending: Vec::new(),
+            previous: Vec::new(),
+        }
+    }
+}
+
+impl<T: Float, D: Coordinate<Scalar = i64>> CellTraversal<T, D> {
+    //the cells touched at the next boundary event; the point at an event lies on the
+    //boundary of every cell in the product of the per axis ranges
+    fn advance(&mut self) -> bool {
+        let mut ranges = Vec::with_capacity(self.axes.len());
+        match self.stage {
+            Stage::Start => {
+                for axis in &self.axes {
+                    //a start on a boundary also touches the cell behind it
+                    ranges.push(if axis.step != 0 && axis.first_on_line {
+                        (axis.cell - axis.step, Some(axis.cell))
+                    } else {
+                        axis.touched(axis.first_on_line)
+                    });
+                }
+                self.stage = Stage::Crossing;
+            }
+            Stage::Crossing => {
+                let t = self
+                    .axes
+                    .iter()
+                    .filter(|axis| axis.crossings > 0)
+                    .map(|axis| axis.t_max)
+                    .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let t = match t {
+                    Some(t) => t,
+                    None => {
+                        self.stage = Stage::End;
+                        return true;
+                    }
+                };
+                //every axis crossing at t steps together, covering the corner cells
+                for axis in self.axes.iter_mut() {
+                    let crossing = axis.crossings > 0 && axis.t_max == t;
+                    let fixed_on_line = axis.step == 0 && axis.first_on_line;
+                    ranges.push(axis.touched(crossing || fixed_on_line));
+                    if crossing {
+                        axis.cell += axis.step;
+                        axis.crossings -= 1;
+                        axis.t_max = axis.t_max + axis.t_delta;
+                    }
+                }
+            }
+            Stage::End => {
+                for axis in &self.axes {
+                    let on_line = if axis.step == 0 {
+                        axis.first_on_line
+                    } else {
+                        axis.last_on_line
+                    };
+                    ranges.push(axis.touched(on_line));
+                }
+                self.stage = Stage::Done;
+            }
+            Stage::Done => return false,
+        }
+
+        let count = ranges
+            .iter()
+            .map(|r| 1 << r.1.is_some() as usize)
+            .product::<usize>();
+        let mut cells = Vec::with_capacity(count);
+        for k in 0..count {
+            let mut cell = D::new_origin();
+            let mut rest = k;
+            for (i, &(lo, hi)) in ranges.iter().enumerate().rev() {
+                *cell.val_mut(i) = match hi {
+                    Some(hi) if rest & 1 == 1 => hi,
+                    _ => lo,
+                };
+                if hi.is_some() {
+                    rest >>= 1;
+                }
+            }
+            cells.push(cell);
+        }
+        //a closed cell meets the segment in one interval, so it can only repeat across
+        //consecutive events
+        self.pending = cells
+            .iter()
+            .rev()
+            .filter(|cell| !self.previous.contains(*cell))
+            .copied()
+            .collect();
+        self.previous = cells;
+        true
+    }
+}
+
+impl<T: Float, D: Coordinate<Scalar = i64>> Iterator for CellTraversal<T, D> {
+    type Item = D;
+
+    fn next(&mut self) -> Option<D> {
+        loop {
+            if let Some(cell) = self.pending.pop() {
+                return Some(cell);
+            }
+            if !self.advance() {
+                return None;
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let steps = self
+            .axes
+            .iter()
+            .map(|axis| axis.crossings as usize)
+            .sum::<usize>();
+        match self.stage {
+            Stage::Done => (self.pending.len(), Some(self.pending.len())),
+            Stage::Start => (1 + steps, None),
+            _ => (self.pending.len() + steps, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Coord, IntCoord};
+
+    #[test]
+    fn test_cells_on_segment() {
+        let cells: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 0.5, y: 0.5 }, &Pt { x: 2.5, y: 1.5 }, 1.).collect();
+        assert_eq!(
+            cells,
+            vec![Coord([0, 0]), Coord([1, 0]), Coord([1, 1]), Coord([2, 1])]
+        );
+
+        let cells: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 3.9, y: -0.1 }, &Pt { x: -0.1, y: -0.1 }, 2.).collect();
+        assert_eq!(cells, vec![Coord([1, -1]), Coord([0, -1]), Coord([-1, -1])]);
+
+        let cells: Vec<IntCoord<3>> =
+            cells_on_segment(&Coord([0.2, 0.2, 0.2]), &Coord([0.4, 0.3, 0.9]), 0.5).collect();
+        assert_eq!(cells, vec![Coord([0, 0, 0]), Coord([0, 0, 1])]);
+
+        //closed cells: a corner crossing touches all four cells around the corner
+        let corner: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 0.5, y: 0.5 }, &Pt { x: 1.5, y: 1.5 }, 1.).collect();
+        assert_eq!(
+            corner,
+            vec![Coord([0, 0]), Coord([0, 1]), Coord([1, 0]), Coord([1, 1])]
+        );
+        let along: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 0.5, y: 1. }, &Pt { x: 2.5, y: 1. }, 1.).collect();
+        assert_eq!(
+            along,
+            vec![
+                Coord([0, 0]),
+                Coord([0, 1]),
+                Coord([1, 0]),
+                Coord([1, 1]),
+                Coord([2, 0]),
+                Coord([2, 1])
+            ]
+        );
+        let node: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 1., y: 1. }, &Pt { x: 1., y: 1. }, 1.).collect();
+        assert_eq!(node.len(), 4);
+
+        let same: Vec<IntCoord<2>> =
+            cells_on_segment(&Pt { x: 0.5, y: 0.5 }, &Pt { x: 0.6, y: 0.5 }, 1.).collect();
+        assert_eq!(same, vec![Coord([0, 0])]);
+        let nan = cells_on_segment::<_, IntCoord<2>>(
+            &Pt { x: f64::NAN, y: 0. },
+            &Pt { x: 1., y: 0. },
+            1.,
+        );
+        assert_eq!(nan.count(), 0);
+    }
+
+    #[test]
+    fn test_cells_on_ring() {
+        let ring = [
+            Pt { x: 0.5, y: 0.5 },
+            Pt { x: 2.5, y: 0.5 },
+            Pt { x: 2.5, y: 2.5 },
+            Pt { x: 0.5, y: 2.5 },
+        ];
+        let cells: Vec<IntCoord<2>> = cells_on_ring(&ring, 1.);
+        assert_eq!(cells.len(), 8);
+        assert_eq!(cells[0], Coord([0, 0]));
+        assert!(!cells.contains(&Coord([1, 1])));
+
+        let mut closed = ring.to_vec();
+        closed.push(ring[0]);
+        assert_eq!(cells_on_ring::<_, IntCoord<2>>(&closed, 1.), cells);
+
+        //a vertex on a grid node touches the four cells around it, each listed once
+        let triangle = [
+            Pt { x: 1., y: 1. },
+            Pt { x: 4.5, y: 1.5 },
+            Pt { x: 2.5, y: 4.5 },
+        ];
+        let cells: Vec<IntCoord<2>> = cells_on_ring(&triangle, 1.);
+        assert_eq!(
+            cells[..4],
+            [Coord([0, 0]), Coord([0, 1]), Coord([1, 0]), Coord([1, 1])]
+        );
+        for cell in &cells[..4] {
+            assert_eq!(cells.iter().filter(|c| *c == cell).count(), 1);
+        }
+
+        let point: Vec<IntCoord<2>> = cells_on_ring(&ring[..1], 1.);
+        assert_eq!(point, vec![Coord([0, 0])]);
+        assert!(cells_on_ring::<Pt<f64>, IntCoord<2>>(&[], 1.).is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+use super::cross2d;
+use crate::Coordinate;
+use bs_num::Zero;
+use num_traits::{Float, FloatConst};
+
+///orientation of a ring on the first two dimensions
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WindingOrder {
+    Clockwise,
+    CounterClockwise,
+    ///zero area - fewer than three distinct points or collinear
+    Degenerate,
+}
+
+///twice the signed area (shoelace) - positive if counter-clockwise;
+///ring may be open or closed
+pub(crate) fn twice_signed_area<C: Coordinate>(ring: &[C]) -> C::Scalar {
+    let mut area = Zero::zero();
+    for i in 0..ring.len() {
+        let (a, b) = (&ring[i], &ring[(i + 1) % ring.len()]);
+        area = area + (a.val(0) * b.val(1) - b.val(0) * a.val(1));
+    }
+    area
+}
+
+///checks if first and last points are within eps
+pub fn is_closed<C: Coordinate>(ring: &[C], eps: C::Scalar) -> bool {
+    match (ring.first(), ring.last()) {
+        (Some(a), Some(b)) if ring.len() > 1 => a.square_distance(b) <= eps * eps,
+        _ => false,
+    }
+}
+
+///appends the first point if the ring is not exactly closed
+pub fn ensure_closed<C: Coordinate>(ring: &mut Vec<C>) {
+    if let (Some(&first), Some(last)) = (ring.first(), ring.last()) {
+        if ring.len() == 1 || first != *last {
+            ring.push(first);
+        }
+    }
+}
+
+///winding order of a ring
+pub fn winding_order<C: Coordinate>(ring: &[C]) -> WindingOrder {
+    let area = twice_signed_area(ring);
+    if area > Zero::zero() {
+        WindingOrder::CounterClockwise
+    } else if area < Zero::zero() {
+        WindingOrder::Clockwise
+    } else {
+        WindingOrder::Degenerate
+    }
+}
+
+///reverses the winding order keeping the start point (and closure) in place
+pub fn reverse_ring<C: Coordinate>(ring: &mut [C]) {
+    let n = ring.len();
+    if n > 1 && ring[0] == ring[n - 1] {
+        ring.reverse();
+    } else if n > 1 {
+        ring[1..].reverse();
+    }
+}
+
+///distinct vertices of a ring - without the closing point
+pub(super) fn open_ring<C: Coordinate>(ring: &[C]) -> &[C] {
+    match ring {
+        [first, .., last] if ring.len() > 1 && first == last => &ring[..ring.len() - 1],
+        _ => ring,
+    }
+}
+
+///checks if a ring (open or closed) is a convex polygon on the first two dimensions -
+///every turn is in the same direction and the boundary winds once; collinear
+///vertices are allowed, fewer than three vertices or zero area is not convex
+pub fn is_convex<C: Coordinate>(ring: &[C]) -> bool {
+    let ring = open_ring(ring);
+    let n = ring.len();
+    if n < 3 || winding_order(ring) == WindingOrder::Degenerate {
+        return false;
+    }
+    let zero = C::Scalar::zero();
+    let (mut pos, mut neg) = (false, false);
+    let mut x_dirs = Vec::with_capacity(n);
+    for i in 0..n {
+        let (a, b, c) = (&ring[i], &ring[(i + 1) % n], &ring[(i + 2) % n]);
+        let turn = cross2d(a, b, c);
+        pos |= turn > zero;
+        neg |= turn < zero;
+        let dx = b.val(0) - a.val(0);
+        if dx != zero {
+            x_dirs.push(dx > zero);
+        }
+    }
+    //a once winding convex boundary reverses x direction exactly twice
+    let x_flips = (0..x_dirs.len())
+        .filter(|&i| x_dirs[i] != x_dirs[(i + 1) % x_dirs.len()])
+        .count();
+    !(pos && neg) && x_flips <= 2
+}
+
+///interior angle (radians) at each distinct vertex of a ring, in vertex order;
+///measured on the inside for either winding order, reflex vertices exceed pi
+pub fn interior_angles<C>(ring: &[C]) -> Vec<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let ring = open_ring(ring);
+    let n = ring.len();
+    if n < 3 {
+        return vec![];
+    }
+    let clockwise = winding_order(ring) == WindingOrder::Clockwise;
+    (0..n)
+        .map(|i| {
+            let (a, b, c) = (&ring[(i + n - 1) % n], &ring[i], &ring[(i + 1) % n]);
+            let (u, v) = (b.sub(a), c.sub(b));
+            let dot = u.val(0) * v.val(0) + u.val(1) * v.val(1);
+            let turn = cross2d(a, b, c).atan2(dot);
+            let turn = if clockwise { -turn } else { turn };
+            C::Scalar::PI() - turn
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_ring_utils() {
+        let mut ring = vec![Pt { x: 0, y: 0 }, Pt { x: 4, y: 0 }, Pt { x: 4, y: 3 }];
+        assert!(!is_closed(&ring, 0));
+        assert_eq!(winding_order(&ring), WindingOrder::CounterClockwise);
+        assert_eq!(twice_signed_area(&ring), 12);
+
+        reverse_ring(&mut ring);
+        assert_eq!(
+            ring,
+            vec![Pt { x: 0, y: 0 }, Pt { x: 4, y: 3 }, Pt { x: 4, y: 0 }]
+        );
+        assert_eq!(winding_order(&ring), WindingOrder::Clockwise);
+
+        ensure_closed(&mut ring);
+        assert_eq!(ring.len(), 4);
+        assert!(is_closed(&ring, 0));
+        ensure_closed(&mut ring);
+        assert_eq!(ring.len(), 4);
+        assert_eq!(twice_signed_area(&ring), -12);
+
+        reverse_ring(&mut ring);
+        assert_eq!(ring[0], ring[3]);
+        assert_eq!(ring[1], Pt { x: 4, y: 0 });
+        assert_eq!(winding_order(&ring), WindingOrder::CounterClockwise);
+
+        let near = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 0. },
+            Pt { x: 1e-9, y: 0. },
+        ];
+        assert!(is_closed(&near, 1e-6));
+        assert_eq!(winding_order(&near), WindingOrder::Degenerate);
+        assert!(!is_closed(&near[..1], 1.));
+
+        let mut empty: Vec<Pt<i32>> = vec![];
+        ensure_closed(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_convexity() {
+        let mut square = vec![
+            Pt { x: 0, y: 0 },
+            Pt { x: 2, y: 0 },
+            Pt { x: 2, y: 2 },
+            Pt { x: 0, y: 2 },
+        ];
+        assert!(is_convex(&square));
+        reverse_ring(&mut square);
+        assert!(is_convex(&square));
+        square.insert(1, Pt { x: 0, y: 1 });
+        assert!(is_convex(&square));
+        ensure_closed(&mut square);
+        assert!(is_convex(&square));
+
+        let notch = [
+            Pt { x: 0, y: 0 },
+            Pt { x: 4, y: 0 },
+            Pt { x: 4, y: 4 },
+            Pt { x: 2, y: 1 },
+            Pt { x: 0, y: 4 },
+        ];
+        assert!(!is_convex(&notch));
+        //pentagram - every turn is left but it winds twice
+        let star = [
+            Pt { x: 0, y: 10 },
+            Pt { x: 6, y: -8 },
+            Pt { x: -10, y: 3 },
+            Pt { x: 10, y: 3 },
+            Pt { x: -6, y: -8 },
+        ];
+        assert!(!is_convex(&star));
+        assert!(!is_convex(&notch[..2]));
+        assert!(!is_convex(&[
+            Pt { x: 0, y: 0 },
+            Pt { x: 1, y: 1 },
+            Pt { x: 2, y: 2 }
+        ]));
+    }
+
+    #[test]
+    fn test_interior_angles() {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12);
+
+        let tri = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 0. },
+            Pt { x: 0., y: 1. },
+        ];
+        let angles = interior_angles(&tri);
+        assert!(close(&angles, &[FRAC_PI_2, FRAC_PI_4, FRAC_PI_4]));
+        let cw = [tri[0], tri[2], tri[1], tri[0]];
+        assert!(close(
+            &interior_angles(&cw),
+            &[FRAC_PI_2, FRAC_PI_4, FRAC_PI_4]
+        ));
+
+        let notch = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 2., y: 0. },
+            Pt { x: 2., y: 2. },
+            Pt { x: 1., y: 1. },
+            Pt { x: 0., y: 2. },
+        ];
+        let angles = interior_angles(&notch);
+        assert!(close(
+            &angles,
+            &[FRAC_PI_2, FRAC_PI_2, FRAC_PI_4, 3. * FRAC_PI_2, FRAC_PI_4]
+        ));
+        assert!((angles.iter().sum::<f64>() - 3. * PI).abs() < 1e-12);
+        assert!(interior_angles(&tri[..2]).is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::iter::CoordIter;
+use crate::Coordinate;
+use num_traits::{Float, One, ToPrimitive, Zero};
+use std::collections::{HashMap, HashSet};
+
+///voxel grid downsampling - one centroid per occupied cell of size cell_size
+///(cells anchored at the origin), in order of first occupancy;
+///points with non-finite components are dropped
+pub fn voxel_downsample<C>(points: &[C], cell_size: C::Scalar) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut cells: HashMap<Vec<i64>, usize> = HashMap::new();
+    let mut sums: Vec<(C, C::Scalar)> = Vec::new();
+    for pt in points {
+        let key: Option<Vec<i64>> = (0..C::DIM)
+            .map(|i| (pt.val(i) / cell_size).floor().to_i64())
+            .collect();
+        let key = match key {
+            Some(key) => key,
+            None => continue,
+        };
+        let next = sums.len();
+        let slot = *cells.entry(key).or_insert(next);
+        if slot == next {
+            sums.push((*pt, C::Scalar::one()));
+        } else {
+            let (sum, n) = &mut sums[slot];
+            *sum = sum.add(pt);
+            *n = *n + C::Scalar::one();
+        }
+    }
+    sums.into_iter()
+        .map(|(sum, n)| sum.mult(n.recip()))
+        .collect()
+}
+
+///farthest point sampling - n well spread indices starting from the first point,
+///each next pick maximizes the distance to those already picked (ties to the lower
+///index); at most points.len() indices
+pub fn farthest_point_sample<C: Coordinate>(points: &[C], n: usize) -> Vec<usize> {
+    let n = n.min(points.len());
+    let mut picked = Vec::with_capacity(n);
+    if n == 0 {
+        return picked;
+    }
+    let mut taken = vec![false; points.len()];
+    let mut dist: Vec<C::Scalar> = points
+        .iter()
+        .map(|p| p.square_distance(&points[0]))
+        .collect();
+    picked.push(0);
+    taken[0] = true;
+    while picked.len() < n {
+        let mut next: Option<usize> = None;
+        for i in (0..points.len()).filter(|&i| !taken[i]) {
+            match next {
+                Some(j) if dist[i] <= dist[j] => {}
+                _ => next = Some(i),
+            }
+        }
+        let next = match next {
+            Some(i) => i,
+            None => break,
+        };
+        picked.push(next);
+        taken[next] = true;
+        for (i, d) in dist.iter_mut().enumerate() {
+            let to_next = points[i].square_distance(&points[next]);
+            if to_next < *d {
+                *d = to_next;
+            }
+        }
+    }
+    picked
+}
+
+///level of detail streaming order - a permutation of point indices where every prefix
+///is spread over the data: level l takes the first point per occupied cell of a 2^l
+///subdivision of the bounds (not yet covered by earlier picks), then the next level;
+///points with non-finite components come last
+pub fn progressive_order<C>(points: &[C]) -> Vec<usize>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let finite = |p: &C| (0..C::DIM).all(|i| p.val(i).is_finite());
+    let bounds = match points.iter().copied().filter_finite().bounds() {
+        Some(b) => b,
+        None => return (0..points.len()).collect(),
+    };
+    let extent = bounds.max.sub(&bounds.min);
+    let mut order = Vec::with_capacity(points.len());
+    let mut taken = vec![false; points.len()];
+    let mut divisions = C::Scalar::one();
+    let two = C::Scalar::one() + C::Scalar::one();
+    let n_finite = points.iter().filter(|p| finite(p)).count();
+    for _ in 0..=32 {
+        if order.len() == n_finite {
+            break;
+        }
+        let mut cells: HashSet<Vec<i64>> = HashSet::new();
+        let key = |p: &C| -> Vec<i64> {
+            (0..C::DIM)
+                .map(|i| {
+                    let e = extent.val(i);
+                    if e > C::Scalar::zero() {
+                        ((p.val(i) - bounds.min.val(i)) / e * divisions)
+                            .floor()
+                            .to_i64()
+                            .unwrap_or(0)
+                    } else {
+                        0
+                    }
+                })
+                .collect()
+        };
+        //cells covered by earlier picks stay covered
+        for &i in &order {
+            cells.insert(key(&points[i]));
+        }
+        for (i, p) in points.iter().enumerate() {
+            if !taken[i] && finite(p) && cells.insert(key(p)) {
+                taken[i] = true;
+                order.push(i);
+            }
+        }
+        divisions = divisions * two;
+    }
+    //finite duplicates left over from the last level, then the non-finite points
+    order.extend((0..points.len()).filter(|&i| !taken[i] && finite(&points[i])));
+    order.extend((0..points.len()).filter(|&i| !finite(&points[i])));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_voxel_downsample() {
+        let pts = vec![
+            Pt { x: 0.1, y: 0.1 },
+            Pt { x: 1.5, y: 0.5 },
+            Pt { x: 0.3, y: 0.5 },
+            Pt { x: -0.5, y: 0.5 },
+            Pt { x: f64::NAN, y: 0. },
+            Pt { x: 1.5, y: 0.7 },
+        ];
+        let out = voxel_downsample(&pts, 1.);
+        assert_eq!(out.len(), 3);
+        assert!((out[0].x - 0.2).abs() < 1e-12 && (out[0].y - 0.3).abs() < 1e-12);
+        assert!((out[1].x - 1.5).abs() < 1e-12 && (out[1].y - 0.6).abs() < 1e-12);
+        assert_eq!(out[2], Pt { x: -0.5, y: 0.5 });
+        assert!(voxel_downsample::<Pt<f64>>(&[], 1.).is_empty());
+    }
+
+    #[test]
+    fn test_farthest_point_sample() {
+        let pts: Vec<_> = (0..=10).map(|i| Pt { x: i as f64, y: 0. }).collect();
+        assert_eq!(farthest_point_sample(&pts, 3), vec![0, 10, 5]);
+        assert_eq!(farthest_point_sample(&pts, 5), vec![0, 10, 5, 2, 7]);
+        assert_eq!(farthest_point_sample(&pts, 100).len(), 11);
+        assert!(farthest_point_sample(&pts, 0).is_empty());
+
+        let dup = vec![Pt { x: 1, y: 1 }; 3];
+        assert_eq!(farthest_point_sample(&dup, 3), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_progressive_order() {
+        let pts: Vec<_> = (0..64)
+            .map(|i| Pt {
+                x: (i % 8) as f64,
+                y: (i / 8) as f64,
+            })
+            .collect();
+        let order = progressive_order(&pts);
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+        //one point per quadrant early on
+        let quadrants: HashSet<_> = order[..5]
+            .iter()
+            .map(|&i| (pts[i].x >= 4., pts[i].y >= 4.))
+            .collect();
+        assert_eq!(quadrants.len(), 4);
+
+        let mut dup = vec![Pt { x: 1., y: 1. }; 3];
+        dup.push(Pt { x: f64::NAN, y: 0. });
+        dup.push(Pt { x: 2., y: 2. });
+        assert_eq!(progressive_order(&dup), vec![0, 4, 1, 2, 3]);
+        dup.rotate_right(2);
+        assert_eq!(progressive_order(&dup), vec![1, 2, 3, 4, 0]);
+        assert!(progressive_order::<Pt<f64>>(&[]).is_empty());
+    }
+}
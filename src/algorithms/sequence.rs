@@ -0,0 +1,116 @@
+use super::ring::reverse_ring;
+use crate::Coordinate;
+
+fn closed<C: Coordinate>(points: &[C]) -> bool {
+    points.len() > 1 && points[0] == points[points.len() - 1]
+}
+
+///reverses a line end to end; a closed ring keeps its start (and closing) point
+pub fn reverse<C: Coordinate>(points: &mut [C]) {
+    if closed(points) {
+        reverse_ring(points);
+    } else {
+        points.reverse();
+    }
+}
+
+///rotates the points so that index comes first (wrapping around); a closed ring
+///is rotated over its distinct vertices and closed again at the new start
+pub fn rotate_start_to<C: Coordinate>(points: &mut [C], index: usize) {
+    let n = points.len();
+    if closed(points) {
+        points[..n - 1].rotate_left(index % (n - 1));
+        points[n - 1] = points[0];
+    } else if n > 0 {
+        points.rotate_left(index % n);
+    }
+}
+
+///joins two lines at the ends that are within eps, reversing b if needed; the shared
+///point is taken from a, and a result whose ends meet within eps is closed exactly.
+///None if no ends meet or either input is empty
+pub fn concat_snapped<C: Coordinate>(a: &[C], b: &[C], eps: C::Scalar) -> Option<Vec<C>> {
+    let (a0, a1) = (a.first()?, a.last()?);
+    let (b0, b1) = (b.first()?, b.last()?);
+    let near = |p: &C, q: &C| p.square_distance(q) <= eps * eps;
+    let mut out = Vec::with_capacity(a.len() + b.len() - 1);
+    if near(a1, b0) {
+        out.extend_from_slice(a);
+        out.extend(b.iter().skip(1));
+    } else if near(a1, b1) {
+        out.extend_from_slice(a);
+        out.extend(b.iter().rev().skip(1));
+    } else if near(a0, b1) {
+        out.extend(b.iter().take(b.len() - 1));
+        out.extend_from_slice(a);
+    } else if near(a0, b0) {
+        out.extend(b.iter().rev().take(b.len() - 1));
+        out.extend_from_slice(a);
+    } else {
+        return None;
+    }
+    let n = out.len();
+    if n > 2 && near(&out[0], &out[n - 1]) {
+        out[n - 1] = out[0];
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_reverse_and_rotate() {
+        let p = |x: i32, y: i32| Pt { x, y };
+        let mut line = vec![p(0, 0), p(1, 0), p(2, 1)];
+        reverse(&mut line);
+        assert_eq!(line, vec![p(2, 1), p(1, 0), p(0, 0)]);
+        rotate_start_to(&mut line, 4);
+        assert_eq!(line, vec![p(1, 0), p(0, 0), p(2, 1)]);
+
+        let mut ring = vec![p(0, 0), p(2, 0), p(2, 2), p(0, 2), p(0, 0)];
+        reverse(&mut ring);
+        assert_eq!(ring, vec![p(0, 0), p(0, 2), p(2, 2), p(2, 0), p(0, 0)]);
+        rotate_start_to(&mut ring, 2);
+        assert_eq!(ring, vec![p(2, 2), p(2, 0), p(0, 0), p(0, 2), p(2, 2)]);
+        rotate_start_to(&mut ring, 4);
+        assert_eq!(ring[0], p(2, 2));
+
+        let mut empty: Vec<Pt<i32>> = vec![];
+        rotate_start_to(&mut empty, 3);
+        reverse(&mut empty);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_concat_snapped() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let a = [p(0., 0.), p(1., 0.)];
+        let b = [p(1.001, 0.), p(2., 1.)];
+        assert_eq!(
+            concat_snapped(&a, &b, 0.01),
+            Some(vec![p(0., 0.), p(1., 0.), p(2., 1.)])
+        );
+        let rb = [p(2., 1.), p(1.001, 0.)];
+        assert_eq!(concat_snapped(&a, &rb, 0.01), concat_snapped(&a, &b, 0.01));
+        assert_eq!(
+            concat_snapped(&b, &a, 0.01),
+            Some(vec![p(0., 0.), p(1.001, 0.), p(2., 1.)])
+        );
+        assert_eq!(
+            concat_snapped(&[p(1., 0.), p(0., 0.)], &b, 0.01),
+            Some(vec![p(2., 1.), p(1., 0.), p(0., 0.)])
+        );
+        assert_eq!(concat_snapped(&a, &b, 0.0001), None);
+        assert_eq!(concat_snapped(&a, &[], 1.), None);
+
+        //the last fragment closes the ring
+        let c = [p(2., 1.), p(0., 0.001)];
+        let ab = concat_snapped(&a, &b, 0.01).unwrap();
+        let ring = concat_snapped(&ab, &c, 0.01).unwrap();
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring[0], ring[3]);
+    }
+}
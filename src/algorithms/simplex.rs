@@ -0,0 +1,279 @@
+use super::solve;
+use crate::Coordinate;
+use num_traits::{Float, One, Zero};
+
+///signed content (length, area, volume) of a DIM-simplex of DIM + 1 points -
+///positive for positively oriented vertices, None if the point count is wrong
+pub fn simplex_volume<C>(points: &[C]) -> Option<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.len() != C::DIM + 1 {
+        return None;
+    }
+    let p0 = points[0];
+    let mut m: Vec<Vec<C::Scalar>> = (0..C::DIM)
+        .map(|r| points[1..].iter().map(|p| p.val(r) - p0.val(r)).collect())
+        .collect();
+    let mut factorial = C::Scalar::one();
+    for k in 2..=C::DIM {
+        factorial = factorial * num_traits::cast(k)?;
+    }
+    Some(determinant(&mut m) / factorial)
+}
+
+///center of the sphere through 2..=DIM + 1 points, lying in their affine hull;
+///None if the points are affinely dependent
+pub fn circumcenter<C>(points: &[C]) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if points.len() < 2 || points.len() > C::DIM + 1 {
+        return None;
+    }
+    //center = p0 + sum(l_i * v_i) with sum_j 2(v_i.v_j) l_j = v_i.v_i
+    let p0 = points[0];
+    let v: Vec<C> = points[1..].iter().map(|p| p.sub(&p0)).collect();
+    let k = v.len();
+    let m = (0..k)
+        .map(|i| {
+            let mut row: Vec<C::Scalar> = (0..k).map(|j| v[i].dot(&v[j])).collect();
+            row.iter_mut().for_each(|x| *x = *x + *x);
+            row.push(v[i].dot(&v[i]));
+            row
+        })
+        .collect();
+    let l = solve(m)?;
+    Some(v.iter().zip(l).fold(p0, |c, (vi, li)| c.add(&vi.mult(li))))
+}
+
+///barycentric weights (summing to one) of pt relative to the DIM + 1 vertices
+///of a simplex, None if the simplex is degenerate
+pub fn barycentric<C>(simplex: &[C], pt: &C) -> Option<Vec<C::Scalar>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if simplex.len() != C::DIM + 1 {
+        return None;
+    }
+    let p0 = simplex[0];
+    let m = (0..C::DIM)
+        .map(|r| {
+            let mut row: Vec<C::Scalar> =
+                simplex[1..].iter().map(|p| p.val(r) - p0.val(r)).collect();
+            row.push(pt.val(r) - p0.val(r));
+            row
+        })
+        .collect();
+    let l = solve(m)?;
+    let first = l.iter().fold(C::Scalar::one(), |acc, &x| acc - x);
+    Some(std::iter::once(first).chain(l).collect())
+}
+
+///point with the given barycentric weights over the simplex vertices,
+///None if the counts differ
+pub fn from_barycentric<C>(simplex: &[C], weights: &[C::Scalar]) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if simplex.is_empty() || simplex.len() != weights.len() {
+        return None;
+    }
+    Some(C::gen(|i| {
+        simplex
+            .iter()
+            .zip(weights)
+            .fold(C::Scalar::zero(), |acc, (p, &w)| acc + w * p.val(i))
+    }))
+}
+
+///circumcenter of triangle a, b, c in 2D or 3D, None if collinear
+pub fn triangle_circumcenter<C>(a: &C, b: &C, c: &C) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    circumcenter(&[*a, *b, *c])
+}
+
+///incenter of triangle a, b, c - vertices weighted by opposite side lengths;
+///None if collinear
+pub fn incenter<C>(a: &C, b: &C, c: &C) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (ab, ac) = (b.sub(a), c.sub(a));
+    let area2 = ab
+        .wedge(&ac)
+        .into_iter()
+        .fold(C::Scalar::zero(), |acc, w| acc + w * w);
+    let tol = C::Scalar::epsilon() * num_traits::cast(1024)?;
+    let limit = tol * tol * ab.square_length() * ac.square_length();
+    if area2.is_nan() || area2 <= limit {
+        return None;
+    }
+    let (la, lb, lc) = (
+        b.square_distance(c).sqrt(),
+        a.square_distance(c).sqrt(),
+        a.square_distance(b).sqrt(),
+    );
+    let sum = la + lb + lc;
+    from_barycentric(&[*a, *b, *c], &[la / sum, lb / sum, lc / sum])
+}
+
+///determinant by elimination with partial pivoting
+fn determinant<T: Float>(m: &mut [Vec<T>]) -> T {
+    let k = m.len();
+    let mut det = T::one();
+    for col in 0..k {
+        let pivot = (col..k).fold(col, |best, r| {
+            if m[r][col].abs() > m[best][col].abs() {
+                r
+            } else {
+                best
+            }
+        });
+        if m[pivot][col] == T::zero() {
+            return T::zero();
+        }
+        if pivot != col {
+            m.swap(col, pivot);
+            det = -det;
+        }
+        det = det * m[col][col];
+        let (top, rest) = m.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for row in rest {
+            let f = row[col] / pivot_row[col];
+            for (dst, &src) in row.iter_mut().zip(pivot_row).skip(col) {
+                *dst = *dst - f * src;
+            }
+        }
+    }
+    det
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_simplex_volume() {
+        let tri = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 4., y: 0. },
+            Pt { x: 0., y: 3. },
+        ];
+        assert_eq!(simplex_volume(&tri), Some(6.));
+        assert_eq!(simplex_volume(&[tri[0], tri[2], tri[1]]), Some(-6.));
+        assert_eq!(simplex_volume(&tri[..2]), None);
+
+        let tet = [
+            Coord([0., 0., 0.]),
+            Coord([1., 0., 0.]),
+            Coord([0., 1., 0.]),
+            Coord([0., 0., 1.]),
+        ];
+        assert!((simplex_volume(&tet).unwrap() - 1. / 6.).abs() < 1e-15);
+        let flat = [tet[0], tet[1], tet[2], Coord([1., 1., 0.])];
+        assert_eq!(simplex_volume(&flat), Some(0.));
+        assert_eq!(simplex_volume(&[Coord([2.]), Coord([-1.])]), Some(-3.));
+    }
+
+    #[test]
+    fn test_circumcenter() {
+        let tri = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 4., y: 0. },
+            Pt { x: 0., y: 2. },
+        ];
+        assert_eq!(circumcenter(&tri), Some(Pt { x: 2., y: 1. }));
+        assert_eq!(circumcenter(&tri[..2]), Some(Pt { x: 2., y: 0. }));
+        assert_eq!(circumcenter(&tri[..1]), None);
+        let line = [tri[0], tri[1], Pt { x: 8., y: 0. }];
+        assert_eq!(circumcenter(&line), None);
+
+        let tet = [
+            Coord([1., 1., 1.]),
+            Coord([1., -1., -1.]),
+            Coord([-1., 1., -1.]),
+            Coord([-1., -1., 1.]),
+        ];
+        let c = circumcenter(&tet).unwrap();
+        assert!(c.square_length() < 1e-24);
+        //triangle in 3D - the center stays in its plane
+        let c = circumcenter(&[
+            Coord([0., 0., 5.]),
+            Coord([2., 0., 5.]),
+            Coord([0., 2., 5.]),
+        ]);
+        assert_eq!(c, Some(Coord([1., 1., 5.])));
+    }
+
+    #[test]
+    fn test_triangle_centers() {
+        let (a, b, c) = (
+            Pt { x: 0., y: 0. },
+            Pt { x: 4., y: 0. },
+            Pt { x: 0., y: 3. },
+        );
+        assert_eq!(
+            triangle_circumcenter(&a, &b, &c),
+            Some(Pt { x: 2., y: 1.5 })
+        );
+        //3-4-5 right triangle - inradius 1
+        assert_eq!(incenter(&a, &b, &c), Some(Pt { x: 1., y: 1. }));
+        let d = Pt { x: 8., y: 1e-17 };
+        assert_eq!(triangle_circumcenter(&a, &b, &d), None);
+        assert_eq!(incenter(&a, &b, &d), None);
+        assert_eq!(incenter(&a, &a, &c), None);
+
+        let (a, b, c) = (
+            Coord([0., 0., 2.]),
+            Coord([0., 4., 2.]),
+            Coord([0., 0., 5.]),
+        );
+        assert_eq!(
+            triangle_circumcenter(&a, &b, &c),
+            Some(Coord([0., 2., 3.5]))
+        );
+        assert_eq!(incenter(&a, &b, &c), Some(Coord([0., 1., 3.])));
+    }
+
+    #[test]
+    fn test_barycentric() {
+        let tri = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 4., y: 0. },
+            Pt { x: 0., y: 4. },
+        ];
+        let w = barycentric(&tri, &Pt { x: 1., y: 2. }).unwrap();
+        assert_eq!(w, vec![0.25, 0.25, 0.5]);
+        assert_eq!(from_barycentric(&tri, &w), Some(Pt { x: 1., y: 2. }));
+        let outside = barycentric(&tri, &Pt { x: 5., y: 0. }).unwrap();
+        assert!(outside[0] < 0.);
+        assert_eq!(
+            barycentric(&[tri[0], tri[1], Pt { x: 8., y: 0. }], &tri[2]),
+            None
+        );
+        assert_eq!(from_barycentric(&tri, &[1.]), None);
+
+        let tet = [
+            Coord([0., 0., 0.]),
+            Coord([2., 0., 0.]),
+            Coord([0., 2., 0.]),
+            Coord([0., 0., 2.]),
+        ];
+        let p = Coord([0.5, 0.5, 0.5]);
+        let w = barycentric(&tet, &p).unwrap();
+        assert_eq!(w, vec![0.25; 4]);
+        assert_eq!(from_barycentric(&tet, &w), Some(p));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::Coordinate;
+
+///n-ball (circle in 2D, sphere in 3D) - center and radius
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ball<C: Coordinate> {
+    pub center: C,
+    pub radius: C::Scalar,
+}
+
+impl<C: Coordinate> Ball<C> {
+    ///new ball from center and radius
+    pub fn new(center: C, radius: C::Scalar) -> Self {
+        Ball { center, radius }
+    }
+
+    ///radius * radius
+    pub fn square_radius(&self) -> C::Scalar {
+        self.radius * self.radius
+    }
+
+    ///checks if pt is inside or on the boundary
+    pub fn contains(&self, pt: &C) -> bool {
+        self.center.square_distance(pt) <= self.square_radius()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_ball() {
+        let b = Ball::new(Pt { x: 1, y: 1 }, 5);
+        assert_eq!(b.square_radius(), 25);
+        assert!(b.contains(&Pt { x: 4, y: 5 }));
+        assert!(b.contains(&Pt { x: 1, y: 1 }));
+        assert!(!b.contains(&Pt { x: 5, y: 5 }));
+    }
+}
@@ -0,0 +1,235 @@
+//!loops over point slices - centroid, bounds, closest pair, polyline length,
+//!pairwise distances and k nearest neighbour joins
+use crate::iter::CoordIter;
+use crate::{Aabb, Coordinate, CoordinateFloat, NTree};
+use num_traits::{Float, Zero};
+use std::cmp::Ordering;
+
+///arithmetic mean of points, None if empty
+pub fn centroid<C>(points: &[C]) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    crate::algorithms::centroid(points)
+}
+
+///bounds of points, None if empty
+pub fn bounds<C: Coordinate>(points: &[C]) -> Option<Aabb<C>> {
+    points.iter().copied().bounds()
+}
+
+///nearest pair of points as (i, j, square distance) with i < j, None for fewer
+///than two points; a sweep along dimension 0 that skips pairs further apart on
+///that dimension than the best so far
+pub fn closest_pair<C: Coordinate>(points: &[C]) -> Option<(usize, usize, C::Scalar)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| {
+        points[a]
+            .val(0)
+            .partial_cmp(&points[b].val(0))
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut best = (
+        order[0],
+        order[1],
+        points[order[0]].square_distance(&points[order[1]]),
+    );
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            let dx = points[j].val(0) - points[i].val(0);
+            if dx * dx > best.2 {
+                break;
+            }
+            let d = points[i].square_distance(&points[j]);
+            if d < best.2 {
+                best = (i.min(j), i.max(j), d);
+            }
+        }
+    }
+    Some(best)
+}
+
+///length of the polyline through points, zero for fewer than two
+pub fn total_length<C>(points: &[C]) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    points
+        .windows(2)
+        .fold(C::Scalar::zero(), |acc, w| acc + w[0].distance(&w[1]))
+}
+
+///square distances from each of a to each of b written row major into out -
+///out[i * b.len() + j] is between a[i] & b[j]; reuse out across calls to avoid
+///allocating. Panics if out.len() != a.len() * b.len()
+pub fn pairwise_square_distances<C: Coordinate>(a: &[C], b: &[C], out: &mut [C::Scalar]) {
+    assert_eq!(out.len(), a.len() * b.len());
+    if b.is_empty() {
+        return;
+    }
+    for (row, p) in out.chunks_mut(b.len()).zip(a) {
+        for (d, q) in row.iter_mut().zip(b) {
+            *d = p.square_distance(q);
+        }
+    }
+}
+
+///points of b per leaf of the tree built by `knn_join`
+const KNN_LEAF: usize = 16;
+///highest dimension `knn_join` indexes in an `NTree` - each split allocates 2^DIM
+///children, so higher dimensions use a linear scan per query
+const KNN_TREE_MAX_DIM: usize = 4;
+
+///the k nearest points of b to each point of a as (index into b, square distance)
+///in order of increasing distance - up to four dimensions b is indexed once in an
+///`NTree` and each query stops after k points, in higher dimensions each query
+///selects from the distances to all of b; fewer than k if b is smaller
+pub fn knn_join<C: Coordinate>(a: &[C], b: &[C], k: usize) -> Vec<Vec<(usize, C::Scalar)>> {
+    if C::DIM > KNN_TREE_MAX_DIM {
+        return a.iter().map(|q| knn_scan(q, b, k)).collect();
+    }
+    let tree = match bounds(b) {
+        Some(bounds) => {
+            let mut tree = NTree::new(bounds, KNN_LEAF);
+            for (i, pt) in b.iter().enumerate() {
+                tree.insert(*pt, i);
+            }
+            tree
+        }
+        None => return vec![Vec::new(); a.len()],
+    };
+    a.iter()
+        .map(|q| {
+            tree.nearest_iter(*q)
+                .take(k)
+                .map(|(_, &i, d)| (i, d))
+                .collect()
+        })
+        .collect()
+}
+
+///k nearest of b to q by partial selection over all distances, ties by index
+fn knn_scan<C: Coordinate>(q: &C, b: &[C], k: usize) -> Vec<(usize, C::Scalar)> {
+    let by_dist = |x: &(usize, C::Scalar), y: &(usize, C::Scalar)| {
+        x.1.partial_cmp(&y.1)
+            .unwrap_or(Ordering::Equal)
+            .then(x.0.cmp(&y.0))
+    };
+    let mut dists: Vec<_> = b
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i, p.square_distance(q)))
+        .collect();
+    let k = k.min(dists.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    dists.select_nth_unstable_by(k - 1, by_dist);
+    dists.truncate(k);
+    dists.sort_unstable_by(by_dist);
+    dists
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Bounds, Coord};
+
+    #[test]
+    fn test_centroid_bounds_length() {
+        let pts = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 3., y: 4. },
+            Pt { x: 3., y: 0. },
+        ];
+        assert_eq!(centroid(&pts), Some(Pt { x: 2., y: 4. / 3. }));
+        assert_eq!(
+            bounds(&pts),
+            Some(Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 3., y: 4. }))
+        );
+        assert_eq!(total_length(&pts), 9.);
+        assert_eq!(total_length(&pts[..1]), 0.);
+        assert_eq!(centroid::<Pt<f64>>(&[]), None);
+        assert_eq!(bounds::<Pt<i32>>(&[]), None);
+    }
+
+    #[test]
+    fn test_closest_pair() {
+        let pts: Vec<_> = (0..50)
+            .map(|i| Pt {
+                x: (i * 37) % 101,
+                y: (i * 53) % 97,
+            })
+            .collect();
+        let brute = (0..pts.len())
+            .flat_map(|i| (i + 1..pts.len()).map(move |j| (i, j)))
+            .map(|(i, j)| pts[i].square_distance(&pts[j]))
+            .min();
+        let (i, j, d) = closest_pair(&pts).unwrap();
+        assert!(i < j && Some(d) == brute && pts[i].square_distance(&pts[j]) == d);
+
+        let dup = [Pt { x: 5, y: 5 }, Pt { x: 0, y: 0 }, Pt { x: 5, y: 5 }];
+        assert_eq!(closest_pair(&dup), Some((0, 2, 0)));
+        assert_eq!(closest_pair(&dup[..1]), None);
+    }
+
+    #[test]
+    fn test_pairwise_square_distances() {
+        let a = [Pt { x: 0, y: 0 }, Pt { x: 1, y: 1 }];
+        let b = [Pt { x: 1, y: 0 }, Pt { x: 2, y: 2 }, Pt { x: 0, y: 3 }];
+        let mut out = vec![0; 6];
+        pairwise_square_distances(&a, &b, &mut out);
+        assert_eq!(out, vec![1, 8, 9, 1, 2, 5]);
+        pairwise_square_distances(&a, &[], &mut []);
+    }
+
+    #[test]
+    fn test_knn_join() {
+        let b: Vec<_> = (0..40)
+            .map(|i| Pt {
+                x: (i * 17) % 23,
+                y: (i * 29) % 31,
+            })
+            .collect();
+        let a = [Pt { x: 5, y: 5 }, Pt { x: -10, y: 40 }, b[7]];
+        let joined = knn_join(&a, &b, 4);
+        assert_eq!(joined.len(), 3);
+        for (q, nn) in a.iter().zip(&joined) {
+            let mut brute: Vec<_> = b.iter().map(|p| p.square_distance(q)).collect();
+            brute.sort_unstable();
+            let dists: Vec<_> = nn.iter().map(|&(_, d)| d).collect();
+            assert_eq!(dists, brute[..4].to_vec());
+            assert!(nn.iter().all(|&(i, d)| b[i].square_distance(q) == d));
+        }
+        assert_eq!(joined[2][0], (7, 0));
+        assert_eq!(knn_join(&a, &b[..2], 5)[0].len(), 2);
+        assert_eq!(knn_join(&a, &[], 3), vec![vec![]; 3]);
+        assert!(knn_join(&[], &b, 3).is_empty());
+    }
+
+    #[test]
+    fn test_knn_join_high_dim() {
+        let b: Vec<Coord<i64, 24>> = (0..60)
+            .map(|i| Coord(std::array::from_fn(|k| (i * (k as i64 + 7)) % 13)))
+            .collect();
+        let a = [b[3], Coord([5; 24]), Coord([-4; 24])];
+        let joined = knn_join(&a, &b, 5);
+        for (q, nn) in a.iter().zip(&joined) {
+            let mut brute: Vec<_> = b.iter().map(|p| p.square_distance(q)).collect();
+            brute.sort_unstable();
+            let dists: Vec<_> = nn.iter().map(|&(_, d)| d).collect();
+            assert_eq!(dists, brute[..5].to_vec());
+            assert!(nn.iter().all(|&(i, d)| b[i].square_distance(q) == d));
+        }
+        assert_eq!(joined[0][0], (3, 0));
+        assert_eq!(knn_join(&a, &b[..2], 5)[1].len(), 2);
+        assert_eq!(knn_join(&a, &b, 0), vec![vec![]; 3]);
+        assert_eq!(knn_join(&a, &[], 3), vec![vec![]; 3]);
+    }
+}
@@ -0,0 +1,640 @@
+use crate::{Coordinate, Interval, OutwardRound};
+use bs_num::Numeric;
+use num_traits::{Bounded, Float, One, Zero};
+use std::cmp::Ordering;
+
+///axis aligned bounds of coordinates - min & max corners
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Bounds<C: Coordinate> {
+    pub min: C,
+    pub max: C,
+}
+
+impl<C: Coordinate> Bounds<C> {
+    ///new bounds from two corners in any order
+    pub fn new(a: C, b: C) -> Self {
+        Bounds {
+            min: a.min_of_bounds(&b),
+            max: a.max_of_bounds(&b),
+        }
+    }
+
+    ///inverted bounds (min at the scalar max, max at the scalar min) - contains
+    ///nothing, intersects nothing and is the identity of `union`, so bounds can be
+    ///folded from it
+    pub fn empty() -> Self {
+        Bounds {
+            min: C::new_from_value(C::Scalar::max_value()),
+            max: C::new_from_value(C::Scalar::min_value()),
+        }
+    }
+
+    ///bounds spanning the whole scalar range (finite for floats) - contains every
+    ///point and is the identity of `intersection`
+    pub fn everything() -> Self {
+        Bounds {
+            min: C::new_from_value(C::Scalar::min_value()),
+            max: C::new_from_value(C::Scalar::max_value()),
+        }
+    }
+
+    ///checks if min > max on any dimension, as for `empty`
+    pub fn is_empty(&self) -> bool {
+        !self.min.all_comp(&self.max, |lo, hi| lo <= hi)
+    }
+
+    ///bounds centered on center reaching half along each dimension either way
+    pub fn from_center_half_extents(center: C, half: C) -> Self {
+        Bounds::new(center.sub(&half), center.add(&half))
+    }
+
+    ///degenerate bounds of a single point
+    pub fn from_point(pt: C) -> Self {
+        Bounds { min: pt, max: pt }
+    }
+
+    ///grows bounds to include pt
+    pub fn expand_to_include(&mut self, pt: &C) {
+        self.min = self.min.min_of_bounds(pt);
+        self.max = self.max.max_of_bounds(pt);
+    }
+
+    ///checks if pt is inside or on the boundary
+    pub fn contains(&self, pt: &C) -> bool {
+        self.min.all_comp(pt, |lo, v| lo <= v) && pt.all_comp(&self.max, |v, hi| v <= hi)
+    }
+
+    ///checks if self & other overlap or touch, never for an empty bounds
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.min.all_comp(&other.max, |lo, hi| lo <= hi)
+            && other.min.all_comp(&self.max, |lo, hi| lo <= hi)
+    }
+
+    ///smallest bounds enclosing self & other
+    pub fn union(&self, other: &Self) -> Self {
+        Bounds {
+            min: self.min.min_of_bounds(&other.min),
+            max: self.max.max_of_bounds(&other.max),
+        }
+    }
+
+    ///overlap of self & other, None if disjoint
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Bounds {
+            min: self.min.max_of_bounds(&other.min),
+            max: self.max.min_of_bounds(&other.max),
+        })
+    }
+
+    ///bounds grown by margin on every side - a query window with a tolerance;
+    ///use `deflate` to shrink
+    pub fn inflate(&self, margin: C::Scalar) -> Self {
+        self.inflate_per_axis(&C::new_from_value(margin))
+    }
+
+    ///bounds grown on both sides of each dimension by that dimension of margins
+    pub fn inflate_per_axis(&self, margins: &C) -> Self {
+        Bounds {
+            min: self.min.sub(margins),
+            max: self.max.add(margins),
+        }
+    }
+
+    ///bounds shrunk by margin on every side, None if that collapses it to empty -
+    ///margin more than half the extent on any dimension
+    pub fn deflate(&self, margin: C::Scalar) -> Option<Self> {
+        let min = self.min.map(|v| v + margin);
+        let max = self.max.map(|v| v - margin);
+        if min.all_comp(&max, |lo, hi| lo <= hi) {
+            Some(Bounds { min, max })
+        } else {
+            None
+        }
+    }
+
+    ///size along each dimension - zero where empty, saturating at the scalar max
+    ///(as for integer `everything`)
+    pub fn extents(&self) -> C {
+        C::gen(|i| span(self.min.val(i), self.max.val(i)))
+    }
+
+    ///midpoint of the min & max corners (truncated towards min for integer scalars),
+    ///min where empty
+    pub fn center(&self) -> C {
+        C::gen(|i| mid(self.min.val(i), self.max.val(i)))
+    }
+
+    ///half the size along each dimension (truncated for integer scalars)
+    pub fn half_extents(&self) -> C {
+        let two = C::Scalar::one() + C::Scalar::one();
+        self.extents().map(|v| v / two)
+    }
+
+    ///corner i of the 2^DIM corners - bit k of i picks max over min on dimension k,
+    ///so corner 0 is min and corner 2^DIM - 1 is max
+    pub fn corner(&self, i: usize) -> C {
+        C::gen(|k| {
+            if (i >> k) & 1 == 1 {
+                self.max.val(k)
+            } else {
+                self.min.val(k)
+            }
+        })
+    }
+
+    ///all 2^DIM corners in `corner` order
+    pub fn corners(&self) -> impl Iterator<Item = C> + '_ {
+        (0..1usize << C::DIM).map(move |i| self.corner(i))
+    }
+
+    ///halves below & above value on axis, sharing the cut face; value is clamped
+    ///into the bounds so a cut outside leaves one half degenerate
+    pub fn split_at(&self, axis: usize, value: C::Scalar) -> (Self, Self) {
+        let (lo, hi) = (self.min.val(axis), self.max.val(axis));
+        let v = if value < lo {
+            lo
+        } else if value > hi {
+            hi
+        } else {
+            value
+        };
+        let (mut below, mut above) = (*self, *self);
+        *below.max.val_mut(axis) = v;
+        *above.min.val_mut(axis) = v;
+        (below, above)
+    }
+
+    ///the 2^DIM children split at the center - quadrants in 2D, octants in 3D - in
+    ///`corner` order: child i holds corner i. Children share faces, and collapse to
+    ///the parent's extent on a dimension of zero extent
+    pub fn subdivide(&self) -> Vec<Self> {
+        let center = self.center();
+        (0..1usize << C::DIM)
+            .map(|i| Bounds {
+                min: C::gen(|k| {
+                    if (i >> k) & 1 == 1 {
+                        center.val(k)
+                    } else {
+                        self.min.val(k)
+                    }
+                }),
+                max: C::gen(|k| {
+                    if (i >> k) & 1 == 1 {
+                        self.max.val(k)
+                    } else {
+                        center.val(k)
+                    }
+                }),
+            })
+            .collect()
+    }
+
+    ///product of extents - area in 2D, volume in 3D; zero if empty, saturating at
+    ///the scalar max
+    pub fn volume(&self) -> C::Scalar {
+        if self.is_empty() {
+            return C::Scalar::zero();
+        }
+        self.extents().fold(C::Scalar::one(), |acc, v| {
+            if v > C::Scalar::zero() && acc > C::Scalar::max_value() / v {
+                C::Scalar::max_value()
+            } else {
+                acc * v
+            }
+        })
+    }
+
+    ///alias of volume
+    pub fn area(&self) -> C::Scalar {
+        self.volume()
+    }
+
+    ///volume of the intersection of self & other, zero if disjoint
+    pub fn overlap_area(&self, other: &Self) -> C::Scalar {
+        self.intersection(other)
+            .map_or_else(C::Scalar::zero, |b| b.volume())
+    }
+
+    ///volume self grows by to enclose other - the R-tree insertion cost
+    pub fn enlargement_needed(&self, other: &Self) -> C::Scalar {
+        self.union(other).volume() - self.volume()
+    }
+
+    ///intersection over union of the volumes in [0, 1]; zero if the union has
+    ///no volume
+    pub fn iou(&self, other: &Self) -> C::Scalar
+    where
+        C::Scalar: Float,
+    {
+        let overlap = self.overlap_area(other);
+        let union = self.volume() + other.volume() - overlap;
+        if union > C::Scalar::zero() {
+            overlap / union
+        } else {
+            C::Scalar::zero()
+        }
+    }
+
+    ///smallest square distance between a point of self & a point of other,
+    ///zero if they intersect - a lower bound for pruning in best first search
+    pub fn min_square_distance(&self, other: &Self) -> C::Scalar {
+        (0..C::DIM).fold(C::Scalar::zero(), |acc, i| {
+            let d = if other.min.val(i) > self.max.val(i) {
+                other.min.val(i) - self.max.val(i)
+            } else if self.min.val(i) > other.max.val(i) {
+                self.min.val(i) - other.max.val(i)
+            } else {
+                C::Scalar::zero()
+            };
+            acc + d * d
+        })
+    }
+
+    ///largest square distance between a point of self & a point of other -
+    ///between the farthest pair of corners
+    pub fn max_square_distance(&self, other: &Self) -> C::Scalar {
+        (0..C::DIM).fold(C::Scalar::zero(), |acc, i| {
+            let a = other.max.val(i) - self.min.val(i);
+            let b = self.max.val(i) - other.min.val(i);
+            let d = if a > b { a } else { b };
+            acc + d * d
+        })
+    }
+
+    ///bounds enclosing an interval valued coordinate
+    pub fn from_interval_coord<I>(coord: &I) -> Self
+    where
+        C::Scalar: OutwardRound,
+        I: Coordinate<Scalar = Interval<C::Scalar>>,
+    {
+        Bounds {
+            min: C::gen(|i| coord.val(i).lo()),
+            max: C::gen(|i| coord.val(i).hi()),
+        }
+    }
+}
+
+///hi - lo, zero if hi <= lo and the scalar max if it would overflow
+fn span<T: Numeric>(lo: T, hi: T) -> T {
+    if hi <= lo {
+        T::zero()
+    } else if lo < T::zero() && hi > T::max_value() + lo {
+        T::max_value()
+    } else {
+        hi - lo
+    }
+}
+
+///lo + (hi - lo) / 2 without overflow, lo if hi <= lo
+fn mid<T: Numeric>(lo: T, hi: T) -> T {
+    let two = T::one() + T::one();
+    if hi <= lo {
+        lo
+    } else if lo < T::zero() && hi > T::zero() {
+        //opposite signs cannot overflow the sum
+        let (sum, half) = (lo + hi, (lo + hi) / two);
+        //integer division truncates towards zero, round towards lo instead
+        if half + half > sum {
+            half - T::one()
+        } else {
+            half
+        }
+    } else {
+        lo + (hi - lo) / two
+    }
+}
+
+///bounds of consecutive chunks of at most chunk_size points - a coarse spatial index
+///of a point sequence, chunk i covers points [i * chunk_size, (i + 1) * chunk_size)
+pub fn chunk_bounds<C: Coordinate>(points: &[C], chunk_size: usize) -> Vec<Bounds<C>> {
+    points
+        .chunks(chunk_size.max(1))
+        .map(|chunk| {
+            let mut bounds = Bounds::from_point(chunk[0]);
+            for pt in &chunk[1..] {
+                bounds.expand_to_include(pt);
+            }
+            bounds
+        })
+        .collect()
+}
+
+///sort-tile-recursive grouping of items into leaves of at most node_capacity for
+///bulk loading a spatial index - items are sorted by the center of their bounds
+///and cut into slabs along each dimension in turn, the last one into leaves
+pub fn str_pack<C, T>(items: &[(Bounds<C>, T)], node_capacity: usize) -> Vec<Vec<(Bounds<C>, T)>>
+where
+    C: Coordinate,
+    T: Clone,
+{
+    let mut leaves = Vec::new();
+    str_tile(items.to_vec(), 0, node_capacity.max(1), &mut leaves);
+    leaves
+}
+
+fn str_tile<C, T>(
+    mut items: Vec<(Bounds<C>, T)>,
+    axis: usize,
+    capacity: usize,
+    leaves: &mut Vec<Vec<(Bounds<C>, T)>>,
+) where
+    C: Coordinate,
+{
+    //twice the center, exact for integer scalars
+    let mid = |b: &Bounds<C>| b.min.val(axis) + b.max.val(axis);
+    items.sort_by(|a, b| mid(&a.0).partial_cmp(&mid(&b.0)).unwrap_or(Ordering::Equal));
+    if axis + 1 >= C::DIM || items.len() <= capacity {
+        let mut rest = items.into_iter().peekable();
+        while rest.peek().is_some() {
+            leaves.push(rest.by_ref().take(capacity).collect());
+        }
+        return;
+    }
+    //slabs per remaining dimension so that leaves come out roughly square
+    let pages = (items.len() + capacity - 1) / capacity;
+    let slabs = (pages as f64).powf(1. / (C::DIM - axis) as f64).ceil() as usize;
+    let slab_size = capacity * ((pages + slabs - 1) / slabs);
+    let mut rest = items.into_iter().peekable();
+    while rest.peek().is_some() {
+        let slab = rest.by_ref().take(slab_size).collect();
+        str_tile(slab, axis + 1, capacity, leaves);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_chunk_bounds() {
+        let pts: Vec<_> = (0..5)
+            .map(|i| Pt {
+                x: i,
+                y: 10 - 2 * i,
+            })
+            .collect();
+        assert_eq!(
+            chunk_bounds(&pts, 2),
+            vec![
+                Bounds::new(Pt { x: 0, y: 10 }, Pt { x: 1, y: 8 }),
+                Bounds::new(Pt { x: 2, y: 6 }, Pt { x: 3, y: 4 }),
+                Bounds::from_point(Pt { x: 4, y: 2 }),
+            ]
+        );
+        assert_eq!(chunk_bounds(&pts, 0).len(), 5);
+        assert!(chunk_bounds::<Pt<i32>>(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_str_pack() {
+        let items: Vec<_> = (0..100)
+            .map(|i| {
+                let pt = Pt {
+                    x: (i % 10) as f64,
+                    y: (i / 10) as f64,
+                };
+                (Bounds::new(pt, pt.add(&Pt { x: 1., y: 1. })), i)
+            })
+            .collect();
+        let leaves = str_pack(&items, 10);
+        assert_eq!(leaves.len(), 10);
+        assert!(leaves.iter().all(|leaf| leaf.len() <= 10));
+        let mut ids: Vec<_> = leaves.iter().flatten().map(|(_, i)| *i).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, (0..100).collect::<Vec<_>>());
+        //first slab holds the three left columns, its first leaf the bottom rows
+        let first = leaves[0]
+            .iter()
+            .fold(Bounds::empty(), |acc, (b, _)| acc.union(b));
+        assert_eq!(first, Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 3., y: 4. }));
+
+        assert_eq!(str_pack(&items[..3], 0).len(), 3);
+        assert_eq!(str_pack(&items[..3], 8), vec![items[..3].to_vec()]);
+        assert!(str_pack::<Pt<f64>, ()>(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_inflate_deflate() {
+        let a = Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 4, y: 2 });
+        assert_eq!(
+            a.inflate(1),
+            Bounds::new(Pt { x: -1, y: -1 }, Pt { x: 5, y: 3 })
+        );
+        assert_eq!(
+            a.inflate_per_axis(&Pt { x: 2, y: 0 }),
+            Bounds::new(Pt { x: -2, y: 0 }, Pt { x: 6, y: 2 })
+        );
+        assert_eq!(a.inflate(0), a);
+        assert_eq!(a.inflate(3).deflate(3), Some(a));
+        assert_eq!(
+            a.deflate(1),
+            Some(Bounds::new(Pt { x: 1, y: 1 }, Pt { x: 3, y: 1 }))
+        );
+        assert_eq!(a.deflate(2), None);
+        assert!(Bounds::from_point(Pt { x: 0.5, y: 0.5 })
+            .inflate(0.5)
+            .contains(&Pt { x: 1.0, y: 0.0 }));
+    }
+
+    #[test]
+    fn test_empty_everything() {
+        let empty = Bounds::<Pt<i32>>::empty();
+        let all = Bounds::<Pt<i32>>::everything();
+        let a = Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 4, y: 2 });
+        assert!(empty.is_empty() && !all.is_empty() && !a.is_empty());
+        assert_eq!(empty.union(&a), a);
+        assert_eq!(a.union(&empty), a);
+        assert_eq!(all.union(&a), all);
+        assert_eq!(all.intersection(&a), Some(a));
+        assert!(!empty.intersects(&a) && !empty.intersects(&all) && !all.intersects(&empty));
+        assert_eq!(empty.intersection(&empty), None);
+        assert!(!empty.contains(&Pt { x: 0, y: 0 }));
+        assert!(all.contains(&Pt {
+            x: i32::MIN,
+            y: i32::MAX
+        }));
+        assert_eq!(empty.volume(), 0);
+        //integer extremes saturate instead of overflowing
+        assert_eq!(all.extents(), Pt::new_from_value(i32::MAX));
+        assert_eq!(all.volume(), i32::MAX);
+        assert_eq!(all.center(), Pt { x: -1, y: -1 });
+        assert_eq!(all.half_extents(), Pt::new_from_value(i32::MAX / 2));
+        assert_eq!(all.enlargement_needed(&a), 0);
+        assert_eq!(a.enlargement_needed(&all), i32::MAX - 8);
+        assert_eq!(empty.extents(), Pt { x: 0, y: 0 });
+        assert_eq!(empty.center(), empty.min);
+        assert_eq!(empty.half_extents(), Pt { x: 0, y: 0 });
+        assert_eq!(empty.enlargement_needed(&all), i32::MAX);
+        let odd = Bounds::new(Pt { x: -3, y: -3 }, Pt { x: 2, y: 4 });
+        assert_eq!(odd.center(), Pt { x: -1, y: 0 });
+        let bytes = Bounds::<Pt<i8>>::everything();
+        assert_eq!(bytes.volume(), i8::MAX);
+        assert_eq!(bytes.center(), Pt { x: -1, y: -1 });
+        let upper = Bounds::new(Pt { x: 1i8, y: 0 }, Pt { x: 127, y: 127 });
+        assert_eq!(
+            (upper.extents(), upper.center()),
+            (Pt { x: 126, y: 127 }, Pt { x: 64, y: 63 })
+        );
+        let floats = Bounds::<Pt<f64>>::everything();
+        assert_eq!(floats.center(), Pt { x: 0., y: 0. });
+        assert_eq!(floats.volume(), f64::MAX);
+
+        let pts = [
+            Pt { x: 1.5, y: -2. },
+            Pt { x: -3., y: 4. },
+            Pt { x: 0., y: 0. },
+        ];
+        let folded = pts
+            .iter()
+            .fold(Bounds::empty(), |acc, p| acc.union(&Bounds::from_point(*p)));
+        assert_eq!(
+            folded,
+            Bounds::new(Pt { x: -3., y: -2. }, Pt { x: 1.5, y: 4. })
+        );
+        let mut grown = Bounds::empty();
+        pts.iter().for_each(|p| grown.expand_to_include(p));
+        assert_eq!(grown, folded);
+        assert!(Bounds::<Pt<f64>>::empty()
+            .union(&Bounds::empty())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_center_and_corners() {
+        let a = Bounds::from_center_half_extents(Pt { x: 1.0, y: -1.0 }, Pt { x: 2.0, y: 0.5 });
+        assert_eq!(
+            a,
+            Bounds::new(Pt { x: -1.0, y: -1.5 }, Pt { x: 3.0, y: -0.5 })
+        );
+        assert_eq!(a.center(), Pt { x: 1.0, y: -1.0 });
+        assert_eq!(a.half_extents(), Pt { x: 2.0, y: 0.5 });
+        assert_eq!(
+            a.corners().collect::<Vec<_>>(),
+            vec![
+                Pt { x: -1.0, y: -1.5 },
+                Pt { x: 3.0, y: -1.5 },
+                Pt { x: -1.0, y: -0.5 },
+                Pt { x: 3.0, y: -0.5 },
+            ]
+        );
+        //a negative half extent is the same box
+        let b = Bounds::from_center_half_extents(Pt { x: 0, y: 0 }, Pt { x: -1, y: 2 });
+        assert_eq!(b, Bounds::new(Pt { x: -1, y: -2 }, Pt { x: 1, y: 2 }));
+
+        let cube = Bounds::new(crate::Coord([0, 0, 0]), crate::Coord([1, 2, 3]));
+        let corners: Vec<_> = cube.corners().collect();
+        assert_eq!(corners.len(), 8);
+        assert_eq!(corners[5], crate::Coord([1, 0, 3]));
+        assert!(corners.iter().all(|c| cube.contains(c)));
+    }
+
+    #[test]
+    fn test_overlap_metrics() {
+        let a = Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 4, y: 2 });
+        let b = Bounds::new(Pt { x: 3, y: 1 }, Pt { x: 6, y: 5 });
+        assert_eq!(a.overlap_area(&b), 1);
+        assert_eq!(b.overlap_area(&a), 1);
+        assert_eq!(a.overlap_area(&Bounds::from_point(Pt { x: 9, y: 9 })), 0);
+        assert_eq!(a.enlargement_needed(&b), 30 - 8);
+        assert_eq!(
+            a.enlargement_needed(&Bounds::from_point(Pt { x: 1, y: 1 })),
+            0
+        );
+        assert_eq!(Bounds::empty().enlargement_needed(&a), 8);
+
+        let c = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 2., y: 2. });
+        let d = Bounds::new(Pt { x: 1., y: 0. }, Pt { x: 3., y: 2. });
+        assert_eq!(c.iou(&d), 2. / 6.);
+        assert_eq!(c.iou(&c), 1.);
+        assert_eq!(c.iou(&Bounds::from_point(Pt { x: 5., y: 5. })), 0.);
+        let p = Bounds::from_point(Pt { x: 1., y: 1. });
+        assert_eq!(p.iou(&p), 0.);
+    }
+
+    #[test]
+    fn test_square_distances() {
+        let a = Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 2, y: 2 });
+        let b = Bounds::new(Pt { x: 5, y: -3 }, Pt { x: 6, y: -1 });
+        assert_eq!(a.min_square_distance(&b), 9 + 1);
+        assert_eq!(b.min_square_distance(&a), 10);
+        assert_eq!(a.max_square_distance(&b), 36 + 25);
+        assert_eq!(b.max_square_distance(&a), 61);
+        let c = Bounds::new(Pt { x: 1, y: 1 }, Pt { x: 4, y: 1 });
+        assert_eq!(a.min_square_distance(&c), 0);
+        assert_eq!(a.max_square_distance(&a), 8);
+        let p = Bounds::from_point(Pt { x: 3, y: 4 });
+        assert_eq!(p.min_square_distance(&p), 0);
+        assert_eq!(
+            p.max_square_distance(&Bounds::from_point(Pt { x: 0, y: 0 })),
+            25
+        );
+    }
+
+    #[test]
+    fn test_split_and_subdivide() {
+        let a = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 4., y: 2. });
+        let (lo, hi) = a.split_at(0, 1.);
+        assert_eq!(lo, Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 1., y: 2. }));
+        assert_eq!(hi, Bounds::new(Pt { x: 1., y: 0. }, Pt { x: 4., y: 2. }));
+        let (lo, hi) = a.split_at(1, 5.);
+        assert_eq!((lo, hi.volume()), (a, 0.));
+
+        let quads = a.subdivide();
+        assert_eq!(quads.len(), 4);
+        for (i, q) in quads.iter().enumerate() {
+            assert_eq!(q.volume(), 2.);
+            assert_eq!(q.corner(i), a.corner(i));
+            assert!(q.contains(&a.center()));
+        }
+        assert_eq!(
+            quads[1],
+            Bounds::new(Pt { x: 2., y: 0. }, Pt { x: 4., y: 1. })
+        );
+        assert_eq!(quads.iter().fold(Bounds::empty(), |acc, q| acc.union(q)), a);
+
+        let flat = Bounds::new(crate::Coord([0, 0, 5]), crate::Coord([4, 4, 5]));
+        let octs = flat.subdivide();
+        assert_eq!(octs.len(), 8);
+        assert!(octs.iter().all(|o| o.min.val(2) == 5 && o.max.val(2) == 5));
+        assert_eq!(
+            octs[3],
+            Bounds::new(crate::Coord([2, 2, 5]), crate::Coord([4, 4, 5]))
+        );
+    }
+
+    #[test]
+    fn test_bounds_ops() {
+        let a = Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 4, y: 2 });
+        let b = Bounds::new(Pt { x: 3, y: 1 }, Pt { x: 6, y: 5 });
+        assert_eq!(
+            a.union(&b),
+            Bounds::new(Pt { x: 0, y: 0 }, Pt { x: 6, y: 5 })
+        );
+        assert_eq!(
+            a.intersection(&b),
+            Some(Bounds::new(Pt { x: 3, y: 1 }, Pt { x: 4, y: 2 }))
+        );
+        let far = Bounds::from_point(Pt { x: 9, y: 9 });
+        assert_eq!(a.intersection(&far), None);
+        assert_eq!(
+            a.intersection(&Bounds::from_point(Pt { x: 4, y: 2 })),
+            Some(Bounds::from_point(Pt { x: 4, y: 2 }))
+        );
+
+        assert_eq!(a.extents(), Pt { x: 4, y: 2 });
+        assert_eq!(a.center(), Pt { x: 2, y: 1 });
+        assert_eq!(b.center(), Pt { x: 4, y: 3 });
+        assert_eq!(a.area(), 8);
+        assert_eq!(far.volume(), 0);
+
+        let c = Bounds::new(Pt { x: -1.0, y: 0.5 }, Pt { x: 2.0, y: 1.5 });
+        assert_eq!(c.center(), Pt { x: 0.5, y: 1.0 });
+        assert_eq!(c.volume(), 3.0);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{Coordinate, Coordinate3};
+use num_traits::{Float, Zero};
+
+///pinhole intrinsics - focal lengths and principal point in pixels
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Intrinsics<T> {
+    pub fx: T,
+    pub fy: T,
+    pub cx: T,
+    pub cy: T,
+}
+
+///pinhole camera - camera axes are x right, y down and z forward (view direction)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Camera<C: Coordinate> {
+    pub position: C,
+    ///world to camera rotation, rows are the camera x, y and z axes in world coordinates
+    pub orientation: [C; 3],
+    pub intrinsics: Intrinsics<C::Scalar>,
+}
+
+impl<C> Camera<C>
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    ///new camera from position, world to camera rotation rows and intrinsics
+    pub fn new(position: C, orientation: [C; 3], intrinsics: Intrinsics<C::Scalar>) -> Self {
+        Camera {
+            position,
+            orientation,
+            intrinsics,
+        }
+    }
+
+    ///camera at position looking at target with up roughly upward on screen;
+    ///None if target is at position or the view direction is parallel to up
+    pub fn look_at(
+        position: C,
+        target: &C,
+        up: &C,
+        intrinsics: Intrinsics<C::Scalar>,
+    ) -> Option<Self> {
+        let unit = |v: C| {
+            let len = v.square_length().sqrt();
+            if len > C::Scalar::zero() {
+                Some(v.mult(len.recip()))
+            } else {
+                None
+            }
+        };
+        let forward = unit(target.sub(&position))?;
+        let right = unit(forward.cross(up))?;
+        let down = forward.cross(&right);
+        Some(Camera::new(position, [right, down, forward], intrinsics))
+    }
+
+    ///pt in camera coordinates
+    pub fn to_camera(&self, pt: &C) -> C {
+        let d = pt.sub(&self.position);
+        C::gen(|i| {
+            if i < 3 {
+                self.orientation[i].dot(&d)
+            } else {
+                C::Scalar::zero()
+            }
+        })
+    }
+
+    ///pixel coordinates of pt, None if pt is not in front of the camera
+    pub fn project<D>(&self, pt: &C) -> Option<D>
+    where
+        D: Coordinate<Scalar = C::Scalar>,
+    {
+        let c = self.to_camera(pt);
+        let z = c.val(2);
+        if z.is_nan() || z <= C::Scalar::zero() {
+            return None;
+        }
+        let k = &self.intrinsics;
+        let (u, v) = (k.fx * c.val(0) / z + k.cx, k.fy * c.val(1) / z + k.cy);
+        Some(D::gen(|i| match i {
+            0 => u,
+            1 => v,
+            _ => C::Scalar::zero(),
+        }))
+    }
+
+    ///world point seen at pixel with the given depth along the view direction
+    pub fn unproject<D>(&self, pixel: &D, depth: C::Scalar) -> C
+    where
+        D: Coordinate<Scalar = C::Scalar>,
+    {
+        let k = &self.intrinsics;
+        let x = (pixel.val(0) - k.cx) / k.fx * depth;
+        let y = (pixel.val(1) - k.cy) / k.fy * depth;
+        let [r, d, f] = &self.orientation;
+        self.position
+            .add(&r.mult(x))
+            .add(&d.mult(y))
+            .add(&f.mult(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_camera() {
+        let k = Intrinsics {
+            fx: 100.,
+            fy: 100.,
+            cx: 320.,
+            cy: 240.,
+        };
+        //at the origin looking along +y with z up
+        let cam = Camera::look_at(
+            Coord([0., 0., 0.]),
+            &Coord([0., 10., 0.]),
+            &Coord([0., 0., 1.]),
+            k,
+        )
+        .unwrap();
+        let center: Pt<f64> = cam.project(&Coord([0., 5., 0.])).unwrap();
+        assert_eq!(center, Pt { x: 320., y: 240. });
+        let right_up: Pt<f64> = cam.project(&Coord([1., 2., 1.])).unwrap();
+        assert_eq!(right_up, Pt { x: 370., y: 190. });
+        assert!(cam.project::<Pt<f64>>(&Coord([0., -1., 0.])).is_none());
+
+        let world = Coord([1., 2., 1.]);
+        let back = cam.unproject(&right_up, cam.to_camera(&world).0[2]);
+        assert!(back.square_distance(&world) < 1e-24);
+
+        assert!(Camera::look_at(
+            Coord([0., 0., 0.]),
+            &Coord([0., 0., 5.]),
+            &Coord([0., 0., 1.]),
+            k
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::Coordinate;
+use bs_num::Numeric;
+use std::io::{self, Read, Write};
+
+///fixed size little / big endian encoding of scalars
+pub trait ScalarCodec: Numeric {
+    ///type tag identifying the scalar in binary headers
+    const TAG: u8;
+    ///encoded size in bytes
+    const SIZE: usize;
+
+    ///writes little endian bytes
+    fn write_le<W: Write>(self, w: &mut W) -> io::Result<()>;
+
+    ///reads little endian bytes
+    fn read_le<R: Read>(r: &mut R) -> io::Result<Self>;
+
+    ///writes big endian bytes
+    fn write_be<W: Write>(self, w: &mut W) -> io::Result<()>;
+
+    ///reads big endian bytes
+    fn read_be<R: Read>(r: &mut R) -> io::Result<Self>;
+
+    ///value with its byte order reversed
+    fn swap_bytes(self) -> Self;
+
+    ///canonical representative of equal values (-0.0 as 0.0, a single NaN)
+    fn canonical(self) -> Self {
+        self
+    }
+}
+
+macro_rules! impl_scalar_codec {
+    ($t:ty, $tag:expr, $swap:expr) => {
+        impl_scalar_codec!($t, $tag, $swap, std::convert::identity);
+    };
+    ($t:ty, $tag:expr, $swap:expr, $canon:expr) => {
+        impl ScalarCodec for $t {
+            const TAG: u8 = $tag;
+            const SIZE: usize = std::mem::size_of::<$t>();
+
+            fn write_le<W: Write>(self, w: &mut W) -> io::Result<()> {
+                w.write_all(&self.to_le_bytes())
+            }
+
+            fn read_le<R: Read>(r: &mut R) -> io::Result<Self> {
+                let mut buf = [0u8; std::mem::size_of::<$t>()];
+                r.read_exact(&mut buf)?;
+                Ok(<$t>::from_le_bytes(buf))
+            }
+
+            fn write_be<W: Write>(self, w: &mut W) -> io::Result<()> {
+                w.write_all(&self.to_be_bytes())
+            }
+
+            fn read_be<R: Read>(r: &mut R) -> io::Result<Self> {
+                let mut buf = [0u8; std::mem::size_of::<$t>()];
+                r.read_exact(&mut buf)?;
+                Ok(<$t>::from_be_bytes(buf))
+            }
+
+            fn swap_bytes(self) -> Self {
+                $swap(self)
+            }
+
+            fn canonical(self) -> Self {
+                $canon(self)
+            }
+        }
+    };
+}
+
+impl_scalar_codec!(i8, 1, i8::swap_bytes);
+impl_scalar_codec!(i16, 2, i16::swap_bytes);
+impl_scalar_codec!(i32, 3, i32::swap_bytes);
+impl_scalar_codec!(i64, 4, i64::swap_bytes);
+impl_scalar_codec!(
+    f32,
+    5,
+    |v: f32| f32::from_bits(v.to_bits().swap_bytes()),
+    |v: f32| if v.is_nan() { f32::NAN } else { v + 0. }
+);
+impl_scalar_codec!(
+    f64,
+    6,
+    |v: f64| f64::from_bits(v.to_bits().swap_bytes()),
+    |v: f64| if v.is_nan() { f64::NAN } else { v + 0. }
+);
+
+///writes coordinate components as little endian scalars
+pub fn write_coord_le<C, W>(w: &mut W, coord: &C) -> io::Result<()>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    W: Write,
+{
+    for i in 0..C::DIM {
+        coord.val(i).write_le(w)?;
+    }
+    Ok(())
+}
+
+///reads coordinate components as little endian scalars
+pub fn read_coord_le<C, R>(r: &mut R) -> io::Result<C>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    R: Read,
+{
+    let mut coord = C::new_origin();
+    for i in 0..C::DIM {
+        *coord.val_mut(i) = C::Scalar::read_le(r)?;
+    }
+    Ok(coord)
+}
+
+///writes coordinate components as big endian scalars
+pub fn write_coord_be<C, W>(w: &mut W, coord: &C) -> io::Result<()>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    W: Write,
+{
+    for i in 0..C::DIM {
+        coord.val(i).write_be(w)?;
+    }
+    Ok(())
+}
+
+///reads coordinate components as big endian scalars
+pub fn read_coord_be<C, R>(r: &mut R) -> io::Result<C>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    R: Read,
+{
+    let mut coord = C::new_origin();
+    for i in 0..C::DIM {
+        *coord.val_mut(i) = C::Scalar::read_be(r)?;
+    }
+    Ok(coord)
+}
+
+///reverses the byte order of each scalar_size wide scalar of a raw buffer in place,
+///a trailing partial scalar is left untouched
+pub fn swap_bytes_in_place(buf: &mut [u8], scalar_size: usize) {
+    if scalar_size < 2 {
+        return;
+    }
+    for chunk in buf.chunks_exact_mut(scalar_size) {
+        chunk.reverse();
+    }
+}
+
+///reverses the byte order of decoded scalars in place
+pub fn swap_scalars<T: ScalarCodec>(vals: &mut [T]) {
+    for v in vals {
+        *v = v.swap_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_coord_le() {
+        let mut buf = Vec::new();
+        write_coord_le(&mut buf, &Pt { x: 1i16, y: -2 }).unwrap();
+        assert_eq!(buf, vec![1, 0, 0xfe, 0xff]);
+        let pt: Pt<i16> = read_coord_le(&mut &buf[..]).unwrap();
+        assert_eq!(pt, Pt { x: 1, y: -2 });
+        assert!(read_coord_le::<Pt<i16>, _>(&mut &buf[..3]).is_err());
+    }
+
+    #[test]
+    fn test_coord_be() {
+        let mut buf = Vec::new();
+        write_coord_be(&mut buf, &Pt { x: 1i16, y: -2 }).unwrap();
+        assert_eq!(buf, vec![0, 1, 0xff, 0xfe]);
+        let pt: Pt<i16> = read_coord_be(&mut &buf[..]).unwrap();
+        assert_eq!(pt, Pt { x: 1, y: -2 });
+
+        let mut buf = Vec::new();
+        write_coord_be(
+            &mut buf,
+            &Pt {
+                x: 1.5f64,
+                y: -0.25,
+            },
+        )
+        .unwrap();
+        swap_bytes_in_place(&mut buf, f64::SIZE);
+        let pt: Pt<f64> = read_coord_le(&mut &buf[..]).unwrap();
+        assert_eq!(pt, Pt { x: 1.5, y: -0.25 });
+
+        let mut odd = [1, 2, 3, 4, 5];
+        swap_bytes_in_place(&mut odd, 2);
+        assert_eq!(odd, [2, 1, 4, 3, 5]);
+
+        let mut vals = [0x0102i16, -2];
+        swap_scalars(&mut vals);
+        assert_eq!(vals, [0x0201, -257]);
+        let mut fs = [3.5f32];
+        swap_scalars(&mut fs);
+        swap_scalars(&mut fs);
+        assert_eq!(fs, [3.5]);
+    }
+}
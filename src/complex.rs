@@ -0,0 +1,125 @@
+use bs_num::{Numeric, Zero};
+use num_complex::Complex;
+use std::fmt::Debug;
+
+///coordinate with complex valued components - e.g. phasor grids;
+///complex numbers are unordered so this mirrors `Coordinate` without bounds & comparisons
+pub trait ComplexCoordinate: Copy + Clone + PartialEq + Debug {
+    ///real numeric type of each component part
+    type Real: Numeric;
+
+    ///dimension of coordinate
+    const DIM: usize;
+
+    /// creates coordinate with values from each dimension
+    /// val_fn(i) -> returns coordinate value in ith dimension
+    fn gen(val_fn: impl Fn(usize) -> Complex<Self::Real>) -> Self;
+
+    ///value in ith dim
+    fn val(&self, i: usize) -> Complex<Self::Real>;
+
+    ///mutable value in ith dim
+    fn val_mut(&mut self, i: usize) -> &mut Complex<Self::Real>;
+
+    ///new from origin (0+0i, 0+0i)
+    fn new_origin() -> Self {
+        Self::gen(|_| Complex::new(Zero::zero(), Zero::zero()))
+    }
+
+    ///performs component-wise operation
+    fn component_wise(
+        &self,
+        other: &Self,
+        func: impl Fn(Complex<Self::Real>, Complex<Self::Real>) -> Complex<Self::Real>,
+    ) -> Self {
+        Self::gen(|i| func(self.val(i), other.val(i)))
+    }
+
+    /// addition
+    fn add(&self, other: &Self) -> Self {
+        self.component_wise(other, |l, r| l + r)
+    }
+
+    ///subtraction
+    fn sub(&self, other: &Self) -> Self {
+        self.component_wise(other, |l, r| l - r)
+    }
+
+    ///multiplication by a complex scalar
+    fn mult(&self, k: Complex<Self::Real>) -> Self {
+        self.map(|v| k * v)
+    }
+
+    ///map given functor
+    fn map(&self, transform: impl Fn(Complex<Self::Real>) -> Complex<Self::Real>) -> Self {
+        Self::gen(|i| transform(self.val(i)))
+    }
+
+    ///sum of norms (|re|^2 + |im|^2) of all components
+    fn square_length(&self) -> Self::Real {
+        let mut total: Self::Real = Zero::zero();
+        for i in 0..Self::DIM {
+            total = total + self.val(i).norm_sqr();
+        }
+        total
+    }
+
+    ///square length between self & other
+    fn square_distance(&self, other: &Self) -> Self::Real {
+        self.sub(other).square_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    struct Phasor {
+        u: Complex<f64>,
+        v: Complex<f64>,
+    }
+
+    impl ComplexCoordinate for Phasor {
+        type Real = f64;
+        const DIM: usize = 2;
+
+        fn gen(val_fn: impl Fn(usize) -> Complex<f64>) -> Self {
+            Phasor {
+                u: val_fn(0),
+                v: val_fn(1),
+            }
+        }
+
+        fn val(&self, i: usize) -> Complex<f64> {
+            match i {
+                0 => self.u,
+                1 => self.v,
+                _ => unreachable!(),
+            }
+        }
+
+        fn val_mut(&mut self, i: usize) -> &mut Complex<f64> {
+            match i {
+                0 => &mut self.u,
+                1 => &mut self.v,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_coordinate() {
+        let mut a = Phasor::new_origin();
+        *a.val_mut(0) = Complex::new(1., 2.);
+        *a.val_mut(1) = Complex::new(0., 2.);
+        assert_eq!(a.square_length(), 9.);
+
+        let b = a.mult(Complex::new(0., 1.));
+        assert_eq!(b.val(0), Complex::new(-2., 1.));
+        assert_eq!(b.square_length(), a.square_length());
+        assert_eq!(a.add(&b).sub(&b), a);
+        assert_eq!(a.square_distance(&a), 0.);
+        assert_eq!(a.square_distance(&Phasor::new_origin()), 9.);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::Coordinate;
+use bs_num::Numeric;
+use num_traits::AsPrimitive;
+
+///concrete N-dimensional coordinate backed by an array
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Hash, Debug)]
+pub struct Coord<T, const N: usize>(pub [T; N]);
+
+///integer cell / grid coordinate
+pub type IntCoord<const N: usize> = Coord<i64, N>;
+
+impl<T, const N: usize> Coord<T, N> {
+    ///new coordinate from component values
+    pub const fn new(vals: [T; N]) -> Self {
+        Coord(vals)
+    }
+}
+
+impl<T: Copy, const N: usize> Coord<T, N> {
+    ///coordinates from a flat buffer of interleaved components (x0, y0, x1, y1 ..),
+    ///an incomplete trailing coordinate is ignored
+    pub fn from_interleaved(buf: &[T]) -> Vec<Self> {
+        buf.chunks_exact(N)
+            .map(|c| Coord(std::array::from_fn(|i| c[i])))
+            .collect()
+    }
+
+    ///flat buffer of interleaved components
+    pub fn to_interleaved(coords: &[Self]) -> Vec<T> {
+        coords.iter().flat_map(|c| c.0).collect()
+    }
+
+    ///coordinates from separate per-axis buffers, truncated to the shortest
+    pub fn from_axes(axes: [&[T]; N]) -> Vec<Self> {
+        let n = axes.iter().map(|a| a.len()).min().unwrap_or(0);
+        (0..n)
+            .map(|j| Coord(std::array::from_fn(|i| axes[i][j])))
+            .collect()
+    }
+
+    ///separate per-axis buffers of components
+    pub fn to_axes(coords: &[Self]) -> [Vec<T>; N] {
+        std::array::from_fn(|i| coords.iter().map(|c| c.0[i]).collect())
+    }
+
+    ///f64 coordinate with `as` conversion of each component
+    pub fn to_f64_coord(&self) -> Coord<f64, N>
+    where
+        T: AsPrimitive<f64>,
+    {
+        Coord(self.0.map(|v| v.as_()))
+    }
+
+    ///f32 coordinate with `as` conversion of each component
+    pub fn to_f32_coord(&self) -> Coord<f32, N>
+    where
+        T: AsPrimitive<f32>,
+    {
+        Coord(self.0.map(|v| v.as_()))
+    }
+}
+
+impl<T: Copy> Coord<T, 2> {
+    ///coordinates from separate x and y buffers, truncated to the shorter
+    pub fn from_xy(xs: &[T], ys: &[T]) -> Vec<Self> {
+        Self::from_axes([xs, ys])
+    }
+}
+
+impl<T: Copy> Coord<T, 3> {
+    ///coordinates from separate x, y and z buffers, truncated to the shortest
+    pub fn from_xyz(xs: &[T], ys: &[T], zs: &[T]) -> Vec<Self> {
+        Self::from_axes([xs, ys, zs])
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Coord<T, N> {
+    fn from(vals: [T; N]) -> Self {
+        Coord(vals)
+    }
+}
+
+impl<T: Numeric, const N: usize> Coordinate for Coord<T, N> {
+    type Scalar = T;
+    const DIM: usize = N;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        Coord(std::array::from_fn(val_fn))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        self.0[i]
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        &mut self.0[i]
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T, const N: usize> approx::AbsDiffEq for Coord<T, N>
+where
+    T: approx::AbsDiffEq,
+    T::Epsilon: Copy,
+{
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        (0..N).all(|i| self.0[i].abs_diff_eq(&other.0[i], epsilon))
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<T, const N: usize> approx::RelativeEq for Coord<T, N>
+where
+    T: approx::RelativeEq,
+    T::Epsilon: Copy,
+{
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        (0..N).all(|i| self.0[i].relative_eq(&other.0[i], epsilon, max_relative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord() {
+        let mut a = Coord::new([1., 2., 3.]);
+        *a.val_mut(2) = 4.;
+        assert_eq!(a.square_length(), 21.);
+        assert_eq!(Coord::<f64, 3>::DIM, 3);
+        let b: IntCoord<2> = [3, -4].into();
+        assert_eq!(b.add(&Coord([1, 1])), Coord([4, -3]));
+        assert_eq!(b.square_length(), 25);
+    }
+
+    #[test]
+    fn test_cast() {
+        let cell: IntCoord<2> = Coord([3, -4]);
+        assert_eq!(cell.to_f64_coord(), Coord([3., -4.]));
+        assert_eq!(cell.to_f32_coord(), Coord([3f32, -4.]));
+        assert_eq!(Coord([1.5f64, 2.]).to_f32_coord(), Coord([1.5f32, 2.]));
+
+        let wide: Coord<i64, 3> = Coord([1i32, 2, 3]).cast();
+        assert_eq!(wide, Coord([1, 2, 3]));
+        let f: Coord<f64, 2> = Coord([1i32, -2]).cast();
+        assert_eq!(f, Coord([1., -2.]));
+
+        assert_eq!(cell.try_cast::<Coord<i8, 2>>(), Some(Coord([3i8, -4])));
+        assert_eq!(Coord([300i64, 0]).try_cast::<Coord<i8, 2>>(), None);
+        assert_eq!(
+            Coord([2.7, -1.2]).try_cast::<Coord<i32, 2>>(),
+            Some(Coord([2, -1]))
+        );
+        assert_eq!(Coord([f64::NAN, 0.]).try_cast::<Coord<i32, 2>>(), None);
+        assert_eq!(Coord([1., 2., 3.]).try_cast::<Coord<f32, 2>>(), None);
+        assert_eq!(Coord([1., 2.]).try_cast::<Coord<f32, 3>>(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cast_dimension_mismatch() {
+        let _: Coord<f64, 3> = Coord([1f32, 2.]).cast();
+    }
+
+    #[cfg(feature = "approx")]
+    #[test]
+    fn test_approx() {
+        let (a, b) = (Coord([0.1 + 0.2, 1.]), Coord([0.3, 1.]));
+        approx::assert_abs_diff_eq!(a, b);
+        approx::assert_relative_eq!(a, b);
+        approx::assert_abs_diff_ne!(a, Coord([0.3, 1.1]));
+        assert!(approx::relative_ne!(a, Coord([0.3001, 1.])));
+    }
+
+    #[test]
+    fn test_interleaved() {
+        let buf = [1, 2, 3, 4, 5, 6, 7];
+        let pts = Coord::<i32, 2>::from_interleaved(&buf);
+        assert_eq!(pts, vec![Coord([1, 2]), Coord([3, 4]), Coord([5, 6])]);
+        assert_eq!(Coord::to_interleaved(&pts), buf[..6].to_vec());
+        let pts3 = Coord::<i32, 3>::from_interleaved(&buf);
+        assert_eq!(pts3, vec![Coord([1, 2, 3]), Coord([4, 5, 6])]);
+
+        let [xs, ys] = Coord::to_axes(&pts);
+        assert_eq!((xs.clone(), ys.clone()), (vec![1, 3, 5], vec![2, 4, 6]));
+        assert_eq!(Coord::from_xy(&xs, &ys), pts);
+        assert_eq!(Coord::from_xy(&xs, &ys[..1]), vec![Coord([1, 2])]);
+        assert_eq!(
+            Coord::from_xyz(&xs, &ys, &xs),
+            Coord::<i32, 3>::from_interleaved(&[1, 2, 1, 3, 4, 3, 5, 6, 5])
+        );
+    }
+}
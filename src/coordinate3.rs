@@ -0,0 +1,101 @@
+use crate::{Coord, Coordinate};
+use bs_num::Numeric;
+use num_traits::{Float, FloatConst, One, Zero};
+
+///3D extension of `Coordinate` - components 0, 1, 2 are x, y, z (z up)
+pub trait Coordinate3: Coordinate {
+    ///cross product self x other
+    fn cross(&self, other: &Self) -> Self {
+        let (a, b) = (self, other);
+        Self::gen(|i| match i {
+            0 => a.val(1) * b.val(2) - a.val(2) * b.val(1),
+            1 => a.val(2) * b.val(0) - a.val(0) * b.val(2),
+            2 => a.val(0) * b.val(1) - a.val(1) * b.val(0),
+            _ => Self::Scalar::zero(),
+        })
+    }
+
+    ///orthographic view rotated by yaw about z then tilted down by pitch -
+    ///screen x right, screen y up; the depth is dropped
+    fn project_axonometric<D>(&self, yaw: Self::Scalar, pitch: Self::Scalar) -> D
+    where
+        Self::Scalar: Float,
+        D: Coordinate<Scalar = Self::Scalar>,
+    {
+        let (x, y, z) = (self.val(0), self.val(1), self.val(2));
+        let (sa, ca) = yaw.sin_cos();
+        let (sb, cb) = pitch.sin_cos();
+        let u = x * ca - y * sa;
+        let v = (x * sa + y * ca) * sb + z * cb;
+        D::gen(|i| match i {
+            0 => u,
+            1 => v,
+            _ => Self::Scalar::zero(),
+        })
+    }
+
+    ///isometric view - all three axes equally foreshortened
+    fn project_isometric<D>(&self) -> D
+    where
+        Self::Scalar: Float + FloatConst,
+        D: Coordinate<Scalar = Self::Scalar>,
+    {
+        let third = (Self::Scalar::one() + Self::Scalar::one() + Self::Scalar::one()).recip();
+        self.project_axonometric(Self::Scalar::FRAC_PI_4(), third.sqrt().asin())
+    }
+
+    ///dimetric view looking down by pitch along the x = y diagonal -
+    ///x and y equally foreshortened
+    fn project_dimetric<D>(&self, pitch: Self::Scalar) -> D
+    where
+        Self::Scalar: Float + FloatConst,
+        D: Coordinate<Scalar = Self::Scalar>,
+    {
+        self.project_axonometric(Self::Scalar::FRAC_PI_4(), pitch)
+    }
+}
+
+impl<T: Numeric> Coordinate3 for Coord<T, 3> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_cross() {
+        let (x, y) = (Coord([1, 0, 0]), Coord([0, 1, 0]));
+        assert_eq!(x.cross(&y), Coord([0, 0, 1]));
+        assert_eq!(y.cross(&x), Coord([0, 0, -1]));
+        assert_eq!(
+            Coord([2, 3, 4]).cross(&Coord([5, 6, 7])),
+            Coord([-3, 6, -3])
+        );
+    }
+
+    #[test]
+    fn test_axonometric() {
+        let axes = [
+            Coord([1., 0., 0.]),
+            Coord([0., 1., 0.]),
+            Coord([0., 0., 1.]),
+        ];
+        let iso: Vec<Pt<f64>> = axes.iter().map(|a| a.project_isometric()).collect();
+        for p in &iso {
+            assert!((p.square_length() - 2. / 3.).abs() < 1e-12);
+        }
+        assert!(iso[0].x > 0. && iso[1].x < 0. && iso[2].x.abs() < 1e-12);
+        assert!(iso[2].y > 0.);
+        let origin: Pt<f64> = Coord([0., 0., 0.]).project_isometric();
+        assert_eq!(origin, Pt { x: 0., y: 0. });
+
+        let di: Vec<Pt<f64>> = axes.iter().map(|a| a.project_dimetric(0.3)).collect();
+        assert!((di[0].square_length() - di[1].square_length()).abs() < 1e-12);
+        assert!((di[0].square_length() - di[2].square_length()).abs() > 1e-3);
+
+        let top: Pt<f64> = Coord([2., 3., 9.]).project_axonometric(0., std::f64::consts::FRAC_PI_2);
+        assert!(top.square_distance(&Pt { x: 2., y: 3. }) < 1e-24);
+        let front: Coord<f64, 3> = Coord([2., 3., 9.]).project_axonometric(0., 0.);
+        assert_eq!(front, Coord([2., 9., 0.]));
+    }
+}
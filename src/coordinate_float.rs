@@ -0,0 +1,254 @@
+use crate::{quantize_with, snap_to_grid_with, Coordinate, RoundingMode};
+use num_traits::{Float, One, Zero};
+
+///euclidean metrics for floating point coordinates; `dot` is on `Coordinate`
+///and the 3D cross product on `Coordinate3`
+pub trait CoordinateFloat: Coordinate {
+    ///euclidean length
+    fn length(&self) -> Self::Scalar
+    where
+        Self::Scalar: Float,
+    {
+        self.square_length().sqrt()
+    }
+
+    ///euclidean distance between self & other
+    fn distance(&self, other: &Self) -> Self::Scalar
+    where
+        Self::Scalar: Float,
+    {
+        self.square_distance(other).sqrt()
+    }
+
+    ///unit vector in the direction of self, none for a zero or non finite length
+    fn normalize(&self) -> Option<Self>
+    where
+        Self::Scalar: Float,
+    {
+        let len = self.length();
+        if len == Zero::zero() || !len.is_finite() {
+            return None;
+        }
+        Some(self.map(|v| v / len))
+    }
+
+    ///2D cross (perp-dot) product on the first two dimensions -
+    ///positive if other is counter-clockwise of self
+    fn cross2d(&self, other: &Self) -> Self::Scalar
+    where
+        Self::Scalar: Float,
+    {
+        self.val(0) * other.val(1) - self.val(1) * other.val(0)
+    }
+
+    ///linear interpolation self + (other - self) * t, extrapolates outside 0 <= t <= 1
+    fn lerp(&self, other: &Self, t: Self::Scalar) -> Self
+    where
+        Self::Scalar: Float,
+    {
+        self.add(&other.sub(self).mult(t))
+    }
+
+    ///linear interpolation with t clamped to 0 <= t <= 1
+    fn lerp_clamped(&self, other: &Self, t: Self::Scalar) -> Self
+    where
+        Self::Scalar: Float,
+    {
+        self.lerp(other, t.max(Zero::zero()).min(One::one()))
+    }
+
+    ///point halfway between self & other
+    fn midpoint(&self, other: &Self) -> Self
+    where
+        Self::Scalar: Float,
+    {
+        self.lerp(other, num_traits::cast(0.5).unwrap())
+    }
+
+    ///point at distance from self towards other, beyond other if the distance is
+    ///longer than the segment and backwards if negative; self if other is self
+    fn interpolate_along(&self, other: &Self, distance: Self::Scalar) -> Self
+    where
+        Self::Scalar: Float,
+    {
+        match other.sub(self).normalize() {
+            Some(dir) => self.add(&dir.mult(distance)),
+            None => *self,
+        }
+    }
+
+    ///checks if every component is within eps of other's
+    fn approx_eq(&self, other: &Self, eps: Self::Scalar) -> bool
+    where
+        Self::Scalar: Float,
+    {
+        self.all_comp(other, |a, b| a == b || (a - b).abs() <= eps)
+    }
+
+    ///checks if every component is within eps of other's or within max_relative
+    ///times the larger magnitude of the two - eps covers values near zero
+    fn relative_eq(&self, other: &Self, eps: Self::Scalar, max_relative: Self::Scalar) -> bool
+    where
+        Self::Scalar: Float,
+    {
+        self.all_comp(other, |a, b| {
+            let diff = (a - b).abs();
+            a == b || diff <= eps || diff <= a.abs().max(b.abs()) * max_relative
+        })
+    }
+
+    ///nearest node of a grid of cell_size through the origin, ties snap up so every
+    ///node owns the half open span [node - cell / 2, node + cell / 2); the node of
+    ///`quantize_with` and `RoundingMode::HalfUp` from the origin
+    fn snap_to_grid(&self, cell_size: Self::Scalar) -> Self
+    where
+        Self::Scalar: Float,
+    {
+        snap_to_grid_with(self, cell_size, RoundingMode::HalfUp)
+    }
+
+    ///index of the grid cell of cell_size holding self, the grid anchored at origin;
+    ///cells are half open [k, k + 1) so a point on a boundary is in the upper cell.
+    ///None for non finite or out of range components
+    fn quantize<D>(&self, origin: &Self, cell_size: Self::Scalar) -> Option<D>
+    where
+        Self::Scalar: Float,
+        D: Coordinate<Scalar = i64>,
+    {
+        quantize_with(self, origin, cell_size, RoundingMode::Floor)
+    }
+
+    ///lower corner of a cell made by `quantize`; add half a cell for its center;
+    ///panics if the cell and Self differ in dimension
+    fn dequantize<D>(cell: &D, origin: &Self, cell_size: Self::Scalar) -> Self
+    where
+        Self::Scalar: Float,
+        D: Coordinate<Scalar = i64>,
+    {
+        assert_eq!(Self::DIM, D::DIM, "dequantize between different dimensions");
+        Self::gen(|i| {
+            let k: Self::Scalar = num_traits::cast(cell.val(i)).unwrap();
+            origin.val(i) + k * cell_size
+        })
+    }
+}
+
+impl<C: Coordinate> CoordinateFloat for C {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Coord, Coordinate3, IntCoord};
+
+    #[test]
+    fn test_grid_snapping() {
+        let p = Pt { x: 1.25, y: -0.74 };
+        assert_eq!(p.snap_to_grid(0.5), Pt { x: 1.5, y: -0.5 });
+        //ties go up on both sides of zero
+        assert_eq!(
+            Pt { x: -0.25, y: 0.25 }.snap_to_grid(0.5),
+            Pt { x: 0., y: 0.5 }
+        );
+        //just below a half, where v + 0.5 rounds up to 1
+        let below = Pt {
+            x: 0.49999999999999994,
+            y: -0.49999999999999994,
+        };
+        assert_eq!(below.snap_to_grid(1.), Pt { x: 0., y: -0. });
+        //agrees with quantizing to the nearest node
+        let zero = Pt { x: 0., y: 0. };
+        for p in [below, Pt { x: -0.25, y: 0.75 }, Pt { x: 2.5, y: -7.5 }] {
+            let node: IntCoord<2> = quantize_with(&p, &zero, 0.5, RoundingMode::HalfUp).unwrap();
+            assert_eq!(p.snap_to_grid(0.5), Pt::dequantize(&node, &zero, 0.5));
+        }
+
+        let origin = Pt { x: 10., y: -10. };
+        let cell: IntCoord<2> = Pt { x: 12.5, y: -10.1 }.quantize(&origin, 1.).unwrap();
+        assert_eq!(cell, Coord([2, -1]));
+        let boundary: IntCoord<2> = Pt { x: 13., y: -10. }.quantize(&origin, 1.).unwrap();
+        assert_eq!(boundary, Coord([3, 0]));
+        assert_eq!(Pt::dequantize(&cell, &origin, 1.), Pt { x: 12., y: -11. });
+        for v in [-3.75, -0.5, 0., 0.1, 7.25] {
+            let p = Pt { x: v, y: -v };
+            let c: IntCoord<2> = p.quantize(&origin, 0.25).unwrap();
+            let lo = Pt::dequantize(&c, &origin, 0.25);
+            assert!(lo.x <= p.x && p.x < lo.x + 0.25 && lo.y <= p.y && p.y < lo.y + 0.25);
+        }
+        assert_eq!(
+            Pt { x: f64::NAN, y: 0. }.quantize::<IntCoord<2>>(&origin, 1.),
+            None
+        );
+        assert_eq!(
+            Pt { x: 1., y: 0. }.quantize::<IntCoord<3>>(&origin, 1.),
+            None
+        );
+        let wide = std::panic::catch_unwind(|| Pt::dequantize(&Coord([1i64, 2, 3]), &origin, 1.));
+        assert!(wide.is_err());
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let a = Pt {
+            x: 0.1 + 0.2,
+            y: 1e6,
+        };
+        let b = Pt {
+            x: 0.3,
+            y: 1e6 + 0.01,
+        };
+        assert!(a != b);
+        assert!(a.approx_eq(&b, 0.02) && !a.approx_eq(&b, 1e-9));
+        assert!(a.relative_eq(&b, 1e-12, 1e-7) && !a.relative_eq(&b, 1e-12, 1e-9));
+        assert!(Pt { x: 1e-20, y: 0. }.relative_eq(&Pt { x: 0., y: 0. }, 1e-12, 1e-9));
+        let inf = Pt {
+            x: f64::INFINITY,
+            y: 1.,
+        };
+        assert!(inf.approx_eq(&inf, 0.) && inf.relative_eq(&inf, 0., 0.));
+        let nan = Pt { x: f64::NAN, y: 1. };
+        assert!(!nan.approx_eq(&nan, 1.) && !nan.relative_eq(&nan, 1., 1.));
+    }
+
+    #[test]
+    fn test_float_metrics() {
+        let a = Pt { x: 3.0, y: 4.0 };
+        assert_eq!(a.length(), 5.0);
+        assert_eq!(a.distance(&Pt { x: 0.0, y: 8.0 }), 5.0);
+        assert_eq!(a.normalize(), Some(Pt { x: 0.6, y: 0.8 }));
+        assert_eq!(Pt { x: 0.0, y: 0.0 }.normalize(), None);
+        assert_eq!(
+            Pt {
+                x: f64::INFINITY,
+                y: 0.0
+            }
+            .normalize(),
+            None
+        );
+
+        let b = Pt { x: -4.0, y: 3.0 };
+        assert_eq!(a.dot(&b), 0.0);
+        assert_eq!(a.cross2d(&b), 25.0);
+        assert_eq!(b.cross2d(&a), -25.0);
+
+        let (x, y) = (Coord([1.0, 0.0, 0.0]), Coord([0.0, 2.0, 0.0]));
+        assert_eq!(x.cross(&y).normalize(), Some(Coord([0.0, 0.0, 1.0])));
+        assert_eq!(Coord([1.0f32, 2.0, 2.0]).length(), 3.0);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let (a, b) = (Pt { x: 1.0, y: 2.0 }, Pt { x: 5.0, y: -2.0 });
+        assert_eq!(a.lerp(&b, 0.25), Pt { x: 2.0, y: 1.0 });
+        assert_eq!(a.lerp(&b, 1.5), Pt { x: 7.0, y: -4.0 });
+        assert_eq!(a.lerp_clamped(&b, 1.5), b);
+        assert_eq!(a.lerp_clamped(&b, -2.0), a);
+        assert_eq!(a.midpoint(&b), Pt { x: 3.0, y: 0.0 });
+
+        let (c, d) = (Coord([0.0, 0.0, 1.0]), Coord([3.0, 4.0, 1.0]));
+        assert_eq!(c.interpolate_along(&d, 2.5), Coord([1.5, 2.0, 1.0]));
+        assert_eq!(c.interpolate_along(&d, 10.0), Coord([6.0, 8.0, 1.0]));
+        assert_eq!(c.interpolate_along(&d, -5.0), Coord([-3.0, -4.0, 1.0]));
+        assert_eq!(c.interpolate_along(&c, 1.0), c);
+    }
+}
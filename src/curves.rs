@@ -0,0 +1,367 @@
+//!space filling curve keys - Morton (Z-order) and Hilbert indices of non negative
+//!integer cells, and of float coordinates quantized over bounds. Keys are u64, so
+//!a curve of order bits per dimension needs DIM * bits <= 64
+use crate::{Bounds, Coordinate};
+use num_traits::{Float, NumCast, ToPrimitive};
+
+///bits per dimension of a Morton key - 32 in 2D, 21 in 3D
+pub fn morton_bits<C: Coordinate>() -> u32 {
+    (64 / C::DIM.max(1)) as u32
+}
+
+fn fits(cell: &[u64], bits: u32) -> bool {
+    bits >= 1 && bits as usize * cell.len() <= 64 && cell.iter().all(|&v| v >> (bits - 1) >> 1 == 0)
+}
+
+fn cell_values<C>(cell: &C) -> Option<Vec<u64>>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    cell.components().map(|v| v.to_u64()).collect()
+}
+
+fn from_values<C>(vals: &[u64]) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: NumCast,
+{
+    let vals = vals
+        .iter()
+        .map(|&v| num_traits::cast(v))
+        .collect::<Option<Vec<C::Scalar>>>()?;
+    Some(C::gen(|i| vals[i]))
+}
+
+///bit b of every value, then bit b - 1 .. from the most significant
+fn interleave(vals: &[u64], bits: u32) -> u64 {
+    let mut key = 0;
+    for b in (0..bits).rev() {
+        for v in vals.iter().rev() {
+            key = (key << 1) | ((v >> b) & 1);
+        }
+    }
+    key
+}
+
+fn deinterleave(key: u64, bits: u32, dim: usize) -> Vec<u64> {
+    let mut vals = vec![0; dim];
+    for b in 0..bits {
+        for (i, v) in vals.iter_mut().enumerate() {
+            *v |= ((key >> (b as usize * dim + i)) & 1) << b;
+        }
+    }
+    vals
+}
+
+fn morton_from_values(vals: &[u64], bits: u32) -> Option<u64> {
+    if !fits(vals, bits) {
+        return None;
+    }
+    Some(interleave(vals, bits))
+}
+
+///Hilbert key of cell values (Skilling's transpose algorithm)
+fn hilbert_from_values(vals: &[u64], bits: u32) -> Option<u64> {
+    if !fits(vals, bits) {
+        return None;
+    }
+    let mut x = vals.to_vec();
+    let n = x.len();
+    //inverse undo of the excess work
+    for b in (1..bits).rev() {
+        let (q, p) = (1u64 << b, (1u64 << b) - 1);
+        for i in 0..n {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+    }
+    //gray encode
+    for i in 1..n {
+        x[i] ^= x[i - 1];
+    }
+    let mut t = 0;
+    for b in (1..bits).rev() {
+        if x[n - 1] & (1 << b) != 0 {
+            t ^= (1 << b) - 1;
+        }
+    }
+    x.iter_mut().for_each(|v| *v ^= t);
+    //the transposed index, dimension 0 holds the most significant bit of each group
+    x.reverse();
+    Some(interleave(&x, bits))
+}
+
+///key bits per dimension, None if the key does not fit dim * bits
+fn split_key(key: u64, bits: u32, dim: usize) -> Option<Vec<u64>> {
+    let used = bits as usize * dim;
+    if bits == 0 || used > 64 || (used < 64 && key >> used != 0) {
+        return None;
+    }
+    Some(deinterleave(key, bits, dim))
+}
+
+fn hilbert_to_values(key: u64, bits: u32, dim: usize) -> Option<Vec<u64>> {
+    let mut x = split_key(key, bits, dim)?;
+    x.reverse();
+    let n = x.len();
+    //gray decode
+    let t = x[n - 1] >> 1;
+    for i in (1..n).rev() {
+        x[i] ^= x[i - 1];
+    }
+    x[0] ^= t;
+    //undo the excess work
+    for b in 1..bits {
+        let (q, p) = (1u64 << b, (1u64 << b) - 1);
+        for i in (0..n).rev() {
+            if x[i] & q != 0 {
+                x[0] ^= p;
+            } else {
+                let t = (x[0] ^ x[i]) & p;
+                x[0] ^= t;
+                x[i] ^= t;
+            }
+        }
+    }
+    Some(x)
+}
+
+///Morton key of a cell with `morton_bits` per dimension, dimension 0 in the lowest
+///bit; None for negative or too large components
+pub fn morton_encode<C>(cell: &C) -> Option<u64>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    morton_from_values(&cell_values(cell)?, morton_bits::<C>())
+}
+
+///cell of a Morton key, None if the key uses bits beyond DIM * `morton_bits` or a
+///component does not fit the scalar
+pub fn morton_decode<C>(key: u64) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: NumCast,
+{
+    from_values(&split_key(key, morton_bits::<C>(), C::DIM)?)
+}
+
+///index of a cell along the Hilbert curve of order bits per dimension - cells next
+///to each other on the curve share a face; None for negative components, ones
+///of order bits or more, or DIM * order over 64
+pub fn hilbert_encode<C>(cell: &C, order: u32) -> Option<u64>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    hilbert_from_values(&cell_values(cell)?, order)
+}
+
+///cell at an index of the Hilbert curve of order bits per dimension, None if the key
+///is out of range or a component does not fit the scalar
+pub fn hilbert_decode<C>(key: u64, order: u32) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: NumCast,
+{
+    from_values(&hilbert_to_values(key, order, C::DIM)?)
+}
+
+///cell of pt on a 2^bits per dimension grid over bounds, None outside the bounds
+fn quantize<C>(pt: &C, bounds: &Bounds<C>, bits: u32) -> Option<Vec<u64>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    if bits == 0 || bits > 63 {
+        return None;
+    }
+    let cells = (1u64 << bits) as f64;
+    (0..C::DIM)
+        .map(|i| {
+            let (lo, hi, v) = (bounds.min.val(i), bounds.max.val(i), pt.val(i));
+            if !(lo <= v && v <= hi) {
+                return None;
+            }
+            if hi == lo {
+                return Some(0);
+            }
+            let t = ((v - lo) / (hi - lo)).to_f64()?;
+            Some(((t * cells) as u64).min((1u64 << bits) - 1))
+        })
+        .collect()
+}
+
+///center of a cell of the 2^bits per dimension grid over bounds
+fn cell_center<C>(cell: &[u64], bounds: &Bounds<C>, bits: u32) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let cells: C::Scalar = num_traits::cast(1u64 << bits).unwrap();
+    let half: C::Scalar = num_traits::cast(0.5).unwrap();
+    C::gen(|i| {
+        let (lo, hi) = (bounds.min.val(i), bounds.max.val(i));
+        let k: C::Scalar = num_traits::cast(cell[i]).unwrap();
+        lo + (k + half) / cells * (hi - lo)
+    })
+}
+
+///Morton key of pt quantized to a 2^bits per dimension grid over bounds;
+///None outside the bounds or for DIM * bits over 64
+pub fn morton_encode_float<C>(pt: &C, bounds: &Bounds<C>, bits: u32) -> Option<u64>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    morton_from_values(&quantize(pt, bounds, bits)?, bits)
+}
+
+///center of the grid cell of a Morton key made by `morton_encode_float`,
+///None if the key is out of range
+pub fn morton_decode_float<C>(key: u64, bounds: &Bounds<C>, bits: u32) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let cell = split_key(key, bits, C::DIM)?;
+    Some(cell_center(&cell, bounds, bits))
+}
+
+///Hilbert index of pt quantized to a 2^order per dimension grid over bounds;
+///None outside the bounds or for DIM * order over 64
+pub fn hilbert_encode_float<C>(pt: &C, bounds: &Bounds<C>, order: u32) -> Option<u64>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    hilbert_from_values(&quantize(pt, bounds, order)?, order)
+}
+
+///center of the grid cell of a Hilbert index made by `hilbert_encode_float`,
+///None if the key is out of range
+pub fn hilbert_decode_float<C>(key: u64, bounds: &Bounds<C>, order: u32) -> Option<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let cell = hilbert_to_values(key, order, C::DIM)?;
+    Some(cell_center(&cell, bounds, order))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Coord, IntCoord};
+
+    #[test]
+    fn test_morton() {
+        assert_eq!(morton_bits::<IntCoord<2>>(), 32);
+        assert_eq!(morton_bits::<IntCoord<3>>(), 21);
+        assert_eq!(morton_encode(&Coord([1, 0])), Some(1));
+        assert_eq!(morton_encode(&Coord([0, 1])), Some(2));
+        assert_eq!(morton_encode(&Coord([3, 5])), Some(39));
+        assert_eq!(morton_encode(&Coord([1, 1, 1])), Some(7));
+        assert_eq!(morton_encode(&Coord([2, 0, 0])), Some(8));
+        assert_eq!(morton_encode(&Coord([-1, 0])), None);
+        assert_eq!(morton_encode(&Coord([1i64 << 21, 0, 0])), None);
+        let big = Coord([u32::MAX as i64, 7]);
+        assert_eq!(morton_decode(morton_encode(&big).unwrap()), Some(big));
+        for code in [0, 39, 1 << 62, u64::MAX >> 1] {
+            let cell: IntCoord<3> = morton_decode(code).unwrap();
+            assert_eq!(morton_encode(&cell), Some(code));
+        }
+        //3 x 21 bits leave the top bit unused, 2 x 32 bits use all of it
+        assert_eq!(morton_decode::<IntCoord<3>>(1 << 63), None);
+        assert_eq!(morton_decode::<IntCoord<3>>(u64::MAX), None);
+        for code in [1 << 63, u64::MAX] {
+            let cell: IntCoord<2> = morton_decode(code).unwrap();
+            assert_eq!(morton_encode(&cell), Some(code));
+        }
+        assert_eq!(morton_decode::<Coord<i8, 2>>(1 << 40), None);
+    }
+
+    #[test]
+    fn test_hilbert() {
+        let order1: Vec<_> = (0..4)
+            .map(|k| hilbert_decode::<IntCoord<2>>(k, 1).unwrap())
+            .collect();
+        assert_eq!(
+            order1,
+            vec![Coord([0, 0]), Coord([0, 1]), Coord([1, 1]), Coord([1, 0])]
+        );
+        assert_eq!(hilbert_encode(&Coord([2, 3]), 2), Some(9));
+        assert_eq!(hilbert_encode(&Coord([1, 2, 3]), 2), Some(22));
+        //every step along the curve moves to a face neighbour
+        for (dim, order) in [(2, 4), (3, 3)] {
+            let n = 1u64 << (dim * order);
+            let mut prev: Option<Vec<i64>> = None;
+            for k in 0..n {
+                let cell = if dim == 2 {
+                    hilbert_decode::<IntCoord<2>>(k, order).unwrap().to_vec()
+                } else {
+                    hilbert_decode::<IntCoord<3>>(k, order).unwrap().to_vec()
+                };
+                let key = if dim == 2 {
+                    hilbert_encode(&Coord([cell[0], cell[1]]), order)
+                } else {
+                    hilbert_encode(&Coord([cell[0], cell[1], cell[2]]), order)
+                };
+                assert_eq!(key, Some(k));
+                if let Some(p) = prev {
+                    let step: i64 = p.iter().zip(&cell).map(|(a, b)| (a - b).abs()).sum();
+                    assert_eq!(step, 1);
+                }
+                prev = Some(cell);
+            }
+        }
+        assert_eq!(hilbert_encode(&Coord([4, 0]), 2), None);
+        assert_eq!(hilbert_encode(&Coord([-1, 0]), 2), None);
+        assert_eq!(hilbert_encode(&Coord([0, 0, 0]), 22), None);
+        assert_eq!(hilbert_decode::<IntCoord<2>>(16, 2), None);
+        let max = Coord([u32::MAX as i64, 0]);
+        assert_eq!(
+            hilbert_decode(hilbert_encode(&max, 32).unwrap(), 32),
+            Some(max)
+        );
+    }
+
+    #[test]
+    fn test_float_keys() {
+        let bounds = Bounds::new(Pt { x: -1.0, y: 0.0 }, Pt { x: 3.0, y: 8.0 });
+        let pt = Pt { x: 0.1, y: 5.0 };
+        let key = morton_encode_float(&pt, &bounds, 2).unwrap();
+        //cell (1, 2) of a 4 x 4 grid
+        assert_eq!(key, 0b1001);
+        assert_eq!(Some(key), morton_encode(&Coord([1, 2])));
+        assert_eq!(
+            morton_decode_float(key, &bounds, 2),
+            Some(Pt { x: 0.5, y: 5.0 })
+        );
+        assert_eq!(morton_decode_float(16, &bounds, 2), None);
+        assert_eq!(morton_encode_float(&bounds.max, &bounds, 2), Some(15));
+        assert_eq!(
+            morton_encode_float(&Pt { x: 4.0, y: 0.0 }, &bounds, 2),
+            None
+        );
+        assert_eq!(morton_encode_float(&pt, &bounds, 33), None);
+
+        let key = hilbert_encode_float(&pt, &bounds, 2).unwrap();
+        assert_eq!(Some(key), hilbert_encode(&Coord([1, 2]), 2));
+        assert_eq!(
+            hilbert_decode_float(key, &bounds, 2),
+            Some(Pt { x: 0.5, y: 5.0 })
+        );
+        let near: Vec<_> = (0..16)
+            .map(|k| hilbert_decode_float(k, &bounds, 2).unwrap())
+            .collect();
+        assert!(near.iter().all(|p| bounds.contains(p)));
+    }
+}
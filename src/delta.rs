@@ -0,0 +1,190 @@
+use crate::Coordinate;
+
+///edit operation of a `CoordDelta`
+#[derive(Clone, PartialEq, Debug)]
+pub enum DeltaOp<C> {
+    ///keep the next n old points
+    Keep(usize),
+    ///drop the next n old points
+    Remove(usize),
+    ///insert new points
+    Insert(Vec<C>),
+    ///replace the next old points one for one with moved points
+    Move(Vec<C>),
+}
+
+///compact edit script turning one coordinate sequence into another
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoordDelta<C> {
+    pub ops: Vec<DeltaOp<C>>,
+}
+
+impl<C: Coordinate> CoordDelta<C> {
+    ///checks if the delta keeps everything
+    pub fn is_identity(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, DeltaOp::Keep(_)))
+    }
+
+    ///applies the delta to old, None if it does not fit the length of old
+    pub fn apply(&self, old: &[C]) -> Option<Vec<C>> {
+        let mut out = Vec::with_capacity(old.len());
+        let mut at = 0;
+        for op in &self.ops {
+            match op {
+                DeltaOp::Keep(n) => {
+                    let end = at.checked_add(*n)?;
+                    out.extend_from_slice(old.get(at..end)?);
+                    at = end;
+                }
+                DeltaOp::Remove(n) => {
+                    let end = at.checked_add(*n)?;
+                    old.get(at..end)?;
+                    at = end;
+                }
+                DeltaOp::Insert(pts) => out.extend_from_slice(pts),
+                DeltaOp::Move(pts) => {
+                    let end = at.checked_add(pts.len())?;
+                    old.get(at..end)?;
+                    out.extend_from_slice(pts);
+                    at = end;
+                }
+            }
+        }
+        if at == old.len() {
+            Some(out)
+        } else {
+            None
+        }
+    }
+}
+
+fn push_op<C>(ops: &mut Vec<DeltaOp<C>>, op: DeltaOp<C>) {
+    match (ops.last_mut(), op) {
+        (Some(DeltaOp::Keep(n)), DeltaOp::Keep(k)) => *n += k,
+        (Some(DeltaOp::Remove(n)), DeltaOp::Remove(k)) => *n += k,
+        (Some(DeltaOp::Insert(a)), DeltaOp::Insert(b)) => a.extend(b),
+        (Some(DeltaOp::Move(a)), DeltaOp::Move(b)) => a.extend(b),
+        (_, op) => ops.push(op),
+    }
+}
+
+///edit script from old to new - a longest common subsequence of points equal within
+///tolerance is kept (as the old coordinates), removals followed by insertions pair up
+///as moves; applying it to old gives new up to tolerance. After trimming the common
+///prefix & suffix the LCS table takes O(n·m) time and memory in the lengths n & m of
+///the differing middles - meant for local edits, not unrelated long sequences
+pub fn diff<C: Coordinate>(old: &[C], new: &[C], tolerance: C::Scalar) -> CoordDelta<C> {
+    let tol2 = tolerance * tolerance;
+    let same = |a: &C, b: &C| a.square_distance(b) <= tol2;
+    let prefix = old.iter().zip(new).take_while(|(a, b)| same(a, b)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| same(a, b))
+        .count();
+    let (o, n) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    //lcs[i][j] - lcs length of o[i..] and n[j..]
+    let mut lcs = vec![vec![0usize; n.len() + 1]; o.len() + 1];
+    for i in (0..o.len()).rev() {
+        for j in (0..n.len()).rev() {
+            lcs[i][j] = if same(&o[i], &n[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut ops = Vec::new();
+    push_op(&mut ops, DeltaOp::Keep(prefix));
+    let (mut i, mut j) = (0, 0);
+    let (mut removed, mut inserted) = (0, Vec::new());
+    let flush = |ops: &mut Vec<DeltaOp<C>>, removed: &mut usize, inserted: &mut Vec<C>| {
+        let moved = (*removed).min(inserted.len());
+        if moved > 0 {
+            push_op(ops, DeltaOp::Move(inserted.drain(..moved).collect()));
+        }
+        if *removed > moved {
+            push_op(ops, DeltaOp::Remove(*removed - moved));
+        }
+        if !inserted.is_empty() {
+            push_op(ops, DeltaOp::Insert(std::mem::take(inserted)));
+        }
+        *removed = 0;
+    };
+    while i < o.len() || j < n.len() {
+        if i < o.len() && j < n.len() && same(&o[i], &n[j]) {
+            flush(&mut ops, &mut removed, &mut inserted);
+            push_op(&mut ops, DeltaOp::Keep(1));
+            i += 1;
+            j += 1;
+        } else if j < n.len() && (i == o.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            inserted.push(n[j]);
+            j += 1;
+        } else {
+            removed += 1;
+            i += 1;
+        }
+    }
+    flush(&mut ops, &mut removed, &mut inserted);
+    push_op(&mut ops, DeltaOp::Keep(suffix));
+    ops.retain(|op| *op != DeltaOp::Keep(0));
+    CoordDelta { ops }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_diff_apply() {
+        let old: Vec<_> = (0..8).map(|i| Pt { x: i as f64, y: 0. }).collect();
+        let mut new = old.clone();
+        new[3].y = 2.;
+        new.remove(6);
+        new.insert(1, Pt { x: 0.5, y: 9. });
+        new.push(Pt { x: 8., y: 0. });
+        //within tolerance
+        new[0].x = 1e-9;
+
+        let delta = diff(&old, &new, 1e-6);
+        assert_eq!(
+            delta.ops,
+            vec![
+                DeltaOp::Keep(1),
+                DeltaOp::Insert(vec![Pt { x: 0.5, y: 9. }]),
+                DeltaOp::Keep(2),
+                DeltaOp::Move(vec![Pt { x: 3., y: 2. }]),
+                DeltaOp::Keep(2),
+                DeltaOp::Remove(1),
+                DeltaOp::Keep(1),
+                DeltaOp::Insert(vec![Pt { x: 8., y: 0. }]),
+            ]
+        );
+        let mut expect = new.clone();
+        expect[0] = old[0];
+        assert_eq!(delta.apply(&old), Some(expect));
+        assert_eq!(delta.apply(&old[..7]), None);
+        assert_eq!(delta.apply(&new[..]), None);
+
+        assert!(diff(&old, &old, 0.).is_identity());
+        let delta = diff(&[], &old, 0.);
+        assert_eq!(delta.apply(&[]), Some(old.clone()));
+        assert_eq!(diff(&old, &[], 0.).ops, vec![DeltaOp::Remove(8)]);
+
+        //crafted counts overflowing the position
+        let crafted = CoordDelta {
+            ops: vec![DeltaOp::Keep(1), DeltaOp::Remove(usize::MAX)],
+        };
+        assert_eq!(crafted.apply(&old), None);
+        let crafted = CoordDelta {
+            ops: vec![DeltaOp::Remove(2), DeltaOp::Keep(usize::MAX)],
+        };
+        assert_eq!(crafted.apply(&old), None);
+    }
+}
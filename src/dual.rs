@@ -0,0 +1,218 @@
+use crate::Coordinate;
+use bs_num::{Numeric, Zero};
+use num_traits::{Bounded, Float, Num, One, Signed};
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+///dual number `re + eps·ε` (ε² = 0) - forward mode automatic differentiation scalar;
+///ordering & comparisons are by real part
+#[derive(Copy, Clone, Debug)]
+pub struct Dual<T: Numeric> {
+    ///real part
+    pub re: T,
+    ///derivative (infinitesimal) part
+    pub eps: T,
+}
+
+impl<T: Numeric> Dual<T> {
+    ///new dual from real and derivative parts
+    pub fn new(re: T, eps: T) -> Self {
+        Dual { re, eps }
+    }
+
+    ///constant - derivative part zero
+    pub fn constant(re: T) -> Self {
+        Dual::new(re, Zero::zero())
+    }
+
+    ///independent variable - derivative part one
+    pub fn variable(re: T) -> Self {
+        Dual::new(re, One::one())
+    }
+}
+
+impl<T: Numeric + Float> Dual<T> {
+    ///square root
+    pub fn sqrt(&self) -> Self {
+        let re = self.re.sqrt();
+        Dual::new(re, self.eps / (re + re))
+    }
+}
+
+impl<T: Numeric> PartialEq for Dual<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.re == other.re
+    }
+}
+
+impl<T: Numeric> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.re.partial_cmp(&other.re)
+    }
+}
+
+impl<T: Numeric> Add for Dual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Dual::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+impl<T: Numeric> Sub for Dual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Dual::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+impl<T: Numeric> Mul for Dual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Dual::new(self.re * rhs.re, self.re * rhs.eps + self.eps * rhs.re)
+    }
+}
+
+impl<T: Numeric> Div for Dual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Dual::new(
+            self.re / rhs.re,
+            (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        )
+    }
+}
+
+impl<T: Numeric> Rem for Dual<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        let re = self.re % rhs.re;
+        //a % b = a - b * trunc(a / b)
+        let quot = (self.re - re) / rhs.re;
+        Dual::new(re, self.eps - rhs.eps * quot)
+    }
+}
+
+impl<T: Numeric> Neg for Dual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Dual::new(-self.re, -self.eps)
+    }
+}
+
+impl<T: Numeric> Zero for Dual<T> {
+    fn zero() -> Self {
+        Dual::constant(Zero::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero()
+    }
+}
+
+impl<T: Numeric> One for Dual<T> {
+    fn one() -> Self {
+        Dual::constant(One::one())
+    }
+}
+
+impl<T: Numeric> Num for Dual<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(s, radix).map(Dual::constant)
+    }
+}
+
+impl<T: Numeric> Bounded for Dual<T> {
+    fn min_value() -> Self {
+        Dual::constant(T::min_value())
+    }
+
+    fn max_value() -> Self {
+        Dual::constant(T::max_value())
+    }
+}
+
+impl<T: Numeric> Signed for Dual<T> {
+    fn abs(&self) -> Self {
+        if self.re.is_negative() {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if *self <= *other {
+            Zero::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        Dual::constant(self.re.signum())
+    }
+
+    fn is_positive(&self) -> bool {
+        self.re.is_positive()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.re.is_negative()
+    }
+}
+
+///gradient of f at point - f is evaluated once per dimension over dual valued coordinates
+pub fn gradient<C, D>(point: &C, f: impl Fn(&D) -> Dual<C::Scalar>) -> C
+where
+    C: Coordinate,
+    D: Coordinate<Scalar = Dual<C::Scalar>>,
+{
+    C::gen(|i| {
+        let seed = D::gen(|j| {
+            if i == j {
+                Dual::variable(point.val(j))
+            } else {
+                Dual::constant(point.val(j))
+            }
+        });
+        f(&seed).eps
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_dual() {
+        let x = Dual::variable(3.0);
+        let y = x * x + Dual::constant(2.) * x;
+        assert_eq!((y.re, y.eps), (15., 8.));
+        let y = Dual::constant(1.) / x;
+        assert_eq!(y.eps, -1. / 9.);
+        let y = (x * x).sqrt();
+        assert_eq!((y.re, y.eps), (3., 1.));
+        let y = (-x).abs();
+        assert_eq!((y.re, y.eps), (3., 1.));
+        let y = Dual::new(7.5, 1.) % Dual::new(2., 1.);
+        assert_eq!((y.re, y.eps), (1.5, -2.));
+        assert!(Dual::constant(1.) < x);
+    }
+
+    #[test]
+    fn test_gradient() {
+        let q = Pt { x: Dual::constant(1.), y: Dual::constant(2.) };
+        let grad = gradient(&Pt { x: 4., y: 6. }, |p: &Pt<Dual<f64>>| {
+            p.square_distance(&q)
+        });
+        assert_eq!(grad, Pt { x: 6., y: 8. });
+
+        let grad = gradient(&Pt { x: 3., y: 4. }, |p: &Pt<Dual<f64>>| {
+            p.square_length().sqrt()
+        });
+        assert_eq!(grad, Pt { x: 0.6, y: 0.8 });
+    }
+}
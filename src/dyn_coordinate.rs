@@ -0,0 +1,178 @@
+use crate::Coordinate;
+use bs_num::Numeric;
+use num_traits::{Float, Zero};
+
+///coordinate with a dimension known only at runtime - the counterpart of
+///`Coordinate` for `Vec`s and slices, e.g. embeddings or CSV rows; binary
+///operations return None if the dimensions differ
+pub trait DynCoordinate {
+    type Scalar: Numeric;
+
+    ///number of components
+    fn dim(&self) -> usize;
+
+    ///component at index i
+    fn val(&self, i: usize) -> Self::Scalar;
+
+    ///static coordinate with the same components, None if the dimension is not DIM
+    fn to_coord<C>(&self) -> Option<C>
+    where
+        C: Coordinate<Scalar = Self::Scalar>,
+    {
+        if self.dim() != C::DIM {
+            return None;
+        }
+        Some(C::gen(|i| self.val(i)))
+    }
+
+    ///components of self combined with other's
+    fn component_wise(
+        &self,
+        other: &Self,
+        func: impl Fn(Self::Scalar, Self::Scalar) -> Self::Scalar,
+    ) -> Option<Vec<Self::Scalar>> {
+        if self.dim() != other.dim() {
+            return None;
+        }
+        Some(
+            (0..self.dim())
+                .map(|i| func(self.val(i), other.val(i)))
+                .collect(),
+        )
+    }
+
+    ///checks if all components satisfy a predicate
+    fn all_comp(
+        &self,
+        other: &Self,
+        func: impl Fn(Self::Scalar, Self::Scalar) -> bool,
+    ) -> Option<bool> {
+        if self.dim() != other.dim() {
+            return None;
+        }
+        Some((0..self.dim()).all(|i| func(self.val(i), other.val(i))))
+    }
+
+    ///folds components
+    fn fold<A>(&self, init: A, func: impl Fn(A, Self::Scalar) -> A) -> A {
+        (0..self.dim()).fold(init, |acc, i| func(acc, self.val(i)))
+    }
+
+    ///self + other
+    fn add(&self, other: &Self) -> Option<Vec<Self::Scalar>> {
+        self.component_wise(other, |a, b| a + b)
+    }
+
+    ///self - other
+    fn sub(&self, other: &Self) -> Option<Vec<Self::Scalar>> {
+        self.component_wise(other, |a, b| a - b)
+    }
+
+    ///self scaled by k
+    fn mult(&self, k: Self::Scalar) -> Vec<Self::Scalar> {
+        (0..self.dim()).map(|i| self.val(i) * k).collect()
+    }
+
+    ///dot product
+    fn dot(&self, other: &Self) -> Option<Self::Scalar> {
+        if self.dim() != other.dim() {
+            return None;
+        }
+        Some((0..self.dim()).fold(Zero::zero(), |acc, i| acc + self.val(i) * other.val(i)))
+    }
+
+    ///square length
+    fn square_length(&self) -> Self::Scalar {
+        self.fold(Zero::zero(), |acc, v| acc + v * v)
+    }
+
+    ///square distance between self & other
+    fn square_distance(&self, other: &Self) -> Option<Self::Scalar> {
+        if self.dim() != other.dim() {
+            return None;
+        }
+        Some((0..self.dim()).fold(Zero::zero(), |acc, i| {
+            let d = self.val(i) - other.val(i);
+            acc + d * d
+        }))
+    }
+
+    ///euclidean length
+    fn length(&self) -> Self::Scalar
+    where
+        Self::Scalar: Float,
+    {
+        self.square_length().sqrt()
+    }
+
+    ///euclidean distance between self & other
+    fn distance(&self, other: &Self) -> Option<Self::Scalar>
+    where
+        Self::Scalar: Float,
+    {
+        self.square_distance(other).map(Float::sqrt)
+    }
+}
+
+impl<T: Numeric> DynCoordinate for Vec<T> {
+    type Scalar = T;
+
+    fn dim(&self) -> usize {
+        self.len()
+    }
+
+    fn val(&self, i: usize) -> T {
+        self[i]
+    }
+}
+
+impl<T: Numeric> DynCoordinate for &[T] {
+    type Scalar = T;
+
+    fn dim(&self) -> usize {
+        self.len()
+    }
+
+    fn val(&self, i: usize) -> T {
+        self[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_dyn_coordinate() {
+        let a = vec![1., 2., 2., 0.];
+        let b = vec![0., 0., 0., 1.];
+        assert_eq!(a.dim(), 4);
+        assert_eq!(a.length(), 3.);
+        assert_eq!(a.square_distance(&b), Some(10.));
+        assert_eq!(a.distance(&b), Some(10f64.sqrt()));
+        assert_eq!(a.add(&b), Some(vec![1., 2., 2., 1.]));
+        assert_eq!(a.sub(&b), Some(vec![1., 2., 2., -1.]));
+        assert_eq!(b.mult(2.), vec![0., 0., 0., 2.]);
+        assert_eq!(a.dot(&b), Some(0.));
+        assert_eq!(a.fold(0., |acc, v| acc + v), 5.);
+        assert_eq!(b.all_comp(&a, |x, y| x <= y + 1.), Some(true));
+
+        //rows of different dimensions
+        let short = vec![1., 2.];
+        assert_eq!(a.add(&short), None);
+        assert_eq!(a.dot(&short), None);
+        assert_eq!(a.square_distance(&short), None);
+        assert_eq!(a.distance(&short), None);
+        assert_eq!(a.all_comp(&short, |_, _| true), None);
+
+        //slices of a flat buffer
+        let buf = [3, 4, 0, 0, 1, 1];
+        let rows: Vec<&[i32]> = buf.chunks(3).collect();
+        assert_eq!(rows[0].square_distance(&rows[1]), Some(9 + 9 + 1));
+        assert_eq!(rows[0].to_coord::<Coord<i32, 3>>(), Some(Coord([3, 4, 0])));
+        assert_eq!(rows[0].to_coord::<Pt<i32>>(), None);
+        assert_eq!(vec![3, 4].to_coord(), Some(Pt { x: 3, y: 4 }));
+    }
+}
@@ -0,0 +1,107 @@
+//!geo-types interop - `Coordinate` for `geo_types::Coord` and `geo_types::Point`
+use crate::{Coord, Coordinate};
+use bs_num::Numeric;
+use geo_types::CoordNum;
+
+impl<T: CoordNum + Numeric> Coordinate for geo_types::Coord<T> {
+    type Scalar = T;
+    const DIM: usize = 2;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        geo_types::Coord {
+            x: val_fn(0),
+            y: val_fn(1),
+        }
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        match i {
+            0 => self.x,
+            1 => self.y,
+            _ => unreachable!(),
+        }
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T: CoordNum + Numeric> Coordinate for geo_types::Point<T> {
+    type Scalar = T;
+    const DIM: usize = 2;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        geo_types::Point(geo_types::Coord::gen(val_fn))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        self.0.val(i)
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        self.0.val_mut(i)
+    }
+}
+
+impl<T: CoordNum> From<geo_types::Coord<T>> for Coord<T, 2> {
+    fn from(c: geo_types::Coord<T>) -> Self {
+        Coord([c.x, c.y])
+    }
+}
+
+impl<T: CoordNum> From<Coord<T, 2>> for geo_types::Coord<T> {
+    fn from(c: Coord<T, 2>) -> Self {
+        geo_types::Coord {
+            x: c.0[0],
+            y: c.0[1],
+        }
+    }
+}
+
+impl<T: CoordNum> From<geo_types::Point<T>> for Coord<T, 2> {
+    fn from(p: geo_types::Point<T>) -> Self {
+        p.0.into()
+    }
+}
+
+impl<T: CoordNum> From<Coord<T, 2>> for geo_types::Point<T> {
+    fn from(c: Coord<T, 2>) -> Self {
+        geo_types::Point(c.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::convex_hull;
+    use crate::CoordinateFloat;
+
+    #[test]
+    fn test_geo_types() {
+        let mut a = geo_types::Coord { x: 3.0, y: 4.0 };
+        assert_eq!(a.length(), 5.0);
+        *a.val_mut(1) = 0.0;
+        assert_eq!(a, geo_types::Coord { x: 3.0, y: 0.0 });
+
+        let pts = [
+            geo_types::Point::new(0, 0),
+            geo_types::Point::new(4, 0),
+            geo_types::Point::new(1, 1),
+            geo_types::Point::new(0, 4),
+        ];
+        assert_eq!(convex_hull(&pts), vec![0, 1, 3]);
+        assert_eq!(pts[1].add(&pts[3]), geo_types::Point::new(4, 4));
+
+        let c: Coord<i32, 2> = pts[1].into();
+        assert_eq!(c, Coord([4, 0]));
+        let back: geo_types::Point<i32> = c.into();
+        assert_eq!(back, pts[1]);
+        let g: geo_types::Coord<f64> = Coord([1.5, -2.0]).into();
+        assert_eq!(Coord::from(g), Coord([1.5, -2.0]));
+    }
+}
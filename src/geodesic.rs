@@ -0,0 +1,252 @@
+//!distances and directions on the earth for lon/lat coordinates - component 0 is
+//!longitude and component 1 latitude, both in degrees; distances are in meters
+//!and bearings in degrees clockwise from north. `LonLat` wraps a coordinate so the
+//!euclidean `Coordinate` metrics are not applied to it by mistake
+use crate::geodesy::delta_lon;
+pub use crate::geodesy::{equirectangular_distance, EARTH_RADIUS};
+use crate::Coordinate;
+use num_traits::{Float, FloatConst, One, Zero};
+
+///WGS84 semi-major axis in meters
+pub const WGS84_A: f64 = 6_378_137.0;
+///WGS84 flattening
+pub const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+///geographic coordinate - not a `Coordinate`, use the distances of this module
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LonLat<C>(pub C);
+
+impl<C> LonLat<C>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    ///point at longitude & latitude, other components are zero
+    pub fn new(lon: C::Scalar, lat: C::Scalar) -> Self {
+        LonLat(C::gen(|i| match i {
+            0 => lon,
+            1 => lat,
+            _ => C::Scalar::zero(),
+        }))
+    }
+
+    ///longitude in degrees
+    pub fn lon(&self) -> C::Scalar {
+        self.0.val(0)
+    }
+
+    ///latitude in degrees
+    pub fn lat(&self) -> C::Scalar {
+        self.0.val(1)
+    }
+
+    ///great circle distance on the mean earth sphere
+    pub fn haversine_distance(&self, other: &Self) -> C::Scalar {
+        haversine_distance(&self.0, &other.0)
+    }
+
+    ///distance on the WGS84 ellipsoid, None if the iteration does not converge
+    pub fn vincenty_distance(&self, other: &Self) -> Option<C::Scalar> {
+        vincenty_distance(&self.0, &other.0)
+    }
+
+    ///initial bearing of the great circle towards other
+    pub fn bearing(&self, other: &Self) -> C::Scalar {
+        bearing(&self.0, &other.0)
+    }
+
+    ///point distance along the great circle leaving at bearing
+    pub fn destination_point(&self, bearing: C::Scalar, distance: C::Scalar) -> Self {
+        LonLat(destination_point(&self.0, bearing, distance))
+    }
+}
+
+fn cast<T: Float>(v: f64) -> T {
+    num_traits::cast(v).unwrap()
+}
+
+///great circle distance on the mean earth sphere
+pub fn haversine_distance<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let (lat1, lat2) = (a.val(1).to_radians(), b.val(1).to_radians());
+    let half: C::Scalar = cast(0.5);
+    let s = ((lat2 - lat1) * half).sin();
+    let t = (delta_lon(a, b) * half).sin();
+    let h = (s * s + lat1.cos() * lat2.cos() * t * t).min(C::Scalar::one());
+    let two = C::Scalar::one() + C::Scalar::one();
+    two * cast::<C::Scalar>(EARTH_RADIUS) * h.sqrt().asin()
+}
+
+///initial bearing of the great circle from a to b in [0, 360)
+pub fn bearing<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let (lat1, lat2) = (a.val(1).to_radians(), b.val(1).to_radians());
+    let dlon = delta_lon(a, b);
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    let deg = y.atan2(x).to_degrees();
+    let full: C::Scalar = cast(360.0);
+    if deg < C::Scalar::zero() {
+        deg + full
+    } else {
+        deg
+    }
+}
+
+///point distance meters along the great circle leaving start at bearing, longitude
+///wrapped to [-180, 180); other components are copied from start
+pub fn destination_point<C>(start: &C, bearing: C::Scalar, distance: C::Scalar) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let delta = distance / cast(EARTH_RADIUS);
+    let theta = bearing.to_radians();
+    let (lon1, lat1) = (start.val(0).to_radians(), start.val(1).to_radians());
+    let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * theta.cos()).asin();
+    let lon2 = lon1
+        + (theta.sin() * delta.sin() * lat1.cos()).atan2(delta.cos() - lat1.sin() * lat2.sin());
+    let (pi, tau) = (C::Scalar::PI(), C::Scalar::TAU());
+    let lon2 = lon2 + pi - ((lon2 + pi) / tau).floor() * tau - pi;
+    let mut out = *start;
+    *out.val_mut(0) = lon2.to_degrees();
+    *out.val_mut(1) = lat2.to_degrees();
+    out
+}
+
+///distance on the WGS84 ellipsoid by Vincenty's inverse formula - accurate to
+///well under a millimeter; None when the iteration does not converge, which
+///happens for nearly antipodal points
+pub fn vincenty_distance<C>(a: &C, b: &C) -> Option<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let one = C::Scalar::one();
+    let (two, three, four) = (one + one, cast::<C::Scalar>(3.0), cast::<C::Scalar>(4.0));
+    let f: C::Scalar = cast(WGS84_F);
+    let major: C::Scalar = cast(WGS84_A);
+    let minor = major * (one - f);
+    let reduced = |lat: C::Scalar| ((one - f) * lat.to_radians().tan()).atan();
+    let (u1, u2) = (reduced(a.val(1)), reduced(b.val(1)));
+    let ((sin_u1, cos_u1), (sin_u2, cos_u2)) = (u1.sin_cos(), u2.sin_cos());
+    let l = delta_lon(a, b);
+
+    let mut lambda = l;
+    let mut converged = false;
+    let (mut sin_sigma, mut cos_sigma, mut sigma) = (C::Scalar::zero(), one, C::Scalar::zero());
+    let (mut cos2_alpha, mut cos_2sm) = (one, C::Scalar::zero());
+    for _ in 0..200 {
+        let (sin_l, cos_l) = lambda.sin_cos();
+        sin_sigma = (cos_u2 * sin_l).hypot(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_l);
+        if sin_sigma == C::Scalar::zero() {
+            return Some(C::Scalar::zero());
+        }
+        cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_l;
+        sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_l / sin_sigma;
+        cos2_alpha = one - sin_alpha * sin_alpha;
+        //zero on an equatorial line
+        cos_2sm = if cos2_alpha == C::Scalar::zero() {
+            C::Scalar::zero()
+        } else {
+            cos_sigma - two * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = f / cast(16.0) * cos2_alpha * (four + f * (four - three * cos2_alpha));
+        let prev = lambda;
+        lambda = l
+            + (one - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma * (cos_2sm + c * cos_sigma * (two * cos_2sm * cos_2sm - one)));
+        if (lambda - prev).abs() < cast(1e-12) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return None;
+    }
+    let u_sq = cos2_alpha * (major * major - minor * minor) / (minor * minor);
+    let series = |d: f64, c0: f64, c1: f64, c2: f64, c3: f64| {
+        let (c0, c1, c2, c3) = (cast(c0), cast(c1), cast(c2), cast::<C::Scalar>(c3));
+        u_sq / cast(d) * (c0 + u_sq * (c1 + u_sq * (c2 + c3 * u_sq)))
+    };
+    let big_a = one + series(16384.0, 4096.0, -768.0, 320.0, -175.0);
+    let big_b = series(1024.0, 256.0, -128.0, 74.0, -47.0);
+    let six = three + three;
+    let delta_sigma = big_b
+        * sin_sigma
+        * (cos_2sm
+            + big_b / four
+                * (cos_sigma * (two * cos_2sm * cos_2sm - one)
+                    - big_b / six
+                        * cos_2sm
+                        * (four * sin_sigma * sin_sigma - three)
+                        * (four * cos_2sm * cos_2sm - three)));
+    Some(minor * big_a * (sigma - delta_sigma))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    fn p(x: f64, y: f64) -> Pt<f64> {
+        Pt { x, y }
+    }
+
+    #[test]
+    fn test_haversine_and_bearing() {
+        let degree = EARTH_RADIUS * f64::PI() / 180.;
+        assert!((haversine_distance(&p(0., 0.), &p(1., 0.)) - degree).abs() < 1e-6);
+        assert!((haversine_distance(&p(179.5, 0.), &p(-179.5, 0.)) - degree).abs() < 1e-6);
+        assert!((haversine_distance(&p(0., 0.), &p(180., 0.)) - 180. * degree).abs() < 1e-3);
+        assert_eq!(haversine_distance(&p(3., 4.), &p(3., 4.)), 0.);
+
+        assert!((bearing(&p(0., 0.), &p(1., 0.)) - 90.).abs() < 1e-12);
+        assert!((bearing(&p(0., 0.), &p(-1., 0.)) - 270.).abs() < 1e-12);
+        assert!(bearing(&p(5., 10.), &p(5., 20.)).abs() < 1e-12);
+        //london to paris
+        assert!((bearing(&p(-0.1278, 51.5074), &p(2.3522, 48.8566)) - 148.1156).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_destination_point() {
+        let (a, b) = (p(-73.9, 40.7), p(139.7, 35.7));
+        let d = destination_point(&a, bearing(&a, &b), haversine_distance(&a, &b));
+        assert!((d.x - b.x).abs() < 1e-9 && (d.y - b.y).abs() < 1e-9);
+        //east across the antimeridian
+        let east = destination_point(&p(179.5, 0.), 90., EARTH_RADIUS * f64::PI() / 180.);
+        assert!((east.x + 179.5).abs() < 1e-9 && east.y.abs() < 1e-12);
+
+        let here = LonLat::<Pt<f64>>::new(10., 45.);
+        let there = here.destination_point(30., 5000.);
+        assert!((here.haversine_distance(&there) - 5000.).abs() < 1e-6);
+        assert!((here.bearing(&there) - 30.).abs() < 1e-6);
+        assert_eq!((here.lon(), here.lat()), (10., 45.));
+    }
+
+    #[test]
+    fn test_vincenty_distance() {
+        //Flinders Peak to Buninyong (Vincenty 1975)
+        let (a, b) = (p(144.42486788, -37.95103342), p(143.92649554, -37.65282113));
+        assert!((vincenty_distance(&a, &b).unwrap() - 54_972.270).abs() < 1e-3);
+        let degree = WGS84_A * f64::PI() / 180.;
+        assert!((vincenty_distance(&p(0., 0.), &p(1., 0.)).unwrap() - degree).abs() < 1e-6);
+        assert!(
+            (vincenty_distance(&p(0., 0.), &p(0., 90.)).unwrap() - 10_001_965.729).abs() < 1e-3
+        );
+        assert_eq!(vincenty_distance(&a, &a), Some(0.));
+        assert_eq!(vincenty_distance(&p(0., 0.), &p(180., 0.)), None);
+        let (x, y) = (LonLat(a), LonLat(b));
+        assert!((x.vincenty_distance(&y).unwrap() - x.haversine_distance(&y)).abs() < 200.);
+    }
+}
@@ -0,0 +1,75 @@
+//!distances between geographic coordinates - component 0 is longitude and
+//!component 1 latitude, both in degrees; distances are in meters
+use crate::Coordinate;
+use num_traits::{Float, FloatConst};
+
+///mean earth radius (IUGG) in meters
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+///longitude difference b - a in radians, wrapped to [-pi, pi]
+pub(crate) fn delta_lon<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let (pi, tau) = (C::Scalar::PI(), C::Scalar::TAU());
+    let dlon = (b.val(0) - a.val(0)).to_radians();
+    if dlon > pi {
+        dlon - tau
+    } else if dlon < -pi {
+        dlon + tau
+    } else {
+        dlon
+    }
+}
+
+///fast approximate distance on a sphere - pythagoras on the plate carree projection
+///scaled by the cosine of the mean latitude. Compared to haversine the error is
+///below 0.01% for points within about 100 km of each other up to 80 degrees of
+///latitude, and grows with separation and closer to the poles; use it to filter
+///candidates before an exact check. Crossing the antimeridian is handled
+pub fn equirectangular_distance<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float + FloatConst,
+{
+    let (lat1, lat2) = (a.val(1).to_radians(), b.val(1).to_radians());
+    let half: C::Scalar = num_traits::cast(0.5).unwrap();
+    let x = delta_lon(a, b) * ((lat1 + lat2) * half).cos();
+    let radius: C::Scalar = num_traits::cast(EARTH_RADIUS).unwrap();
+    radius * x.hypot(lat2 - lat1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    fn haversine(a: &Pt<f64>, b: &Pt<f64>) -> f64 {
+        let (p1, p2) = (a.y.to_radians(), b.y.to_radians());
+        let h = ((p2 - p1) / 2.).sin().powi(2)
+            + p1.cos() * p2.cos() * ((b.x - a.x).to_radians() / 2.).sin().powi(2);
+        2. * EARTH_RADIUS * h.sqrt().asin()
+    }
+
+    #[test]
+    fn test_equirectangular_distance() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let degree = EARTH_RADIUS * f64::PI() / 180.;
+        assert!((equirectangular_distance(&p(0., 0.), &p(1., 0.)) - degree).abs() < 1e-6);
+        assert!((equirectangular_distance(&p(5., 10.), &p(5., 11.)) - degree).abs() < 1e-6);
+        for (a, b) in [
+            (p(10., 45.), p(11., 45.5)),
+            (p(-73.9, 40.7), p(-74.5, 40.1)),
+            (p(10., 79.5), p(11., 80.)),
+        ] {
+            let (fast, exact) = (equirectangular_distance(&a, &b), haversine(&a, &b));
+            assert!(((fast - exact) / exact).abs() < 1e-4);
+        }
+        //across the antimeridian
+        let (a, b) = (p(179.9, 10.), p(-179.9, 10.1));
+        let d = equirectangular_distance(&a, &b);
+        assert!(d < 30_000. && ((d - haversine(&a, &b)) / d).abs() < 1e-4);
+        assert_eq!(equirectangular_distance(&a, &a), 0.);
+    }
+}
@@ -0,0 +1,536 @@
+//!integer grid neighbourhoods, distance heuristics and shortest paths
+use crate::{Bounds, Coord, Coordinate, IntCoord};
+use num_traits::{Bounded, One, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+///grid neighbourhood of a cell
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Connectivity {
+    ///cells sharing a face - 4 in 2D, 6 in 3D
+    VonNeumann,
+    ///cells sharing a face, edge or corner - 8 in 2D, 26 in 3D
+    Moore,
+}
+
+///offsets in {-1, 0, 1}^N with between lo and hi non-zero components,
+///axis 0 varying fastest from -1
+const fn offsets<const N: usize, const K: usize>(lo: usize, hi: usize) -> [IntCoord<N>; K] {
+    let mut out = [Coord([0; N]); K];
+    let mut offset = [-1i64; N];
+    let mut k = 0;
+    loop {
+        let mut nonzero = 0;
+        let mut i = 0;
+        while i < N {
+            if offset[i] != 0 {
+                nonzero += 1;
+            }
+            i += 1;
+        }
+        if nonzero >= lo && nonzero <= hi {
+            out[k] = Coord(offset);
+            k += 1;
+        }
+        let mut axis = 0;
+        while axis < N && offset[axis] == 1 {
+            offset[axis] = -1;
+            axis += 1;
+        }
+        if axis == N {
+            break;
+        }
+        offset[axis] += 1;
+    }
+    assert!(k == K);
+    out
+}
+
+///4-connected 2D offsets
+pub const VON_NEUMANN: [IntCoord<2>; 4] = offsets(1, 1);
+
+///8-connected 2D offsets
+pub const MOORE: [IntCoord<2>; 8] = offsets(1, 2);
+
+///6-connected (face) 3D offsets
+pub const VON_NEUMANN_3D: [IntCoord<3>; 6] = offsets(1, 1);
+
+///18-connected (face & edge) 3D offsets
+pub const EDGE_NEIGHBORS_3D: [IntCoord<3>; 18] = offsets(1, 2);
+
+///26-connected (face, edge & corner) 3D offsets
+pub const MOORE_3D: [IntCoord<3>; 26] = offsets(1, 3);
+
+///neighbours of cell in the given connectivity, axis 0 offsets varying fastest from -1;
+///neighbours past the ends of the scalar range are skipped
+pub fn neighbors<C: Coordinate>(cell: &C, connectivity: Connectivity) -> Vec<C> {
+    let one = C::Scalar::one();
+    let (lowest, highest) = (C::Scalar::min_value(), C::Scalar::max_value());
+    let mut out = Vec::new();
+    //odometer over offsets in {-1, 0, 1} per axis
+    let mut offset = vec![-1i8; C::DIM];
+    loop {
+        let nonzero = offset.iter().filter(|&&o| o != 0).count();
+        let keep = match connectivity {
+            Connectivity::VonNeumann => nonzero == 1,
+            Connectivity::Moore => nonzero > 0,
+        };
+        let in_range = (0..C::DIM).all(|i| match offset[i] {
+            -1 => cell.val(i) > lowest,
+            0 => true,
+            _ => cell.val(i) < highest,
+        });
+        if keep && in_range {
+            out.push(C::gen(|i| match offset[i] {
+                -1 => cell.val(i) - one,
+                0 => cell.val(i),
+                _ => cell.val(i) + one,
+            }));
+        }
+        let mut axis = 0;
+        while axis < C::DIM && offset[axis] == 1 {
+            offset[axis] = -1;
+            axis += 1;
+        }
+        if axis == C::DIM {
+            break;
+        }
+        offset[axis] += 1;
+    }
+    out
+}
+
+///4-connected neighbours of a 2D cell
+pub fn neighbors4<C: Coordinate>(cell: &C) -> Vec<C> {
+    neighbors(cell, Connectivity::VonNeumann)
+}
+
+///8-connected neighbours of a 2D cell
+pub fn neighbors8<C: Coordinate>(cell: &C) -> Vec<C> {
+    neighbors(cell, Connectivity::Moore)
+}
+
+///26-connected neighbours of a 3D cell, panics for other dimensions
+pub fn neighbors26<C: Coordinate>(cell: &C) -> Vec<C> {
+    assert_eq!(C::DIM, 3, "neighbors26 of a {}D cell", C::DIM);
+    neighbors(cell, Connectivity::Moore)
+}
+
+///L1 (taxicab) distance - admissible A* heuristic for unit cost von Neumann moves
+pub fn manhattan<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Signed,
+{
+    a.manhattan_distance(b)
+}
+
+///L-infinity (chessboard) distance - admissible A* heuristic for unit cost Moore moves
+pub fn chebyshev<C>(a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Signed,
+{
+    a.chebyshev_distance(b)
+}
+
+struct Open<W> {
+    f: W,
+    g: W,
+    node: usize,
+}
+
+impl<W: PartialOrd> PartialEq for Open<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for Open<W> {}
+
+impl<W: PartialOrd> PartialOrd for Open<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for Open<W> {
+    //min-heap on f, ties to the larger g (deeper node), then to the older node
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .f
+            .partial_cmp(&self.f)
+            .unwrap_or(Ordering::Equal)
+            .then(self.g.partial_cmp(&other.g).unwrap_or(Ordering::Equal))
+            .then(other.node.cmp(&self.node))
+    }
+}
+
+///A* shortest path from start to goal - successors yields (node, step cost >= 0)
+///pairs and heuristic must not overestimate the remaining cost; expanded nodes are
+///reopened when a cheaper path reaches them, which only happens if the heuristic is
+///not consistent. Returns the path (start and goal included) and its cost, None if
+///unreachable
+pub fn astar<C, W, N, I, H>(
+    start: C,
+    goal: C,
+    mut successors: N,
+    heuristic: H,
+) -> Option<(Vec<C>, W)>
+where
+    C: Coordinate + Eq + Hash,
+    W: Copy + PartialOrd + Zero + Add<Output = W>,
+    N: FnMut(&C) -> I,
+    I: IntoIterator<Item = (C, W)>,
+    H: Fn(&C, &C) -> W,
+{
+    //nodes, best known cost and parent per node index
+    let mut nodes: Vec<C> = vec![start];
+    let mut index: HashMap<C, usize> = HashMap::new();
+    index.insert(start, 0);
+    let mut cost: Vec<W> = vec![W::zero()];
+    let mut parent: Vec<Option<usize>> = vec![None];
+    let mut closed: Vec<bool> = vec![false];
+
+    let mut open = BinaryHeap::new();
+    open.push(Open {
+        f: heuristic(&start, &goal),
+        g: W::zero(),
+        node: 0,
+    });
+    while let Some(Open { g, node, .. }) = open.pop() {
+        //stale entry of a node since reached more cheaply
+        if closed[node] || cost[node] < g {
+            continue;
+        }
+        closed[node] = true;
+        if nodes[node] == goal {
+            let mut path = vec![goal];
+            let mut at = node;
+            while let Some(p) = parent[at] {
+                path.push(nodes[p]);
+                at = p;
+            }
+            path.reverse();
+            return Some((path, g));
+        }
+        let here = nodes[node];
+        for (next, step) in successors(&here) {
+            let g_next = g + step;
+            let (n, fresh) = match index.get(&next) {
+                Some(&n) => (n, false),
+                None => {
+                    index.insert(next, nodes.len());
+                    nodes.push(next);
+                    cost.push(g_next);
+                    parent.push(None);
+                    closed.push(false);
+                    (nodes.len() - 1, true)
+                }
+            };
+            if !fresh && cost[n] <= g_next {
+                continue;
+            }
+            closed[n] = false;
+            cost[n] = g_next;
+            parent[n] = Some(node);
+            open.push(Open {
+                f: g_next + heuristic(&next, &goal),
+                g: g_next,
+                node: n,
+            });
+        }
+    }
+    None
+}
+
+///Dijkstra shortest path - A* without a heuristic
+pub fn dijkstra<C, W, N, I>(start: C, goal: C, successors: N) -> Option<(Vec<C>, W)>
+where
+    C: Coordinate + Eq + Hash,
+    W: Copy + PartialOrd + Zero + Add<Output = W>,
+    N: FnMut(&C) -> I,
+    I: IntoIterator<Item = (C, W)>,
+{
+    astar(start, goal, successors, |_, _| W::zero())
+}
+
+///visited cells of a grid traversal
+pub trait Visited<C> {
+    ///marks cell visited, false if it was already visited (or cannot be visited)
+    fn visit(&mut self, cell: &C) -> bool;
+}
+
+impl<C: Coordinate + Eq + Hash> Visited<C> for HashSet<C> {
+    fn visit(&mut self, cell: &C) -> bool {
+        self.insert(*cell)
+    }
+}
+
+///dense visited bitmap over integer bounds - cells outside count as visited
+#[derive(Clone, Debug)]
+pub struct Bitmap<C: Coordinate> {
+    bounds: Bounds<C>,
+    shape: Vec<usize>,
+    bits: Vec<u64>,
+}
+
+impl<C> Bitmap<C>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    ///empty bitmap over the cells of bounds (inclusive), None if bounds is empty or
+    ///its cell count overflows or cannot be allocated
+    pub fn new(bounds: Bounds<C>) -> Option<Self> {
+        let mut shape = Vec::with_capacity(C::DIM);
+        let mut n = 1usize;
+        for i in 0..C::DIM {
+            let len = cell_offset(bounds.min.val(i), bounds.max.val(i))?.checked_add(1)?;
+            n = n.checked_mul(len)?;
+            shape.push(len);
+        }
+        let mut bits = Vec::new();
+        bits.try_reserve_exact(n.div_ceil(64)).ok()?;
+        bits.resize(n.div_ceil(64), 0);
+        Some(Bitmap {
+            bounds,
+            shape,
+            bits,
+        })
+    }
+
+    fn index(&self, cell: &C) -> Option<usize> {
+        if !self.bounds.contains(cell) {
+            return None;
+        }
+        let mut index = 0;
+        for i in (0..C::DIM).rev() {
+            index = index * self.shape[i] + cell_offset(self.bounds.min.val(i), cell.val(i))?;
+        }
+        Some(index)
+    }
+
+    ///checks if cell was visited
+    pub fn contains(&self, cell: &C) -> bool {
+        match self.index(cell) {
+            Some(i) => self.bits[i / 64] & (1 << (i % 64)) != 0,
+            None => false,
+        }
+    }
+}
+
+///v - lo as a cell count, None if negative or out of range; computed in i128 so
+///that narrow scalars do not overflow
+fn cell_offset<T: ToPrimitive>(lo: T, v: T) -> Option<usize> {
+    v.to_i128()?.checked_sub(lo.to_i128()?)?.to_usize()
+}
+
+impl<C> Visited<C> for Bitmap<C>
+where
+    C: Coordinate,
+    C::Scalar: ToPrimitive,
+{
+    fn visit(&mut self, cell: &C) -> bool {
+        match self.index(cell) {
+            Some(i) => {
+                let (word, bit) = (i / 64, 1 << (i % 64));
+                let fresh = self.bits[word] & bit == 0;
+                self.bits[word] |= bit;
+                fresh
+            }
+            None => false,
+        }
+    }
+}
+
+///breadth first region growing (flood fill) from seed through neighbours
+///that satisfy accept - cells in visit order, empty if the seed is rejected
+pub fn region_grow<C, N, I, A>(seed: C, neighbors: N, accept: A) -> Vec<C>
+where
+    C: Coordinate + Eq + Hash,
+    N: FnMut(&C) -> I,
+    I: IntoIterator<Item = C>,
+    A: FnMut(&C) -> bool,
+{
+    region_grow_with(seed, neighbors, accept, &mut HashSet::new())
+}
+
+///region growing with a caller provided visited set, e.g. a `Bitmap` over bounds
+pub fn region_grow_with<C, N, I, A, V>(
+    seed: C,
+    mut neighbors: N,
+    mut accept: A,
+    visited: &mut V,
+) -> Vec<C>
+where
+    C: Coordinate,
+    N: FnMut(&C) -> I,
+    I: IntoIterator<Item = C>,
+    A: FnMut(&C) -> bool,
+    V: Visited<C>,
+{
+    let mut region = Vec::new();
+    if !accept(&seed) || !visited.visit(&seed) {
+        return region;
+    }
+    let mut queue = VecDeque::from(vec![seed]);
+    while let Some(cell) = queue.pop_front() {
+        region.push(cell);
+        for next in neighbors(&cell) {
+            if accept(&next) && visited.visit(&next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coord, IntCoord};
+
+    #[test]
+    fn test_neighbors() {
+        let c = Coord([0i64, 0]);
+        assert_eq!(
+            neighbors4(&c),
+            vec![Coord([0, -1]), Coord([-1, 0]), Coord([1, 0]), Coord([0, 1])]
+        );
+        assert_eq!(neighbors8(&c).len(), 8);
+        let c3: IntCoord<3> = Coord([5, 5, 5]);
+        assert_eq!(neighbors26(&c3).len(), 26);
+        assert_eq!(neighbors(&c3, Connectivity::VonNeumann).len(), 6);
+        assert!(neighbors26(&c3).iter().all(|n| chebyshev(n, &c3) == 1));
+
+        //no wrap around at the ends of the scalar range
+        let corner = Coord([i8::MIN, i8::MIN]);
+        assert_eq!(
+            neighbors4(&corner),
+            vec![Coord([i8::MIN + 1, i8::MIN]), Coord([i8::MIN, i8::MIN + 1])]
+        );
+        assert_eq!(neighbors8(&corner).len(), 3);
+        assert_eq!(neighbors8(&Coord([i8::MAX, 0])).len(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_neighbors26_dim() {
+        neighbors26(&Coord([0i64, 0]));
+    }
+
+    #[test]
+    fn test_offset_tables() {
+        assert_eq!(
+            VON_NEUMANN,
+            [Coord([0, -1]), Coord([-1, 0]), Coord([1, 0]), Coord([0, 1])]
+        );
+        let o2 = Coord([0i64, 0]);
+        let o3 = Coord([0i64, 0, 0]);
+        assert_eq!(MOORE.to_vec(), neighbors8(&o2));
+        assert_eq!(
+            VON_NEUMANN_3D.to_vec(),
+            neighbors(&o3, Connectivity::VonNeumann)
+        );
+        assert_eq!(MOORE_3D.to_vec(), neighbors26(&o3));
+        assert!(EDGE_NEIGHBORS_3D.iter().all(|c| manhattan(c, &o3) <= 2));
+        const FIRST: IntCoord<3> = MOORE_3D[0];
+        assert_eq!(FIRST, Coord([-1, -1, -1]));
+    }
+
+    #[test]
+    fn test_heuristics() {
+        let (a, b) = (Coord([1i64, -2, 3]), Coord([4i64, 2, 3]));
+        assert_eq!(manhattan(&a, &b), 7);
+        assert_eq!(chebyshev(&a, &b), 4);
+    }
+
+    #[test]
+    fn test_astar() {
+        //10 x 10 map with a wall at x = 5 open only at y = 9
+        let blocked = |c: &IntCoord<2>| c.0[0] == 5 && c.0[1] != 9;
+        let inside = |c: &IntCoord<2>| c.0.iter().all(|v| (0..10).contains(v));
+        let succ = |c: &IntCoord<2>| {
+            neighbors4(c)
+                .into_iter()
+                .filter(|n| inside(n) && !blocked(n))
+                .map(|n| (n, 1i64))
+                .collect::<Vec<_>>()
+        };
+        let (start, goal) = (Coord([0, 0]), Coord([9, 0]));
+        let (path, cost) = astar(start, goal, succ, manhattan).unwrap();
+        assert_eq!(cost, 27);
+        assert_eq!(path.len(), 28);
+        assert_eq!((path[0], path[27]), (start, goal));
+        assert!(path.windows(2).all(|w| manhattan(&w[0], &w[1]) == 1));
+        assert!(path.contains(&Coord([5, 9])));
+
+        let (_, dcost) = dijkstra(start, goal, succ).unwrap();
+        assert_eq!(dcost, cost);
+        assert_eq!(astar(start, start, succ, manhattan), Some((vec![start], 0)));
+        assert_eq!(astar(start, Coord([5, 0]), succ, manhattan), None);
+
+        //diagonal moves at cost 1 with the matching heuristic
+        let succ8 = |c: &IntCoord<2>| {
+            neighbors8(c)
+                .into_iter()
+                .filter(|n| inside(n))
+                .map(|n| (n, 1i64))
+                .collect::<Vec<_>>()
+        };
+        let (path, cost) = astar(start, Coord([9, 4]), succ8, chebyshev).unwrap();
+        assert_eq!((path.len(), cost), (10, 9));
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        //a -> b -> c -> d -> goal at cost 1 each, and a -> c directly at cost 3;
+        //the heuristic is admissible but not consistent, so c is first expanded
+        //through the expensive edge and must be reopened
+        let (a, b, c, d, goal) = (
+            Coord([0i64, 0]),
+            Coord([1, 0]),
+            Coord([2, 0]),
+            Coord([3, 0]),
+            Coord([4, 0]),
+        );
+        let succ = |n: &IntCoord<2>| match n.0[0] {
+            0 => vec![(b, 1), (c, 3)],
+            4 => vec![],
+            _ => vec![(Coord([n.0[0] + 1, 0]), 1)],
+        };
+        let h = |n: &IntCoord<2>, _: &IntCoord<2>| if *n == b { 3 } else { 0 };
+        let (path, cost) = astar(a, goal, succ, h).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path, vec![a, b, c, d, goal]);
+    }
+
+    #[test]
+    fn test_region_grow() {
+        //ring of blocked cells around (0, 0) with radius 3
+        let open = |c: &IntCoord<2>| chebyshev(c, &Coord([0, 0])) != 3;
+        let inner = region_grow(Coord([0i64, 0]), neighbors4, open);
+        assert_eq!(inner.len(), 25);
+        assert_eq!(inner[0], Coord([0, 0]));
+        assert!(region_grow(Coord([3i64, 0]), neighbors4, open).is_empty());
+
+        let bounds = Bounds::new(Coord([-10i64, -10]), Coord([10, 10]));
+        let mut bitmap = Bitmap::new(bounds).unwrap();
+        let outer = region_grow_with(Coord([5i64, 5]), neighbors8, open, &mut bitmap);
+        assert_eq!(outer.len(), 21 * 21 - 25 - 24);
+        assert!(bitmap.contains(&Coord([-10, 10])) && !bitmap.contains(&Coord([0, 0])));
+        assert!(!bitmap.contains(&Coord([11, 0])));
+
+        //narrow scalars spanning their whole range
+        let mut bytes = Bitmap::new(Bounds::new(Coord([i8::MIN]), Coord([i8::MAX]))).unwrap();
+        assert!(bytes.visit(&Coord([i8::MAX])) && !bytes.visit(&Coord([i8::MAX])));
+        assert!(!bytes.contains(&Coord([i8::MIN])));
+        //too many cells or empty bounds
+        let wide = Bounds::new(Coord([i64::MIN, i64::MIN]), Coord([i64::MAX, i64::MAX]));
+        assert!(Bitmap::new(wide).is_none());
+        assert!(Bitmap::new(Bounds::<IntCoord<2>>::empty()).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Bounds, Coordinate};
+use num_traits::Zero;
+
+///closed half-space of points p with normal . p <= offset (normal points outward)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HalfSpace<C: Coordinate> {
+    pub normal: C,
+    pub offset: C::Scalar,
+}
+
+impl<C: Coordinate> HalfSpace<C> {
+    ///new half-space from outward normal and offset
+    pub fn new(normal: C, offset: C::Scalar) -> Self {
+        HalfSpace { normal, offset }
+    }
+
+    ///half-space bounded by the plane through pt with outward normal
+    pub fn from_point_normal(pt: &C, normal: C) -> Self {
+        HalfSpace {
+            offset: normal.dot(pt),
+            normal,
+        }
+    }
+
+    ///normal . pt - offset - negative inside, scaled by the normal length
+    pub fn signed_distance(&self, pt: &C) -> C::Scalar {
+        self.normal.dot(pt) - self.offset
+    }
+
+    ///checks if pt is inside or on the boundary
+    pub fn contains(&self, pt: &C) -> bool {
+        self.normal.dot(pt) <= self.offset
+    }
+
+    ///corner of bounds farthest along the normal (max) or against it (!max)
+    fn support(&self, bounds: &Bounds<C>, max: bool) -> C {
+        C::gen(|i| {
+            let up = self.normal.val(i) >= C::Scalar::zero();
+            if up == max {
+                bounds.max.val(i)
+            } else {
+                bounds.min.val(i)
+            }
+        })
+    }
+
+    ///checks if bounds are inside the half-space
+    pub fn contains_bounds(&self, bounds: &Bounds<C>) -> bool {
+        self.contains(&self.support(bounds, true))
+    }
+
+    ///checks if some of bounds is inside the half-space
+    pub fn intersects_bounds(&self, bounds: &Bounds<C>) -> bool {
+        self.contains(&self.support(bounds, false))
+    }
+}
+
+///convex volume as an intersection of half-spaces, e.g. a view frustum
+#[derive(Clone, PartialEq, Debug)]
+pub struct Frustum<C: Coordinate> {
+    pub planes: Vec<HalfSpace<C>>,
+}
+
+impl<C: Coordinate> Frustum<C> {
+    ///new frustum from its bounding half-spaces
+    pub fn new(planes: Vec<HalfSpace<C>>) -> Self {
+        Frustum { planes }
+    }
+
+    ///checks if pt is inside all half-spaces
+    pub fn contains(&self, pt: &C) -> bool {
+        self.planes.iter().all(|h| h.contains(pt))
+    }
+
+    ///checks if bounds are entirely inside
+    pub fn contains_bounds(&self, bounds: &Bounds<C>) -> bool {
+        self.planes.iter().all(|h| h.contains_bounds(bounds))
+    }
+
+    ///conservative overlap test for culling - false only if bounds are entirely
+    ///outside one of the half-spaces
+    pub fn intersects_bounds(&self, bounds: &Bounds<C>) -> bool {
+        self.planes.iter().all(|h| h.intersects_bounds(bounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_half_space() {
+        //x + y <= 2
+        let h = HalfSpace::from_point_normal(&Pt { x: 1., y: 1. }, Pt { x: 1., y: 1. });
+        assert_eq!(h.offset, 2.);
+        assert!(h.contains(&Pt { x: 0., y: 0. }) && h.contains(&Pt { x: 2., y: 0. }));
+        assert!(!h.contains(&Pt { x: 2., y: 1. }));
+        assert_eq!(h.signed_distance(&Pt { x: 3., y: 3. }), 4.);
+
+        let b = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 1.5, y: 1. });
+        assert!(h.intersects_bounds(&b) && !h.contains_bounds(&b));
+        let small = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 1., y: 1. });
+        assert!(h.contains_bounds(&small));
+        let far = Bounds::new(Pt { x: 2., y: 2. }, Pt { x: 3., y: 3. });
+        assert!(!h.intersects_bounds(&far));
+    }
+
+    #[test]
+    fn test_frustum() {
+        //2D view wedge between y = x and y = -x, 1 <= x <= 10
+        let f = Frustum::new(vec![
+            HalfSpace::new(Pt { x: -1, y: 1 }, 0),
+            HalfSpace::new(Pt { x: -1, y: -1 }, 0),
+            HalfSpace::new(Pt { x: -1, y: 0 }, -1),
+            HalfSpace::new(Pt { x: 1, y: 0 }, 10),
+        ]);
+        assert!(f.contains(&Pt { x: 5, y: 4 }) && !f.contains(&Pt { x: 5, y: 6 }));
+        assert!(!f.contains(&Pt { x: 0, y: 0 }));
+        let inside = Bounds::new(Pt { x: 4, y: -1 }, Pt { x: 6, y: 1 });
+        assert!(f.contains_bounds(&inside) && f.intersects_bounds(&inside));
+        let straddle = Bounds::new(Pt { x: 8, y: 5 }, Pt { x: 12, y: 9 });
+        assert!(!f.contains_bounds(&straddle) && f.intersects_bounds(&straddle));
+        let behind = Bounds::new(Pt { x: -5, y: -1 }, Pt { x: 0, y: 1 });
+        assert!(!f.intersects_bounds(&behind));
+    }
+}
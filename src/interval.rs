@@ -0,0 +1,344 @@
+use bs_num::{max, min, Numeric, Zero};
+use num_traits::{Bounded, Num, One, Signed};
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+///outward rounding of interval end points - one representable step
+///towards -inf (down) or +inf (up); exact types round to themselves
+pub trait OutwardRound: Numeric {
+    ///next value towards -inf
+    fn round_down(self) -> Self;
+    ///next value towards +inf
+    fn round_up(self) -> Self;
+
+    ///lower end of an unbounded interval, -inf for floats
+    fn unbounded_lo() -> Self {
+        Self::min_value()
+    }
+
+    ///upper end of an unbounded interval, +inf for floats
+    fn unbounded_hi() -> Self {
+        Self::max_value()
+    }
+}
+
+macro_rules! impl_exact_round {
+    ($($t:ty),*) => {$(
+        impl OutwardRound for $t {
+            fn round_down(self) -> Self {
+                self
+            }
+            fn round_up(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float_round {
+    ($t:ty) => {
+        impl OutwardRound for $t {
+            fn round_down(self) -> Self {
+                -(-self).round_up()
+            }
+            fn round_up(self) -> Self {
+                if self.is_nan() || self == <$t>::INFINITY {
+                    self
+                } else if self == 0.0 {
+                    <$t>::from_bits(1)
+                } else if self > 0.0 {
+                    <$t>::from_bits(self.to_bits() + 1)
+                } else {
+                    <$t>::from_bits(self.to_bits() - 1)
+                }
+            }
+            fn unbounded_lo() -> Self {
+                <$t>::NEG_INFINITY
+            }
+            fn unbounded_hi() -> Self {
+                <$t>::INFINITY
+            }
+        }
+    };
+}
+
+impl_exact_round!(i8, i16, i32, i64, i128, isize);
+impl_float_round!(f32);
+impl_float_round!(f64);
+
+///closed interval [lo, hi] scalar - arithmetic rounds outward so results
+///enclose the exact value; ordered only when intervals are disjoint
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Interval<T: OutwardRound> {
+    lo: T,
+    hi: T,
+}
+
+impl<T: OutwardRound> Interval<T> {
+    ///new interval, end points in any order
+    pub fn new(a: T, b: T) -> Self {
+        Interval {
+            lo: min(a, b),
+            hi: max(a, b),
+        }
+    }
+
+    ///degenerate interval [v, v]
+    pub fn point(v: T) -> Self {
+        Interval { lo: v, hi: v }
+    }
+
+    ///lower end point
+    pub fn lo(&self) -> T {
+        self.lo
+    }
+
+    ///upper end point
+    pub fn hi(&self) -> T {
+        self.hi
+    }
+
+    ///hi - lo
+    pub fn width(&self) -> T {
+        self.hi - self.lo
+    }
+
+    ///checks if v is in [lo, hi]
+    pub fn contains(&self, v: T) -> bool {
+        self.lo <= v && v <= self.hi
+    }
+
+    fn outward(lo: T, hi: T) -> Self {
+        Interval {
+            lo: lo.round_down(),
+            hi: hi.round_up(),
+        }
+    }
+
+    fn hull(vals: [T; 4]) -> Self {
+        let (mut lo, mut hi) = (vals[0], vals[0]);
+        for &v in &vals[1..] {
+            lo = min(lo, v);
+            hi = max(hi, v);
+        }
+        Interval::outward(lo, hi)
+    }
+}
+
+impl<T: OutwardRound> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.hi < other.lo {
+            Some(Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: OutwardRound> Add for Interval<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Interval::outward(self.lo + rhs.lo, self.hi + rhs.hi)
+    }
+}
+
+impl<T: OutwardRound> Sub for Interval<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Interval::outward(self.lo - rhs.hi, self.hi - rhs.lo)
+    }
+}
+
+impl<T: OutwardRound> Mul for Interval<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Interval::hull([
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        ])
+    }
+}
+
+impl<T: OutwardRound> Div for Interval<T> {
+    type Output = Self;
+    ///division by an interval containing zero is unbounded - [-inf, inf] for floats,
+    ///[min_value, max_value] for exact types
+    fn div(self, rhs: Self) -> Self {
+        if rhs.contains(Zero::zero()) {
+            return Interval {
+                lo: T::unbounded_lo(),
+                hi: T::unbounded_hi(),
+            };
+        }
+        Interval::hull([
+            self.lo / rhs.lo,
+            self.lo / rhs.hi,
+            self.hi / rhs.lo,
+            self.hi / rhs.hi,
+        ])
+    }
+}
+
+impl<T: OutwardRound> Rem for Interval<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        if self.lo == self.hi && rhs.lo == rhs.hi && !rhs.lo.is_zero() {
+            return Interval::point(self.lo % rhs.lo);
+        }
+        //|a % b| <= |a|, |a % b| < |b| and a % b has the sign of a
+        let m = max(rhs.lo.abs(), rhs.hi.abs());
+        let lo = if self.lo < Zero::zero() {
+            max(self.lo, -m)
+        } else {
+            Zero::zero()
+        };
+        let hi = if self.hi > Zero::zero() {
+            min(self.hi, m)
+        } else {
+            Zero::zero()
+        };
+        Interval { lo, hi }
+    }
+}
+
+impl<T: OutwardRound> Neg for Interval<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl<T: OutwardRound> Zero for Interval<T> {
+    fn zero() -> Self {
+        Interval::point(Zero::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.lo.is_zero() && self.hi.is_zero()
+    }
+}
+
+impl<T: OutwardRound> One for Interval<T> {
+    fn one() -> Self {
+        Interval::point(One::one())
+    }
+}
+
+impl<T: OutwardRound> Num for Interval<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(s, radix).map(Interval::point)
+    }
+}
+
+impl<T: OutwardRound> Bounded for Interval<T> {
+    fn min_value() -> Self {
+        Interval::point(T::min_value())
+    }
+
+    fn max_value() -> Self {
+        Interval::point(T::max_value())
+    }
+}
+
+impl<T: OutwardRound> Signed for Interval<T> {
+    fn abs(&self) -> Self {
+        if self.lo >= Zero::zero() {
+            *self
+        } else if self.hi <= Zero::zero() {
+            -*self
+        } else {
+            Interval {
+                lo: Zero::zero(),
+                hi: max(-self.lo, self.hi),
+            }
+        }
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        let d = *self - *other;
+        Interval {
+            lo: max(d.lo, Zero::zero()),
+            hi: max(d.hi, Zero::zero()),
+        }
+    }
+
+    fn signum(&self) -> Self {
+        Interval {
+            lo: self.lo.signum(),
+            hi: self.hi.signum(),
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        self.lo.is_positive()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.hi.is_negative()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Bounds, Coordinate};
+
+    #[test]
+    fn test_interval() {
+        let third = Interval::point(1.0) / Interval::point(3.0);
+        assert!(third.lo() < third.hi());
+        assert!(third.contains(1.0 / 3.0));
+
+        let sum = Interval::point(0.1) + Interval::point(0.2);
+        assert!(sum.contains(0.1 + 0.2));
+        assert!(sum.width() > 0.);
+        let unbounded = Interval::point(1.) / Interval::new(-1., 1.);
+        assert_eq!(unbounded, Interval::new(f64::NEG_INFINITY, f64::INFINITY));
+        assert!(unbounded.contains(1e308 / 1e-308));
+
+        let a = Interval::new(3, -2);
+        assert_eq!((a.lo(), a.hi()), (-2, 3));
+        assert_eq!(a * a, Interval::new(-6, 9));
+        assert_eq!(a - a, Interval::new(-5, 5));
+        assert_eq!(a.abs(), Interval::new(0, 3));
+        assert_eq!(a / Interval::point(0), Interval::new(i32::MIN, i32::MAX));
+        assert_eq!(
+            Interval::new(5, 9) % Interval::point(4),
+            Interval::new(0, 4)
+        );
+        assert_eq!(Interval::point(7) % Interval::point(4), Interval::point(3));
+        assert_eq!(a.partial_cmp(&Interval::point(1)), None);
+        assert!(a < Interval::point(4));
+        assert!(!a.is_positive() && !a.is_negative());
+    }
+
+    #[test]
+    fn test_interval_bounds() {
+        let a = Pt {
+            x: Interval::point(0.1),
+            y: Interval::new(-1., 1.),
+        };
+        let b = Pt {
+            x: Interval::point(0.2),
+            y: Interval::point(2.),
+        };
+        let c = a.add(&b).mult(Interval::point(3.));
+        let bounds: Bounds<Pt<f64>> = Bounds::from_interval_coord(&c);
+        assert!(bounds.min.x <= 0.9 && 0.9 <= bounds.max.x);
+        assert!(bounds.min.x < bounds.max.x);
+        assert!(bounds.min.y <= 3. && 9. <= bounds.max.y);
+        assert!(bounds.max.y - bounds.min.y < 6. + 1e-12);
+    }
+}
@@ -0,0 +1,434 @@
+use crate::codec::{read_coord_le, write_coord_le, ScalarCodec};
+use crate::{Bounds, Coordinate};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+///file signature of the binary point format
+pub const MAGIC: [u8; 4] = *b"CRDP";
+///current version of the binary point format
+pub const VERSION: u16 = 1;
+
+const FLAG_BOUNDS: u8 = 1;
+//magic, version, scalar tag, dim, flags
+const COUNT_OFFSET: u64 = 4 + 2 + 1 + 2 + 1;
+//points reserved up front when reading, whatever the header count
+const MAX_RESERVE: u64 = 1 << 16;
+
+///options recorded when writing a point file
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Meta {
+    ///store bounds of all points in the header
+    pub with_bounds: bool,
+}
+
+///header of a point file - readers can filter on bounds without reading points
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Header<C: Coordinate> {
+    pub version: u16,
+    pub count: u64,
+    pub bounds: Option<Bounds<C>>,
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+///writes points as: magic, version(u16), scalar tag(u8), dim(u16), flags(u8),
+///count(u64), [bounds min, max], points - all little endian
+pub fn write_points_to<C, W, I>(w: &mut W, points: I, meta: &Meta) -> io::Result<Header<C>>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    W: Write + Seek,
+    I: IntoIterator<Item = C>,
+{
+    let flags = if meta.with_bounds { FLAG_BOUNDS } else { 0 };
+    w.write_all(&MAGIC)?;
+    w.write_all(&VERSION.to_le_bytes())?;
+    w.write_all(&[C::Scalar::TAG])?;
+    w.write_all(&(C::DIM as u16).to_le_bytes())?;
+    w.write_all(&[flags])?;
+    //count & bounds are patched after streaming points
+    w.write_all(&0u64.to_le_bytes())?;
+    if meta.with_bounds {
+        write_coord_le(w, &C::new_origin())?;
+        write_coord_le(w, &C::new_origin())?;
+    }
+
+    let mut count = 0u64;
+    let mut bounds: Option<Bounds<C>> = None;
+    for pt in points {
+        write_coord_le(w, &pt)?;
+        count += 1;
+        if meta.with_bounds {
+            match bounds.as_mut() {
+                Some(b) => b.expand_to_include(&pt),
+                None => bounds = Some(Bounds::from_point(pt)),
+            }
+        }
+    }
+
+    let end = w.stream_position()?;
+    w.seek(SeekFrom::Start(COUNT_OFFSET))?;
+    w.write_all(&count.to_le_bytes())?;
+    if let Some(b) = bounds {
+        write_coord_le(w, &b.min)?;
+        write_coord_le(w, &b.max)?;
+    }
+    w.seek(SeekFrom::Start(end))?;
+
+    Ok(Header {
+        version: VERSION,
+        count,
+        bounds,
+    })
+}
+
+///reads and validates a point file header
+pub fn read_header_from<C, R>(r: &mut R) -> io::Result<Header<C>>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    R: Read,
+{
+    let mut buf = [0u8; COUNT_OFFSET as usize + 8];
+    r.read_exact(&mut buf)?;
+    if buf[0..4] != MAGIC {
+        return Err(invalid("not a point file"));
+    }
+    let version = u16::from_le_bytes([buf[4], buf[5]]);
+    if version > VERSION {
+        return Err(invalid("unsupported point file version"));
+    }
+    if buf[6] != C::Scalar::TAG {
+        return Err(invalid("scalar type mismatch"));
+    }
+    if u16::from_le_bytes([buf[7], buf[8]]) as usize != C::DIM {
+        return Err(invalid("dimension mismatch"));
+    }
+    let flags = buf[9];
+    let mut count = [0u8; 8];
+    count.copy_from_slice(&buf[10..18]);
+    let count = u64::from_le_bytes(count);
+
+    let mut bounds = None;
+    if flags & FLAG_BOUNDS != 0 {
+        let min = read_coord_le(r)?;
+        let max = read_coord_le(r)?;
+        //empty point sets record no bounds
+        if count > 0 {
+            bounds = Some(Bounds { min, max });
+        }
+    }
+    Ok(Header {
+        version,
+        count,
+        bounds,
+    })
+}
+
+///reads a point file header followed by all points
+pub fn read_points_from<C, R>(r: &mut R) -> io::Result<(Header<C>, Vec<C>)>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    R: Read,
+{
+    let header = read_header_from(r)?;
+    //count comes from the file - reserve a bounded amount and let the vec grow
+    let mut points = Vec::with_capacity(header.count.min(MAX_RESERVE) as usize);
+    for _ in 0..header.count {
+        match read_coord_le(r) {
+            Ok(pt) => points.push(pt),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(invalid("truncated point file"))
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok((header, points))
+}
+
+///writes points to a file, see `write_points_to` for the layout
+pub fn write_points<C, P, I>(path: P, points: I, meta: &Meta) -> io::Result<Header<C>>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    P: AsRef<Path>,
+    I: IntoIterator<Item = C>,
+{
+    let mut w = BufWriter::new(File::create(path)?);
+    let header = write_points_to(&mut w, points, meta)?;
+    w.flush()?;
+    Ok(header)
+}
+
+///reads only the header of a point file
+pub fn read_header<C, P>(path: P) -> io::Result<Header<C>>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    P: AsRef<Path>,
+{
+    read_header_from(&mut BufReader::new(File::open(path)?))
+}
+
+///reads header and points of a point file
+pub fn read_points<C, P>(path: P) -> io::Result<(Header<C>, Vec<C>)>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+    P: AsRef<Path>,
+{
+    read_points_from(&mut BufReader::new(File::open(path)?))
+}
+
+///read-only memory mapped point file with indexed random access
+#[cfg(feature = "mmap")]
+pub struct PointFile<C: Coordinate> {
+    mmap: memmap2::Mmap,
+    header: Header<C>,
+    offset: usize,
+}
+
+#[cfg(feature = "mmap")]
+impl<C> PointFile<C>
+where
+    C: Coordinate,
+    C::Scalar: ScalarCodec,
+{
+    const STRIDE: usize = C::DIM * C::Scalar::SIZE;
+
+    ///maps a point file written by `write_points`;
+    ///the file must not be modified while mapped
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        //safety: mapping is read-only, callers must not truncate the file while mapped
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        let mut r = &mmap[..];
+        let header = read_header_from(&mut r)?;
+        let offset = mmap.len() - r.len();
+        //count comes from the file, so its size must not overflow
+        let size = header
+            .count
+            .checked_mul(Self::STRIDE as u64)
+            .ok_or_else(|| invalid("point count too large"))?;
+        if (r.len() as u64) < size {
+            return Err(invalid("truncated point file"));
+        }
+        Ok(PointFile {
+            mmap,
+            header,
+            offset,
+        })
+    }
+
+    ///file header
+    pub fn header(&self) -> &Header<C> {
+        &self.header
+    }
+
+    ///number of points
+    pub fn len(&self) -> usize {
+        self.header.count as usize
+    }
+
+    ///checks if file has no points
+    pub fn is_empty(&self) -> bool {
+        self.header.count == 0
+    }
+
+    ///ith point
+    pub fn get(&self, i: usize) -> Option<C> {
+        if i >= self.len() {
+            return None;
+        }
+        let start = self.offset + i * Self::STRIDE;
+        read_coord_le(&mut &self.mmap[start..start + Self::STRIDE]).ok()
+    }
+
+    ///all points in file order
+    pub fn iter(&self) -> impl Iterator<Item = C> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+    }
+
+    ///points inside query bounds - skips the scan if header bounds are disjoint
+    pub fn iter_within<'a>(&'a self, query: &'a Bounds<C>) -> impl Iterator<Item = C> + 'a {
+        let n = match self.header.bounds {
+            Some(b) if !b.intersects(query) => 0,
+            _ => self.len(),
+        };
+        (0..n)
+            .filter_map(move |i| self.get(i))
+            .filter(move |pt| query.contains(pt))
+    }
+
+    ///bounds of consecutive chunks of chunk_size points, see `chunk_bounds`
+    pub fn chunk_bounds(&self, chunk_size: usize) -> Vec<Bounds<C>> {
+        let chunk_size = chunk_size.max(1);
+        let mut index: Vec<Bounds<C>> = Vec::with_capacity(self.len().div_ceil(chunk_size));
+        for (i, pt) in self.iter().enumerate() {
+            if i % chunk_size == 0 {
+                index.push(Bounds::from_point(pt));
+            } else if let Some(b) = index.last_mut() {
+                b.expand_to_include(&pt);
+            }
+        }
+        index
+    }
+
+    ///points inside query bounds, scanning only chunks whose bounds in index
+    ///(built with the same chunk_size) intersect the query
+    pub fn iter_within_chunks<'a>(
+        &'a self,
+        index: &'a [Bounds<C>],
+        chunk_size: usize,
+        query: &'a Bounds<C>,
+    ) -> impl Iterator<Item = C> + 'a {
+        let chunk_size = chunk_size.max(1);
+        let len = self.len();
+        index
+            .iter()
+            .enumerate()
+            .filter(move |(_, b)| b.intersects(query))
+            .flat_map(move |(c, _)| (c * chunk_size)..len.min((c + 1) * chunk_size))
+            .filter_map(move |i| self.get(i))
+            .filter(move |pt| query.contains(pt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_points_file() {
+        let pts = vec![
+            Pt { x: 1.5, y: -2. },
+            Pt { x: -3., y: 4. },
+            Pt { x: 0., y: 1. },
+        ];
+        let path = std::env::temp_dir().join(format!("coordinate-io-{}.bin", std::process::id()));
+
+        let header = write_points(&path, pts.iter().copied(), &Meta { with_bounds: true }).unwrap();
+        assert_eq!(header.count, 3);
+        let bounds = Bounds::new(Pt { x: -3., y: -2. }, Pt { x: 1.5, y: 4. });
+        assert_eq!(header.bounds, Some(bounds));
+
+        assert_eq!(read_header::<Pt<f64>, _>(&path).unwrap(), header);
+        let (h, out) = read_points::<Pt<f64>, _>(&path).unwrap();
+        assert_eq!(h, header);
+        assert_eq!(out, pts);
+
+        //wrong scalar type
+        assert!(read_header::<Pt<f32>, _>(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_points_stream() {
+        let mut buf = Cursor::new(Vec::new());
+        let header =
+            write_points_to(&mut buf, vec![Pt { x: 7i32, y: 8 }], &Meta::default()).unwrap();
+        assert_eq!(header.bounds, None);
+        assert_eq!(buf.get_ref().len(), 18 + 8);
+
+        buf.set_position(0);
+        let (h, out) = read_points_from::<Pt<i32>, _>(&mut buf).unwrap();
+        assert_eq!((h.count, h.bounds), (1, None));
+        assert_eq!(out, vec![Pt { x: 7, y: 8 }]);
+
+        let mut empty = Cursor::new(Vec::new());
+        write_points_to(
+            &mut empty,
+            Vec::<Pt<i32>>::new(),
+            &Meta { with_bounds: true },
+        )
+        .unwrap();
+        empty.set_position(0);
+        let h = read_header_from::<Pt<i32>, _>(&mut empty).unwrap();
+        assert_eq!((h.count, h.bounds), (0, None));
+
+        assert!(read_header_from::<Pt<i32>, _>(&mut &b"nope"[..]).is_err());
+        let bad = [0u8; 18];
+        assert_eq!(
+            read_header_from::<Pt<i32>, _>(&mut &bad[..])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_points_stream_corrupt_count() {
+        let mut buf = Cursor::new(Vec::new());
+        write_points_to(&mut buf, vec![Pt { x: 7i32, y: 8 }], &Meta::default()).unwrap();
+        let mut bytes = buf.into_inner();
+        let at = COUNT_OFFSET as usize;
+        bytes[at..at + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        let err = read_points_from::<Pt<i32>, _>(&mut &bytes[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_point_file() {
+        let pts: Vec<_> = (0..100).map(|i| Pt { x: i, y: -i }).collect();
+        let path = std::env::temp_dir().join(format!("coordinate-mmap-{}.bin", std::process::id()));
+        write_points(&path, pts.iter().copied(), &Meta { with_bounds: true }).unwrap();
+
+        let file = PointFile::<Pt<i32>>::open(&path).unwrap();
+        assert_eq!(file.len(), 100);
+        assert!(!file.is_empty());
+        assert_eq!(file.get(42), Some(Pt { x: 42, y: -42 }));
+        assert_eq!(file.get(100), None);
+        assert_eq!(file.iter().collect::<Vec<_>>(), pts);
+
+        let query = Bounds::new(Pt { x: 10, y: -12 }, Pt { x: 20, y: 0 });
+        let found: Vec<_> = file.iter_within(&query).map(|p| p.x).collect();
+        assert_eq!(found, vec![10, 11, 12]);
+        let far = Bounds::new(Pt { x: 500, y: 500 }, Pt { x: 600, y: 600 });
+        assert_eq!(file.iter_within(&far).count(), 0);
+
+        let index = file.chunk_bounds(16);
+        assert_eq!(index, crate::chunk_bounds(&pts, 16));
+        assert_eq!(index.len(), 7);
+        let found: Vec<_> = file
+            .iter_within_chunks(&index, 16, &query)
+            .map(|p| p.x)
+            .collect();
+        assert_eq!(found, vec![10, 11, 12]);
+        let tail = Bounds::new(Pt { x: 98, y: -200 }, Pt { x: 200, y: 0 });
+        assert_eq!(file.iter_within_chunks(&index, 16, &tail).count(), 2);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_point_file_corrupt_count() {
+        let path = std::env::temp_dir().join(format!(
+            "coordinate-mmap-corrupt-{}.bin",
+            std::process::id()
+        ));
+        let mut buf = Cursor::new(Vec::new());
+        write_points_to(&mut buf, vec![Pt { x: 1i32, y: 2 }], &Meta::default()).unwrap();
+        let mut bytes = buf.into_inner();
+        //count * stride wraps to 0 in u64
+        let count = 1u64 << 61;
+        bytes[COUNT_OFFSET as usize..COUNT_OFFSET as usize + 8]
+            .copy_from_slice(&count.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        let err = PointFile::<Pt<i32>>::open(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        //a count past the data is truncated
+        bytes[COUNT_OFFSET as usize..COUNT_OFFSET as usize + 8]
+            .copy_from_slice(&2u64.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(PointFile::<Pt<i32>>::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{douglas_peucker, Bounds, Coordinate};
+use num_traits::Float;
+use std::collections::VecDeque;
+
+///lazy adapters over iterators of coordinates
+pub trait CoordIter<C: Coordinate>: Iterator<Item = C> + Sized {
+    ///translates each coordinate by offset
+    fn translate(self, offset: C) -> Translate<Self, C> {
+        Translate { iter: self, offset }
+    }
+
+    ///scales each coordinate by k
+    fn scale(self, k: C::Scalar) -> Scale<Self, C> {
+        Scale { iter: self, k }
+    }
+
+    ///skips coordinates with NaN or infinite components
+    fn filter_finite(self) -> FilterFinite<Self>
+    where
+        C::Scalar: Float,
+    {
+        FilterFinite { iter: self }
+    }
+
+    ///bounds of all coordinates, None if empty
+    fn bounds(self) -> Option<Bounds<C>> {
+        let mut iter = self;
+        let mut bounds = Bounds::from_point(iter.next()?);
+        for pt in iter {
+            bounds.expand_to_include(&pt);
+        }
+        Some(bounds)
+    }
+
+    ///Douglas-Peucker simplification applied to consecutive chunks of at most
+    ///chunk_size points - memory is bounded by chunk_size, chunk end points are kept
+    fn chunked_simplify(self, chunk_size: usize, tolerance: C::Scalar) -> ChunkedSimplify<Self, C>
+    where
+        C::Scalar: Float,
+    {
+        ChunkedSimplify {
+            iter: self,
+            chunk_size: chunk_size.max(2),
+            tolerance,
+            chunk: Vec::with_capacity(chunk_size),
+            out: VecDeque::new(),
+            carried: false,
+            done: false,
+        }
+    }
+}
+
+impl<C: Coordinate, I: Iterator<Item = C>> CoordIter<C> for I {}
+
+///see `CoordIter::translate`
+pub struct Translate<I, C> {
+    iter: I,
+    offset: C,
+}
+
+impl<C: Coordinate, I: Iterator<Item = C>> Iterator for Translate<I, C> {
+    type Item = C;
+    fn next(&mut self) -> Option<C> {
+        self.iter.next().map(|pt| pt.add(&self.offset))
+    }
+}
+
+///see `CoordIter::scale`
+pub struct Scale<I, C: Coordinate> {
+    iter: I,
+    k: C::Scalar,
+}
+
+impl<C: Coordinate, I: Iterator<Item = C>> Iterator for Scale<I, C> {
+    type Item = C;
+    fn next(&mut self) -> Option<C> {
+        self.iter.next().map(|pt| pt.mult(self.k))
+    }
+}
+
+///see `CoordIter::filter_finite`
+pub struct FilterFinite<I> {
+    iter: I,
+}
+
+impl<C, I> Iterator for FilterFinite<I>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+    I: Iterator<Item = C>,
+{
+    type Item = C;
+    fn next(&mut self) -> Option<C> {
+        self.iter
+            .by_ref()
+            .find(|pt| (0..C::DIM).all(|i| pt.val(i).is_finite()))
+    }
+}
+
+///see `CoordIter::chunked_simplify`
+pub struct ChunkedSimplify<I, C: Coordinate> {
+    iter: I,
+    chunk_size: usize,
+    tolerance: C::Scalar,
+    chunk: Vec<C>,
+    out: VecDeque<C>,
+    carried: bool,
+    done: bool,
+}
+
+impl<C, I> Iterator for ChunkedSimplify<I, C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+    I: Iterator<Item = C>,
+{
+    type Item = C;
+    fn next(&mut self) -> Option<C> {
+        loop {
+            if let Some(pt) = self.out.pop_front() {
+                return Some(pt);
+            }
+            if self.done {
+                return None;
+            }
+            while self.chunk.len() < self.chunk_size {
+                match self.iter.next() {
+                    Some(pt) => self.chunk.push(pt),
+                    None => {
+                        self.done = true;
+                        break;
+                    }
+                }
+            }
+            //carried point of the previous chunk was already emitted
+            let skip = if self.carried { 1 } else { 0 };
+            if self.chunk.len() <= skip {
+                return None;
+            }
+            let kept = douglas_peucker(&self.chunk, self.tolerance);
+            self.out.extend(kept.into_iter().skip(skip));
+
+            let last = self.chunk[self.chunk.len() - 1];
+            self.chunk.clear();
+            self.chunk.push(last);
+            self.carried = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_coord_iter() {
+        let pts = vec![
+            Pt { x: 1., y: 2. },
+            Pt { x: f64::NAN, y: 0. },
+            Pt {
+                x: -3.,
+                y: f64::INFINITY,
+            },
+            Pt { x: 4., y: -1. },
+        ];
+        let out: Vec<_> = pts
+            .clone()
+            .into_iter()
+            .filter_finite()
+            .translate(Pt { x: 1., y: 1. })
+            .scale(2.)
+            .collect();
+        assert_eq!(out, vec![Pt { x: 4., y: 6. }, Pt { x: 10., y: 0. }]);
+
+        let bounds = pts.into_iter().filter_finite().bounds().unwrap();
+        assert_eq!(
+            bounds,
+            Bounds::new(Pt { x: 1., y: -1. }, Pt { x: 4., y: 2. })
+        );
+        assert_eq!(Vec::<Pt<f64>>::new().into_iter().bounds(), None);
+    }
+
+    #[test]
+    fn test_chunked_simplify() {
+        //straight line with a spike at x = 7
+        let pts: Vec<_> = (0..12)
+            .map(|i| Pt {
+                x: i as f64,
+                y: if i == 7 { 5. } else { 0. },
+            })
+            .collect();
+        let out: Vec<_> = pts.clone().into_iter().chunked_simplify(4, 0.1).collect();
+        let xs: Vec<_> = out.iter().map(|p| p.x).collect();
+        assert_eq!(xs, vec![0., 3., 6., 7., 8., 9., 11.]);
+
+        let out: Vec<_> = pts.clone().into_iter().chunked_simplify(100, 0.1).collect();
+        assert_eq!(out, douglas_peucker(&pts, 0.1));
+
+        let out: Vec<_> = pts
+            .iter()
+            .copied()
+            .take(1)
+            .chunked_simplify(4, 0.1)
+            .collect();
+        assert_eq!(out, pts[..1].to_vec());
+        assert_eq!(
+            Vec::<Pt<f64>>::new()
+                .into_iter()
+                .chunked_simplify(4, 0.1)
+                .count(),
+            0
+        );
+    }
+}
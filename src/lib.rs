@@ -1,6 +1,100 @@
 use bs_num::{max, min, Numeric, Zero};
 use std::fmt::Debug;
 
+pub mod algorithms;
+mod ball;
+pub mod batch;
+mod bounds;
+mod camera;
+pub mod codec;
+mod coord;
+mod coordinate3;
+mod coordinate_float;
+pub mod curves;
+mod delta;
+mod digest;
+pub mod grid;
+mod halfspace;
+mod dual;
+mod dyn_coordinate;
+#[cfg(feature = "geodesic")]
+pub mod geodesic;
+pub mod geodesy;
+mod interval;
+mod line3;
+pub mod io;
+pub mod iter;
+mod ordered;
+mod polar;
+mod primitive;
+mod quantize;
+mod ray;
+mod rng;
+mod simplify;
+mod spatial_hash;
+pub mod stats;
+mod tagged;
+mod transform;
+mod tree;
+mod ulps;
+mod units;
+#[cfg(feature = "complex")]
+mod complex;
+#[cfg(feature = "geo")]
+mod geo;
+#[cfg(any(feature = "nalgebra", feature = "glam"))]
+mod linalg;
+#[cfg(feature = "predicates")]
+pub mod predicates;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "sweep")]
+pub mod sweep;
+
+pub use ball::Ball;
+pub use bounds::{chunk_bounds, str_pack, Bounds};
+///axis aligned bounding box - same type as `Bounds`
+pub type Aabb<C> = Bounds<C>;
+pub use camera::{Camera, Intrinsics};
+pub use coord::{Coord, IntCoord};
+pub use coordinate3::Coordinate3;
+pub use coordinate_float::CoordinateFloat;
+pub use delta::{diff, CoordDelta, DeltaOp};
+pub use digest::digest;
+pub use dual::{gradient, Dual};
+pub use dyn_coordinate::DynCoordinate;
+pub use halfspace::{Frustum, HalfSpace};
+pub use interval::{Interval, OutwardRound};
+pub use line3::Line3;
+pub use ordered::{OrderedCoord, TotalOrder};
+pub use polar::{
+    from_cylindrical, from_polar, from_spherical, to_cylindrical, to_polar, to_spherical,
+    Cylindrical, Polar, Spherical,
+};
+pub use quantize::{quantize_with, snap_to_grid_with, RoundingMode};
+pub use ray::Ray;
+pub use simplify::{douglas_peucker, douglas_peucker_indices};
+pub use spatial_hash::SpatialHash;
+pub use tagged::Tagged;
+pub use transform::{normalize_to_unit, Transform};
+pub use tree::{LooseTree, NTree, NearestIter};
+pub use ulps::Ulps;
+pub use units::{Foot, Kilometer, Measured, Meter, Mile, Millimeter, Unit};
+#[cfg(feature = "complex")]
+pub use complex::ComplexCoordinate;
+#[cfg(feature = "derive")]
+pub use coordinate_derive::Coordinate;
+#[cfg(feature = "serde")]
+pub use serialize::{deserialize_coord, serialize_coord};
+#[cfg(feature = "simd")]
+pub use simd::{Coord2d, Coord2f, Coord3d, Coord3f, Coord4d, Coord4f};
+
+//lets the derive's `::coordinate` paths resolve in unit tests
+#[cfg(all(test, feature = "derive"))]
+extern crate self as coordinate;
+
 pub trait Coordinate: Copy + Clone + PartialEq + Debug {
     ///numeric type
     type Scalar: Numeric;
@@ -28,6 +122,57 @@ pub trait Coordinate: Copy + Clone + PartialEq + Debug {
         Self::gen(|_| v)
     }
 
+    ///new from component values, None unless there are exactly DIM of them
+    fn try_from_slice(vals: &[Self::Scalar]) -> Option<Self> {
+        if vals.len() != Self::DIM {
+            return None;
+        }
+        Some(Self::gen(|i| vals[i]))
+    }
+
+    ///new from an iterator of component values, None unless it yields exactly DIM
+    fn from_iter_exact(vals: impl IntoIterator<Item = Self::Scalar>) -> Option<Self> {
+        let vals: Vec<_> = vals.into_iter().take(Self::DIM + 1).collect();
+        Self::try_from_slice(&vals)
+    }
+
+    ///component values in dimension order
+    fn components(&self) -> impl Iterator<Item = Self::Scalar> {
+        let c = *self;
+        (0..Self::DIM).map(move |i| c.val(i))
+    }
+
+    ///component values as a vector
+    fn to_vec(&self) -> Vec<Self::Scalar> {
+        self.components().collect()
+    }
+
+    ///conversion to a coordinate type of the same dimension with a wider scalar;
+    ///panics if the dimensions differ
+    fn cast<D: Coordinate>(&self) -> D
+    where
+        D::Scalar: From<Self::Scalar>,
+    {
+        assert_eq!(Self::DIM, D::DIM, "cast between coordinates of different dimension");
+        D::gen(|i| self.val(i).into())
+    }
+
+    ///numeric conversion to a coordinate type of the same dimension, None if the
+    ///dimensions differ or a component does not fit the target scalar
+    fn try_cast<D: Coordinate>(&self) -> Option<D>
+    where
+        Self::Scalar: num_traits::NumCast,
+        D::Scalar: num_traits::NumCast,
+    {
+        if Self::DIM != D::DIM {
+            return None;
+        }
+        let vals = (0..D::DIM)
+            .map(|i| num_traits::cast(self.val(i)))
+            .collect::<Option<Vec<D::Scalar>>>()?;
+        Some(D::gen(|i| vals[i]))
+    }
+
     ///performs component-wise operation
     fn component_wise(
         &self,
@@ -48,6 +193,53 @@ pub trait Coordinate: Copy + Clone + PartialEq + Debug {
         bln
     }
 
+    ///checks if any component satisfies a predicate
+    fn any_comp(&self, other: &Self, func: impl Fn(Self::Scalar, Self::Scalar) -> bool) -> bool {
+        !self.all_comp(other, |l, r| !func(l, r))
+    }
+
+    ///dimension of the smallest component, the first on ties
+    fn argmin(&self) -> usize {
+        let mut idx = 0;
+        for i in 1..Self::DIM {
+            if self.val(i) < self.val(idx) {
+                idx = i;
+            }
+        }
+        idx
+    }
+
+    ///dimension of the largest component, the first on ties
+    fn argmax(&self) -> usize {
+        let mut idx = 0;
+        for i in 1..Self::DIM {
+            if self.val(i) > self.val(idx) {
+                idx = i;
+            }
+        }
+        idx
+    }
+
+    ///smallest component value
+    fn min_val(&self) -> Self::Scalar {
+        self.val(self.argmin())
+    }
+
+    ///largest component value
+    fn max_val(&self) -> Self::Scalar {
+        self.val(self.argmax())
+    }
+
+    ///sum of all components
+    fn sum(&self) -> Self::Scalar {
+        self.fold(Zero::zero(), |acc, v| acc + v)
+    }
+
+    ///product of all components
+    fn product(&self) -> Self::Scalar {
+        self.fold(num_traits::One::one(), |acc, v| acc * v)
+    }
+
     ///minimum of bounding box - self & other
     fn min_of_bounds(&self, other: &Self) -> Self {
         self.component_wise(other, min)
@@ -83,6 +275,41 @@ pub trait Coordinate: Copy + Clone + PartialEq + Debug {
         Self::gen(|i| transform(self.val(i)))
     }
 
+    ///in place component-wise operation, self = func(self, other)
+    fn component_wise_mut(
+        &mut self,
+        other: &Self,
+        func: impl Fn(Self::Scalar, Self::Scalar) -> Self::Scalar,
+    ) {
+        for i in 0..Self::DIM {
+            let v = self.val_mut(i);
+            *v = func(*v, other.val(i));
+        }
+    }
+
+    ///in place addition
+    fn add_assign_coord(&mut self, other: &Self) {
+        self.component_wise_mut(other, |l, r| l + r)
+    }
+
+    ///in place subtraction
+    fn sub_assign_coord(&mut self, other: &Self) {
+        self.component_wise_mut(other, |l, r| l - r)
+    }
+
+    ///in place multiplication
+    fn scale_mut(&mut self, k: Self::Scalar) {
+        self.map_mut(|v| k * v)
+    }
+
+    ///in place map given functor
+    fn map_mut(&mut self, transform: impl Fn(Self::Scalar) -> Self::Scalar) {
+        for i in 0..Self::DIM {
+            let v = self.val_mut(i);
+            *v = transform(*v);
+        }
+    }
+
     ///fold component values given functor
     fn fold(
         &self,
@@ -95,6 +322,30 @@ pub trait Coordinate: Copy + Clone + PartialEq + Debug {
         }
         total
     }
+
+    ///fold component values into an accumulator of any type
+    fn fold_with<A>(&self, init: A, func: impl Fn(A, Self::Scalar) -> A) -> A {
+        let mut acc = init;
+        for i in 0..Self::DIM {
+            acc = func(acc, self.val(i))
+        }
+        acc
+    }
+
+    ///fold paired component values of self & other into an accumulator of any type
+    fn zip_fold<A>(
+        &self,
+        other: &Self,
+        init: A,
+        func: impl Fn(A, Self::Scalar, Self::Scalar) -> A,
+    ) -> A {
+        let mut acc = init;
+        for i in 0..Self::DIM {
+            acc = func(acc, self.val(i), other.val(i))
+        }
+        acc
+    }
+
     ///sum of squares of all components
     fn square_length(&self) -> Self::Scalar {
         self.fold(Zero::zero(), |acc, v| acc + (v * v))
@@ -104,20 +355,131 @@ pub trait Coordinate: Copy + Clone + PartialEq + Debug {
     fn square_distance(&self, other: &Self) -> Self::Scalar {
         self.comp(other).square_length()
     }
+
+    ///dot (inner) product
+    fn dot(&self, other: &Self) -> Self::Scalar {
+        let mut total = Zero::zero();
+        for i in 0..Self::DIM {
+            total = total + self.val(i) * other.val(i);
+        }
+        total
+    }
+
+    ///L1 (taxicab) distance - sum of absolute component differences
+    fn manhattan_distance(&self, other: &Self) -> Self::Scalar {
+        self.sub(other).fold(Zero::zero(), |acc, v| acc + bs_num::Signed::abs(&v))
+    }
+
+    ///L-infinity (chessboard) distance - largest absolute component difference
+    fn chebyshev_distance(&self, other: &Self) -> Self::Scalar {
+        self.sub(other).fold(Zero::zero(), |acc, v| max(acc, bs_num::Signed::abs(&v)))
+    }
+
+    ///Lp distance (sum |d_i|^p)^(1/p) for p >= 1; p = infinity is chebyshev
+    fn minkowski_distance(&self, other: &Self, p: Self::Scalar) -> Self::Scalar
+    where
+        Self::Scalar: num_traits::Float,
+    {
+        use num_traits::Float;
+        if p == Self::Scalar::one() {
+            return self.manhattan_distance(other);
+        }
+        if p == Self::Scalar::infinity() {
+            return self.chebyshev_distance(other);
+        }
+        self.sub(other)
+            .fold(Zero::zero(), |acc, v| acc + Float::abs(v).powf(p))
+            .powf(p.recip())
+    }
+
+    ///wedge (exterior) product - bivector components as pairwise determinants
+    ///ordered (0,1), (0,2) .. (1,2) ..; perp-dot in 2D, [z, -y, x] of cross product in 3D
+    fn wedge(&self, other: &Self) -> Vec<Self::Scalar> {
+        let mut bivec = Vec::with_capacity(Self::DIM * Self::DIM.saturating_sub(1) / 2);
+        for i in 0..Self::DIM {
+            for j in (i + 1)..Self::DIM {
+                bivec.push(self.val(i) * other.val(j) - self.val(j) * other.val(i));
+            }
+        }
+        bivec
+    }
+
+    ///per axis linear map taking from.min to to.min and from.max to to.max;
+    ///a box with min above max on an axis flips it (e.g. screen y), degenerate
+    ///axes of from map to to.min
+    fn remap(&self, from: &Bounds<Self>, to: &Bounds<Self>) -> Self
+    where
+        Self::Scalar: num_traits::Float,
+    {
+        Self::gen(|i| {
+            let span = from.max.val(i) - from.min.val(i);
+            let t = if span == Zero::zero() {
+                Zero::zero()
+            } else {
+                (self.val(i) - from.min.val(i)) / span
+            };
+            to.min.val(i) + t * (to.max.val(i) - to.min.val(i))
+        })
+    }
+
+    ///world to pixel coordinates of a width x height viewport - the world bounds fill
+    ///the viewport, y points down from the top left corner; later dimensions are kept
+    fn to_screen(&self, world: &Bounds<Self>, viewport: (Self::Scalar, Self::Scalar)) -> Self
+    where
+        Self::Scalar: num_traits::Float,
+    {
+        self.remap(world, &screen_bounds(world, viewport))
+    }
+
+    ///world coordinates of pixel, the inverse of `to_screen`
+    fn from_screen(pixel: &Self, world: &Bounds<Self>, viewport: (Self::Scalar, Self::Scalar)) -> Self
+    where
+        Self::Scalar: num_traits::Float,
+    {
+        pixel.remap(&screen_bounds(world, viewport), world)
+    }
+
+    ///max ulps between self & other over all dimensions
+    fn ulps_distance(&self, other: &Self) -> u64
+    where
+        Self::Scalar: Ulps,
+    {
+        let mut dist = 0;
+        for i in 0..Self::DIM {
+            dist = max(dist, self.val(i).ulps(other.val(i)));
+        }
+        dist
+    }
 }
 
+///viewport box matching world, with y flipped
+fn screen_bounds<C: Coordinate>(world: &Bounds<C>, (width, height): (C::Scalar, C::Scalar)) -> Bounds<C> {
+    let zero = Zero::zero();
+    Bounds {
+        min: C::gen(|i| match i {
+            0 => zero,
+            1 => height,
+            _ => world.min.val(i),
+        }),
+        max: C::gen(|i| match i {
+            0 => width,
+            1 => zero,
+            _ => world.max.val(i),
+        }),
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
-    struct Pt<T>
+    pub(crate) struct Pt<T>
         where
             T: Numeric,
     {
-        x: T,
-        y: T,
+        pub(crate) x: T,
+        pub(crate) y: T,
     }
 
     impl<T> Coordinate for Pt<T>
@@ -188,4 +550,173 @@ mod tests {
         let c = a.add(&b);
         assert_eq!(c, Pt { x: 10, y: 12 });
     }
+
+    #[test]
+    fn test_remap_to_screen() {
+        let world = Bounds::new(Pt { x: -10.0, y: 0.0 }, Pt { x: 10.0, y: 5.0 });
+        let screen = Bounds { min: Pt { x: 0.0, y: 100.0 }, max: Pt { x: 200.0, y: 0.0 } };
+        let p = Pt { x: 5.0, y: 1.0 };
+        let s = p.remap(&world, &screen);
+        assert_eq!(s, Pt { x: 150.0, y: 80.0 });
+        assert_eq!(s.remap(&screen, &world), p);
+        let flat = Bounds::new(Pt { x: 1.0, y: 0.0 }, Pt { x: 1.0, y: 5.0 });
+        assert_eq!(p.remap(&flat, &screen).x, 0.0);
+
+        let px = p.to_screen(&world, (200.0, 100.0));
+        assert_eq!(px, Pt { x: 150.0, y: 80.0 });
+        assert_eq!(Pt::from_screen(&Pt { x: 0.0, y: 0.0 }, &world, (200.0, 100.0)), Pt { x: -10.0, y: 5.0 });
+        assert_eq!(Pt::from_screen(&px, &world, (200.0, 100.0)), p);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let (a, b) = (Pt { x: 1, y: -2 }, Pt { x: 4, y: 2 });
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(b.chebyshev_distance(&a), 4);
+        assert_eq!(a.manhattan_distance(&a), 0);
+
+        let (a, b) = (Pt { x: 1.0, y: -2.0 }, Pt { x: 4.0, y: 2.0 });
+        assert_eq!(a.minkowski_distance(&b, 1.0), 7.0);
+        assert_eq!(a.minkowski_distance(&b, 2.0), 5.0);
+        assert_eq!(a.minkowski_distance(&b, f64::INFINITY), 4.0);
+        let d3 = a.minkowski_distance(&b, 3.0);
+        assert!((d3 - 91f64.cbrt()).abs() < 1e-12);
+        assert!(d3 > 4.0 && d3 < 5.0);
+
+        let c = Coord([0.0f32, 0.0, 0.0]);
+        assert_eq!(c.minkowski_distance(&Coord([1.0, 2.0, 2.0]), 2.0), 3.0);
+    }
+
+    #[test]
+    fn test_dot() {
+        let a = Pt { x: 3, y: 1 };
+        let b = Pt { x: 2, y: 5 };
+        assert_eq!(a.dot(&b), 11);
+        assert_eq!(a.dot(&Pt { x: -1, y: 3 }), 0);
+    }
+
+    #[test]
+    fn test_wedge() {
+        let a = Pt { x: 3, y: 1 };
+        let b = Pt { x: 2, y: 5 };
+        assert_eq!(a.wedge(&b), vec![13]);
+        assert_eq!(b.wedge(&a), vec![-13]);
+        assert_eq!(a.wedge(&a), vec![0]);
+    }
+
+    #[test]
+    fn test_ulps_distance() {
+        let a = Pt { x: 1.0, y: 2.0 };
+        let b = Pt { x: 1.0 + f64::EPSILON, y: 2.0 + 4. * f64::EPSILON };
+        assert_eq!(a.ulps_distance(&a), 0);
+        assert_eq!(a.ulps_distance(&b), 2);
+    }
+
+    #[test]
+    fn test_in_place_ops() {
+        let mut a = Pt { x: 1, y: 2 };
+        let b = Pt { x: 3, y: 5 };
+        a.add_assign_coord(&b);
+        assert_eq!(a, Pt { x: 4, y: 7 });
+        a.sub_assign_coord(&b);
+        assert_eq!(a, Pt { x: 1, y: 2 });
+        a.scale_mut(3);
+        assert_eq!(a, Pt { x: 3, y: 6 });
+        a.map_mut(|v| v - 1);
+        assert_eq!(a, Pt { x: 2, y: 5 });
+        a.component_wise_mut(&b, |l, r| l * r);
+        assert_eq!(a, Pt { x: 6, y: 25 });
+
+        let mut c = Coord([1.0, 2.0, 3.0]);
+        c.add_assign_coord(&Coord([1.0, 1.0, 1.0]));
+        assert_eq!(c, Coord([2.0, 3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_components() {
+        let a = Pt { x: 3, y: -4 };
+        assert_eq!(a.components().collect::<Vec<_>>(), vec![3, -4]);
+        assert_eq!(a.to_vec(), vec![3, -4]);
+        assert_eq!(a.components().map(|v| v * v).sum::<i32>(), a.square_length());
+
+        assert_eq!(Pt::try_from_slice(&[1, 2]), Some(Pt { x: 1, y: 2 }));
+        assert_eq!(Pt::<i32>::try_from_slice(&[1]), None);
+        assert_eq!(Pt::<i32>::try_from_slice(&[1, 2, 3]), None);
+
+        assert_eq!(Pt::from_iter_exact(vec![5, 6]), Some(Pt { x: 5, y: 6 }));
+        assert_eq!(Pt::<i32>::from_iter_exact(0..1), None);
+        assert_eq!(Pt::<i32>::from_iter_exact(0..), None);
+        let c: Option<Coord<f64, 3>> =
+            Coordinate::from_iter_exact(a.components().map(f64::from).chain(Some(1.)));
+        assert_eq!(c, Some(Coord([3., -4., 1.])));
+
+        //arrays keep the slice iterator
+        let arr = [3, -4];
+        assert_eq!(arr.iter().collect::<Vec<&i32>>(), vec![&3, &-4]);
+        assert_eq!(arr.components().collect::<Vec<i32>>(), vec![3, -4]);
+    }
+
+    #[test]
+    fn test_fold_with() {
+        let a = Pt { x: 3, y: -4 };
+        let b = Pt { x: 3, y: 5 };
+        assert_eq!(a.fold_with(0usize, |n, v| n + (v < 0) as usize), 1);
+        assert_eq!(a.fold_with((0, 0), |(lo, hi), v| (lo.min(v), hi.max(v))), (-4, 3));
+        assert!(!a.fold_with(true, |all, v| all && v > 0));
+        assert_eq!(a.zip_fold(&b, 0usize, |n, l, r| n + (l == r) as usize), 1);
+        assert_eq!(a.zip_fold(&b, 0, |acc, l, r| acc + l * r), a.dot(&b));
+        assert!(a.zip_fold(&b, true, |all, l, r| all && l <= r));
+    }
+
+    #[test]
+    fn test_reductions() {
+        let a = Coord([3, -4, 7, -4]);
+        assert_eq!((a.argmin(), a.min_val()), (1, -4));
+        assert_eq!((a.argmax(), a.max_val()), (2, 7));
+        assert_eq!(a.sum(), 2);
+        assert_eq!(a.product(), 336);
+        assert!(a.any_comp(&Coord([0, 0, 8, 0]), |l, r| l > r));
+        assert!(!a.any_comp(&Coord([9, 9, 9, 9]), |l, r| l > r));
+
+        let ext = Pt { x: 2.5, y: 0.5 };
+        assert_eq!(ext.argmax(), 0);
+        assert_eq!(ext.product(), 1.25);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive() {
+        #[derive(Copy, Clone, PartialEq, Debug, Coordinate)]
+        struct Xyz<T: Numeric> {
+            x: T,
+            y: T,
+            #[coordinate(skip)]
+            id: u8,
+            z: T,
+        }
+
+        #[derive(Copy, Clone, PartialEq, Debug, Coordinate)]
+        pub struct Lon<'a, L, const K: usize>
+        where
+            L: Copy + PartialEq + Debug,
+        {
+            #[coordinate(skip)]
+            label: Option<&'a L>,
+            pub lon: f64,
+        }
+
+        assert_eq!(Xyz::<i32>::DIM, 3);
+        let mut a = Xyz::gen(|i| i as i32 + 1);
+        assert_eq!(a, Xyz { x: 1, y: 2, id: 0, z: 3 });
+        *a.val_mut(2) = 7;
+        a.id = 9;
+        assert_eq!((a.val(0), a.val(1), a.val(2)), (1, 2, 7));
+        assert_eq!(a.add(&a), Xyz { x: 2, y: 4, id: 0, z: 14 });
+
+        assert_eq!(Lon::<u8, 3>::DIM, 1);
+        let b: Lon<u8, 3> = Lon::new_from_value(2.5);
+        assert_eq!(b.label, None);
+        assert_eq!(b.square_length(), 6.25);
+    }
 }
@@ -0,0 +1,120 @@
+//!`Coordinate` for nalgebra points & vectors and glam vectors
+use crate::{Coordinate, Coordinate3};
+
+#[cfg(feature = "nalgebra")]
+mod na {
+    use super::*;
+    use bs_num::Numeric;
+    use nalgebra::{Point2, Point3, Vector2, Vector3};
+
+    macro_rules! impl_nalgebra {
+        ($t:ident, $dim:expr, $($i:expr),+) => {
+            impl<T: Numeric + nalgebra::Scalar> Coordinate for $t<T> {
+                type Scalar = T;
+                const DIM: usize = $dim;
+
+                fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+                    $t::new($(val_fn($i)),+)
+                }
+
+                fn val(&self, i: usize) -> Self::Scalar {
+                    self[i]
+                }
+
+                fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+                    &mut self[i]
+                }
+            }
+        };
+    }
+
+    impl_nalgebra!(Point2, 2, 0, 1);
+    impl_nalgebra!(Point3, 3, 0, 1, 2);
+    impl_nalgebra!(Vector2, 2, 0, 1);
+    impl_nalgebra!(Vector3, 3, 0, 1, 2);
+
+    impl<T: Numeric + nalgebra::Scalar> Coordinate3 for Point3<T> {}
+    impl<T: Numeric + nalgebra::Scalar> Coordinate3 for Vector3<T> {}
+}
+
+#[cfg(feature = "glam")]
+mod gl {
+    use super::*;
+    use glam::{DVec2, DVec3, Vec2, Vec3};
+
+    macro_rules! impl_glam {
+        ($t:ident, $s:ty, $dim:expr, $($i:expr => $f:ident),+) => {
+            impl Coordinate for $t {
+                type Scalar = $s;
+                const DIM: usize = $dim;
+
+                fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+                    $t::new($(val_fn($i)),+)
+                }
+
+                fn val(&self, i: usize) -> Self::Scalar {
+                    match i {
+                        $($i => self.$f,)+
+                        _ => unreachable!(),
+                    }
+                }
+
+                fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+                    match i {
+                        $($i => &mut self.$f,)+
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        };
+    }
+
+    impl_glam!(Vec2, f32, 2, 0 => x, 1 => y);
+    impl_glam!(Vec3, f32, 3, 0 => x, 1 => y, 2 => z);
+    impl_glam!(DVec2, f64, 2, 0 => x, 1 => y);
+    impl_glam!(DVec3, f64, 3, 0 => x, 1 => y, 2 => z);
+
+    impl Coordinate3 for Vec3 {}
+    impl Coordinate3 for DVec3 {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CoordinateFloat;
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        use crate::Bounds;
+        use nalgebra::{Point2, Point3, Vector3};
+        let mut p = Point2::new(3.0, 4.0);
+        assert_eq!(CoordinateFloat::length(&p), 5.0);
+        *p.val_mut(0) = 1.0;
+        assert_eq!(p, Point2::new(1.0, 4.0));
+        assert_eq!(Point3::<i64>::DIM, 3);
+        let (x, y) = (Vector3::new(1, 0, 0), Vector3::new(0, 1, 0));
+        assert_eq!(Coordinate3::cross(&x, &y), Vector3::new(0, 0, 1));
+        let b = Bounds::new(Point3::new(1, 5, 2), Point3::new(3, 0, 4));
+        assert_eq!(b.min, Point3::new(1, 0, 2));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam() {
+        use glam::{DVec2, DVec3, Vec3};
+        let v = Vec3::new(1.0, 2.0, 2.0);
+        assert_eq!(CoordinateFloat::length(&v), 3.0);
+        assert_eq!(v.val(2), 2.0);
+        assert_eq!(
+            Coordinate3::cross(&DVec3::new(1., 0., 0.), &DVec3::new(0., 1., 0.)),
+            DVec3::new(0., 0., 1.)
+        );
+        let mut d = DVec2::new(0.5, -1.0);
+        *d.val_mut(1) = 2.0;
+        assert_eq!(
+            Coordinate::add(&d, &DVec2::new(1.0, 1.0)),
+            DVec2::new(1.5, 3.0)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::Coordinate3;
+use num_traits::{Float, Zero};
+
+///3D line in Plucker coordinates - direction d and moment m = p x d for any point p on it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Line3<C> {
+    pub direction: C,
+    pub moment: C,
+}
+
+impl<C: Coordinate3> Line3<C> {
+    ///line through a and b, directed from a to b
+    pub fn from_points(a: &C, b: &C) -> Self {
+        let direction = b.sub(a);
+        Line3 {
+            moment: a.cross(&direction),
+            direction,
+        }
+    }
+
+    ///permuted inner product d1 . m2 + d2 . m1 - zero if the lines are coplanar
+    ///(intersect or are parallel), otherwise the sign tells on which side one
+    ///passes the other
+    pub fn side(&self, other: &Self) -> C::Scalar {
+        self.direction.dot(&other.moment) + other.direction.dot(&self.moment)
+    }
+
+    ///point of the line closest to the origin
+    pub fn point(&self) -> C
+    where
+        C::Scalar: Float,
+    {
+        self.direction
+            .cross(&self.moment)
+            .mult(self.direction.square_length().recip())
+    }
+
+    ///closest points (on self, on other) of the two lines, None if parallel
+    pub fn closest_points(&self, other: &Self) -> Option<(C, C)>
+    where
+        C::Scalar: Float,
+    {
+        let (d1, d2) = (&self.direction, &other.direction);
+        let n = d1.cross(d2);
+        let nn = n.square_length();
+        let tiny = d1.square_length() * d2.square_length() * C::Scalar::epsilon();
+        if nn.is_nan() || nn <= tiny {
+            return None;
+        }
+        let (p1, p2) = (self.point(), other.point());
+        let w = p2.sub(&p1);
+        let t1 = w.cross(d2).dot(&n) / nn;
+        let t2 = w.cross(d1).dot(&n) / nn;
+        Some((p1.add(&d1.mult(t1)), p2.add(&d2.mult(t2))))
+    }
+
+    ///distance between the lines
+    pub fn distance(&self, other: &Self) -> C::Scalar
+    where
+        C::Scalar: Float,
+    {
+        match self.closest_points(other) {
+            Some((a, b)) => a.square_distance(&b).sqrt(),
+            //parallel - distance of a point of other to self
+            None => {
+                let w = other.point().sub(&self.point());
+                let len2 = self.direction.square_length();
+                if len2 == C::Scalar::zero() {
+                    return w.square_length().sqrt();
+                }
+                (w.cross(&self.direction).square_length() / len2).sqrt()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coord, Coordinate};
+
+    #[test]
+    fn test_plucker_side() {
+        let x = Line3::from_points(&Coord([0., 0., 0.]), &Coord([1., 0., 0.]));
+        let above = Line3::from_points(&Coord([0., -1., 1.]), &Coord([0., 1., 1.]));
+        let below = Line3::from_points(&Coord([0., -1., -1.]), &Coord([0., 1., -1.]));
+        let through = Line3::from_points(&Coord([0., -1., 0.]), &Coord([0., 1., 0.]));
+        assert!(x.side(&above) * x.side(&below) < 0.);
+        assert_eq!(x.side(&through), 0.);
+        assert_eq!(
+            x.side(&Line3::from_points(
+                &Coord([0., 3., 0.]),
+                &Coord([5., 3., 0.])
+            )),
+            0.
+        );
+
+        let pi = Line3::from_points(&Coord([3, 1, 2]), &Coord([4, 1, 2]));
+        assert_eq!(pi.moment.dot(&pi.direction), 0);
+    }
+
+    #[test]
+    fn test_closest_points() {
+        let a = Line3::from_points(&Coord([0., 0., 0.]), &Coord([2., 0., 0.]));
+        let b = Line3::from_points(&Coord([5., -3., 4.]), &Coord([5., 3., 4.]));
+        let (p, q) = a.closest_points(&b).unwrap();
+        assert!(p.square_distance(&Coord([5., 0., 0.])) < 1e-24);
+        assert!(q.square_distance(&Coord([5., 0., 4.])) < 1e-24);
+        assert!((a.distance(&b) - 4.).abs() < 1e-12);
+
+        let c = Line3::from_points(&Coord([0., 1., 1.]), &Coord([3., 1., 1.]));
+        assert!(a.closest_points(&c).is_none());
+        assert!((a.distance(&c) - 2f64.sqrt()).abs() < 1e-12);
+        assert!(a.point().square_length() < 1e-24);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::Coordinate;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+///scalar with a total order - a key that is monotonic in the value; floats order
+///-NaN < -inf < .. < -0 == 0 < .. < inf < NaN by their bit patterns, with -0 taken
+///as 0 and all NaNs of a sign as one
+pub trait TotalOrder: Copy {
+    ///order key of the value
+    fn order_key(self) -> i128;
+}
+
+macro_rules! impl_total_order_int {
+    ($($t:ty),*) => {
+        $(impl TotalOrder for $t {
+            fn order_key(self) -> i128 {
+                self as i128
+            }
+        })*
+    };
+}
+
+macro_rules! impl_total_order_float {
+    ($t:ty, $i:ty) => {
+        impl TotalOrder for $t {
+            fn order_key(self) -> i128 {
+                let v = if self == 0.0 {
+                    0.0
+                } else if self.is_nan() {
+                    <$t>::NAN.copysign(self)
+                } else {
+                    self
+                };
+                //flip the magnitude bits of negatives so keys grow with the value
+                let i = v.to_bits() as $i;
+                (if i < 0 { <$i>::MIN - i } else { i }) as i128
+            }
+        }
+    };
+}
+
+impl_total_order_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+impl_total_order_float!(f32, i32);
+impl_total_order_float!(f64, i64);
+
+///coordinate with `Eq`, `Ord` (lexicographic over components) and `Hash` for
+///float scalars - a key for hash maps, sorting and dedup; derefs to the coordinate
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedCoord<C>(pub C);
+
+impl<C> OrderedCoord<C> {
+    ///wrapped coordinate
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> From<C> for OrderedCoord<C> {
+    fn from(coord: C) -> Self {
+        OrderedCoord(coord)
+    }
+}
+
+impl<C> Deref for OrderedCoord<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+impl<C> PartialEq for OrderedCoord<C>
+where
+    C: Coordinate,
+    C::Scalar: TotalOrder,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C> Eq for OrderedCoord<C>
+where
+    C: Coordinate,
+    C::Scalar: TotalOrder,
+{
+}
+
+impl<C> PartialOrd for OrderedCoord<C>
+where
+    C: Coordinate,
+    C::Scalar: TotalOrder,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C> Ord for OrderedCoord<C>
+where
+    C: Coordinate,
+    C::Scalar: TotalOrder,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..C::DIM)
+            .map(|i| self.0.val(i).order_key().cmp(&other.0.val(i).order_key()))
+            .find(|&o| o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<C> Hash for OrderedCoord<C>
+where
+    C: Coordinate,
+    C::Scalar: TotalOrder,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for i in 0..C::DIM {
+            self.0.val(i).order_key().hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_total_order() {
+        let vals = [
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            -1.5,
+            -f64::MIN_POSITIVE,
+            0.0,
+            1e-300,
+            2.0,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+        assert!(vals.windows(2).all(|w| w[0].order_key() < w[1].order_key()));
+        assert_eq!((-0.0f64).order_key(), 0.0f64.order_key());
+        assert_eq!((-3i32).order_key(), -3);
+        assert!((-1f32).order_key() < 0.5f32.order_key());
+        assert_eq!(u64::MAX.order_key(), u64::MAX as i128);
+        assert!(7u8.order_key() < 200u8.order_key() && 0usize.order_key() == 0);
+    }
+
+    #[test]
+    fn test_ordered_coord() {
+        let o = |x: f64, y: f64| OrderedCoord(Pt { x, y });
+        let mut pts = vec![
+            o(1., 2.),
+            o(f64::NAN, 0.),
+            o(-0., 5.),
+            o(1., -1.),
+            o(0., 5.),
+        ];
+        pts.sort();
+        assert_eq!(pts[0], o(0., 5.));
+        assert_eq!(pts[2].y, -1.);
+        assert!(pts[4].x.is_nan());
+        pts.dedup();
+        assert_eq!(pts.len(), 4);
+        assert_eq!(o(f64::NAN, 1.), o(f64::NAN, 1.));
+
+        let set: HashSet<_> = [o(1., 1.), o(1., 1.), o(-0., 0.), o(0., 0.)]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(
+            OrderedCoord::from(Pt { x: 3, y: 4 }).into_inner(),
+            Pt { x: 3, y: 4 }
+        );
+    }
+}
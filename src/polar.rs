@@ -0,0 +1,172 @@
+//!polar, cylindrical and spherical forms of float coordinates - angles are in
+//!radians, azimuths counter clockwise from the x axis in (-pi, pi]
+use crate::{Coordinate, Coordinate3, CoordinateFloat};
+use num_traits::{Float, Zero};
+
+///2D polar coordinates
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Polar<T> {
+    ///distance from the origin
+    pub r: T,
+    ///angle from the x axis
+    pub theta: T,
+}
+
+///3D cylindrical coordinates - polar in the xy plane plus height
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Cylindrical<T> {
+    ///distance from the z axis
+    pub r: T,
+    ///angle from the x axis in the xy plane
+    pub theta: T,
+    pub z: T,
+}
+
+///3D spherical coordinates
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Spherical<T> {
+    ///distance from the origin
+    pub r: T,
+    ///angle from the x axis in the xy plane
+    pub azimuth: T,
+    ///angle down from the z axis in [0, pi]
+    pub inclination: T,
+}
+
+///polar form on the first two dimensions; the angle of the origin is 0
+pub fn to_polar<C>(pt: &C) -> Polar<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (x, y) = (pt.val(0), pt.val(1));
+    Polar {
+        r: x.hypot(y),
+        theta: y.atan2(x),
+    }
+}
+
+///coordinate from polar form, dimensions past the first two are zero
+pub fn from_polar<C>(p: &Polar<C::Scalar>) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let (s, c) = p.theta.sin_cos();
+    C::gen(|i| match i {
+        0 => p.r * c,
+        1 => p.r * s,
+        _ => C::Scalar::zero(),
+    })
+}
+
+///cylindrical form of a 3D coordinate
+pub fn to_cylindrical<C>(pt: &C) -> Cylindrical<C::Scalar>
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    let Polar { r, theta } = to_polar(pt);
+    Cylindrical {
+        r,
+        theta,
+        z: pt.val(2),
+    }
+}
+
+///3D coordinate from cylindrical form
+pub fn from_cylindrical<C>(p: &Cylindrical<C::Scalar>) -> C
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    let mut pt: C = from_polar(&Polar {
+        r: p.r,
+        theta: p.theta,
+    });
+    *pt.val_mut(2) = p.z;
+    pt
+}
+
+///spherical form of a 3D coordinate; the angles of the origin are 0
+pub fn to_spherical<C>(pt: &C) -> Spherical<C::Scalar>
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    let (x, y, z) = (pt.val(0), pt.val(1), pt.val(2));
+    Spherical {
+        r: pt.length(),
+        azimuth: y.atan2(x),
+        inclination: x.hypot(y).atan2(z),
+    }
+}
+
+///3D coordinate from spherical form
+pub fn from_spherical<C>(p: &Spherical<C::Scalar>) -> C
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    let (sa, ca) = p.azimuth.sin_cos();
+    let (si, ci) = p.inclination.sin_cos();
+    C::gen(|i| match i {
+        0 => p.r * si * ca,
+        1 => p.r * si * sa,
+        2 => p.r * ci,
+        _ => C::Scalar::zero(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn test_polar() {
+        let p = to_polar(&Pt { x: -1.0, y: 1.0 });
+        assert!((p.r - 2f64.sqrt()).abs() < 1e-15 && (p.theta - 3. * FRAC_PI_4).abs() < 1e-15);
+        assert_eq!(to_polar(&Pt { x: -2.0, y: 0.0 }).theta, PI);
+        assert_eq!(
+            to_polar(&Pt { x: 0.0, y: 0.0 }),
+            Polar { r: 0.0, theta: 0.0 }
+        );
+        for pt in [Pt { x: 3.0, y: -4.0 }, Pt { x: -0.5, y: 0.25 }] {
+            let back: Pt<f64> = from_polar(&to_polar(&pt));
+            assert!(back.square_distance(&pt) < 1e-28);
+        }
+        let c: Coord<f64, 3> = from_polar(&Polar {
+            r: 2.0,
+            theta: FRAC_PI_2,
+        });
+        assert!(c.square_distance(&Coord([0., 2., 0.])) < 1e-28);
+    }
+
+    #[test]
+    fn test_cylindrical_spherical() {
+        let pt = Coord([1.0, 1.0, 2f64.sqrt()]);
+        let s = to_spherical(&pt);
+        assert!((s.r - 2.).abs() < 1e-15);
+        assert!((s.azimuth - FRAC_PI_4).abs() < 1e-15);
+        assert!((s.inclination - FRAC_PI_4).abs() < 1e-15);
+        let c = to_cylindrical(&pt);
+        assert!((c.r - 2f64.sqrt()).abs() < 1e-15 && c.z == pt.val(2));
+        for p in [pt, Coord([-3.0, 0.5, -7.0]), Coord([0.0, 0.0, -1.0])] {
+            let a: Coord<f64, 3> = from_spherical(&to_spherical(&p));
+            let b: Coord<f64, 3> = from_cylindrical(&to_cylindrical(&p));
+            assert!(a.square_distance(&p) < 1e-26 && b.square_distance(&p) < 1e-26);
+        }
+        assert_eq!(to_spherical(&Coord([0.0, 0.0, -1.0])).inclination, PI);
+        assert_eq!(
+            to_spherical(&Coord([0.0, 0.0, 0.0])),
+            Spherical {
+                r: 0.0,
+                azimuth: 0.0,
+                inclination: 0.0
+            }
+        );
+    }
+}
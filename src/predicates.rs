@@ -0,0 +1,224 @@
+//!exact orientation and incircle signs - a floating point filter with a fallback
+//!to exact expansion arithmetic (Shewchuk). Scalars are widened to f64, so the
+//!signs are exact for any scalar that converts losslessly (f32, f64, i32 ..)
+use crate::Coordinate;
+
+const EPS: f64 = f64::EPSILON * 0.5;
+const ORIENT2D_BOUND: f64 = (3. + 16. * EPS) * EPS;
+const ORIENT3D_BOUND: f64 = (7. + 56. * EPS) * EPS;
+const INCIRCLE_BOUND: f64 = (10. + 96. * EPS) * EPS;
+
+///nonoverlapping expansion, components in increasing magnitude
+type Expansion = Vec<f64>;
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bv = x - a;
+    let av = x - bv;
+    (x, (a - av) + (b - bv))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn grow(e: &[f64], b: f64) -> Expansion {
+    let mut out = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &c in e {
+        let (sum, err) = two_sum(q, c);
+        if err != 0. {
+            out.push(err);
+        }
+        q = sum;
+    }
+    out.push(q);
+    out
+}
+
+fn sum(e: &[f64], f: &[f64]) -> Expansion {
+    f.iter().fold(e.to_vec(), |acc, &b| grow(&acc, b))
+}
+
+fn product(e: &[f64], f: &[f64]) -> Expansion {
+    let mut out = vec![0.];
+    for &b in f {
+        for &a in e {
+            let (x, y) = two_product(a, b);
+            out = grow(&grow(&out, y), x);
+        }
+    }
+    out
+}
+
+fn neg(e: &[f64]) -> Expansion {
+    e.iter().map(|v| -v).collect()
+}
+
+fn diff(a: f64, b: f64) -> Expansion {
+    let (x, y) = two_sum(a, -b);
+    vec![y, x]
+}
+
+///most significant non-zero component, carries the sign of the expansion
+fn estimate_sign(e: &[f64]) -> f64 {
+    e.iter().rev().copied().find(|v| *v != 0.).unwrap_or(0.)
+}
+
+fn xy<C>(p: &C) -> [f64; 2]
+where
+    C: Coordinate,
+    C::Scalar: Into<f64>,
+{
+    [p.val(0).into(), p.val(1).into()]
+}
+
+///positive if a, b, c turn counter-clockwise on the first two dimensions,
+///negative if clockwise, zero if collinear
+pub fn orient2d<C>(a: &C, b: &C, c: &C) -> f64
+where
+    C: Coordinate,
+    C::Scalar: Into<f64>,
+{
+    let (a, b, c) = (xy(a), xy(b), xy(c));
+    let left = (a[0] - c[0]) * (b[1] - c[1]);
+    let right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = left - right;
+    if det.abs() >= ORIENT2D_BOUND * (left.abs() + right.abs()) && det.is_finite() {
+        return det;
+    }
+    let l = product(&diff(a[0], c[0]), &diff(b[1], c[1]));
+    let r = product(&diff(a[1], c[1]), &diff(b[0], c[0]));
+    estimate_sign(&sum(&l, &neg(&r)))
+}
+
+///positive if d lies below the plane through a, b, c on the first three dimensions -
+///below being the side from which a, b, c appear clockwise; negative if above,
+///zero if coplanar
+pub fn orient3d<C>(a: &C, b: &C, c: &C, d: &C) -> f64
+where
+    C: Coordinate,
+    C::Scalar: Into<f64>,
+{
+    let xyz = |p: &C| -> [f64; 3] { [p.val(0).into(), p.val(1).into(), p.val(2).into()] };
+    let (a, b, c, d) = (xyz(a), xyz(b), xyz(c), xyz(d));
+    let (adx, ady, adz) = (a[0] - d[0], a[1] - d[1], a[2] - d[2]);
+    let (bdx, bdy, bdz) = (b[0] - d[0], b[1] - d[1], b[2] - d[2]);
+    let (cdx, cdy, cdz) = (c[0] - d[0], c[1] - d[1], c[2] - d[2]);
+    let det = adz * (bdx * cdy - cdx * bdy)
+        + bdz * (cdx * ady - adx * cdy)
+        + cdz * (adx * bdy - bdx * ady);
+    let permanent = adz.abs() * ((bdx * cdy).abs() + (cdx * bdy).abs())
+        + bdz.abs() * ((cdx * ady).abs() + (adx * cdy).abs())
+        + cdz.abs() * ((adx * bdy).abs() + (bdx * ady).abs());
+    if det.abs() >= ORIENT3D_BOUND * permanent && det.is_finite() {
+        return det;
+    }
+    let (adx, ady, adz) = (diff(a[0], d[0]), diff(a[1], d[1]), diff(a[2], d[2]));
+    let (bdx, bdy, bdz) = (diff(b[0], d[0]), diff(b[1], d[1]), diff(b[2], d[2]));
+    let (cdx, cdy, cdz) = (diff(c[0], d[0]), diff(c[1], d[1]), diff(c[2], d[2]));
+    let cross = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| {
+        sum(&product(px, qy), &neg(&product(qx, py)))
+    };
+    let terms = [
+        product(&adz, &cross(&bdx, &bdy, &cdx, &cdy)),
+        product(&bdz, &cross(&cdx, &cdy, &adx, &ady)),
+        product(&cdz, &cross(&adx, &ady, &bdx, &bdy)),
+    ];
+    estimate_sign(&sum(&sum(&terms[0], &terms[1]), &terms[2]))
+}
+
+///positive if d lies inside the circle through counter-clockwise a, b, c on the
+///first two dimensions, negative if outside, zero if cocircular
+pub fn incircle<C>(a: &C, b: &C, c: &C, d: &C) -> f64
+where
+    C: Coordinate,
+    C::Scalar: Into<f64>,
+{
+    let (a, b, c, d) = (xy(a), xy(b), xy(c), xy(d));
+    let (adx, ady) = (a[0] - d[0], a[1] - d[1]);
+    let (bdx, bdy) = (b[0] - d[0], b[1] - d[1]);
+    let (cdx, cdy) = (c[0] - d[0], c[1] - d[1]);
+    let (bc, ca, ab) = (
+        bdx * cdy - cdx * bdy,
+        cdx * ady - adx * cdy,
+        adx * bdy - bdx * ady,
+    );
+    let (al, bl, cl) = (
+        adx * adx + ady * ady,
+        bdx * bdx + bdy * bdy,
+        cdx * cdx + cdy * cdy,
+    );
+    let det = al * bc + bl * ca + cl * ab;
+    let permanent = al * ((bdx * cdy).abs() + (cdx * bdy).abs())
+        + bl * ((cdx * ady).abs() + (adx * cdy).abs())
+        + cl * ((adx * bdy).abs() + (bdx * ady).abs());
+    if det.abs() >= INCIRCLE_BOUND * permanent && det.is_finite() {
+        return det;
+    }
+    let (adx, ady) = (diff(a[0], d[0]), diff(a[1], d[1]));
+    let (bdx, bdy) = (diff(b[0], d[0]), diff(b[1], d[1]));
+    let (cdx, cdy) = (diff(c[0], d[0]), diff(c[1], d[1]));
+    let cross = |px: &[f64], py: &[f64], qx: &[f64], qy: &[f64]| {
+        sum(&product(px, qy), &neg(&product(qx, py)))
+    };
+    let lift = |x: &[f64], y: &[f64]| sum(&product(x, x), &product(y, y));
+    let terms = [
+        product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy)),
+        product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady)),
+        product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy)),
+    ];
+    estimate_sign(&sum(&sum(&terms[0], &terms[1]), &terms[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orient2d() {
+        assert!(orient2d(&[0., 0.], &[1., 0.], &[0., 1.]) > 0.);
+        assert!(orient2d(&[0., 0.], &[0., 1.], &[1., 0.]) < 0.);
+        assert_eq!(orient2d(&[0., 0.], &[1., 1.], &[2., 2.]), 0.);
+        //nearly collinear points where the naive determinant has the wrong sign
+        let (a, b) = ([0.5, 0.5], [12., 12.]);
+        for i in 0..64 {
+            let c = [24.0 + i as f64 * f64::EPSILON * 16., 24.];
+            let exact = orient2d(&a, &b, &c);
+            assert!(exact <= 0., "{}", i);
+        }
+        let c = [24.00000000000005, 24.000000000000053];
+        assert!(orient2d(&a, &b, &c) > 0.);
+    }
+
+    #[test]
+    fn test_orient3d() {
+        let (a, b, c) = ([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+        assert!(orient3d(&a, &b, &c, &[0., 0., -1.]) > 0.);
+        assert!(orient3d(&a, &b, &c, &[0.3, 0.3, 1.]) < 0.);
+        assert!(orient3d(&b, &a, &c, &[0., 0., -1.]) < 0.);
+        assert_eq!(orient3d(&a, &b, &c, &[5., -7., 0.]), 0.);
+        //points just off a plane where the x & y differences are inexact
+        let (a, b, c) = ([0.1, 0.2, 0.3], [1.1, 0.7, 0.3], [0.4, 1.9, 0.3]);
+        let tiny = f64::EPSILON / 4.;
+        assert_eq!(orient3d(&a, &b, &c, &[0.5, 0.5, 0.3]), 0.);
+        assert!(orient3d(&a, &b, &c, &[0.5, 0.5, 0.3 + tiny]) < 0.);
+        assert!(orient3d(&a, &b, &c, &[0.5, 0.5, 0.3 - tiny]) > 0.);
+        //integer and f32 scalars are exact too
+        let z: [i32; 3] = [0, 0, -1];
+        assert!(orient3d(&[0, 0, 0], &[1, 0, 0], &[0, 1, 0], &z) > 0.);
+        assert!(orient2d(&[0f32, 0.], &[1., 0.], &[0., 1.]) > 0.);
+    }
+
+    #[test]
+    fn test_incircle() {
+        let (a, b, c) = ([1., 0.], [0., 1.], [-1., 0.]);
+        assert!(incircle(&a, &b, &c, &[0., 0.]) > 0.);
+        assert!(incircle(&a, &b, &c, &[2., 0.]) < 0.);
+        assert_eq!(incircle(&a, &b, &c, &[0., -1.]), 0.);
+        let tiny = f64::EPSILON;
+        assert!(incircle(&a, &b, &c, &[0., -1. + tiny]) > 0.);
+        assert!(incircle(&a, &b, &c, &[0., -1. - tiny]) < 0.);
+    }
+}
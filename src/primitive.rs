@@ -0,0 +1,116 @@
+//!`Coordinate` for fixed size arrays and homogeneous tuples
+use crate::{Coordinate, Coordinate3};
+use bs_num::Numeric;
+
+impl<T: Numeric, const N: usize> Coordinate for [T; N] {
+    type Scalar = T;
+    const DIM: usize = N;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        std::array::from_fn(val_fn)
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        self[i]
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        &mut self[i]
+    }
+}
+
+impl<T: Numeric> Coordinate for (T, T) {
+    type Scalar = T;
+    const DIM: usize = 2;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        (val_fn(0), val_fn(1))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        match i {
+            0 => self.0,
+            1 => self.1,
+            _ => unreachable!(),
+        }
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        match i {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T: Numeric> Coordinate for (T, T, T) {
+    type Scalar = T;
+    const DIM: usize = 3;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        (val_fn(0), val_fn(1), val_fn(2))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        match i {
+            0 => self.0,
+            1 => self.1,
+            2 => self.2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        match i {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            2 => &mut self.2,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<T: Numeric> Coordinate3 for [T; 3] {}
+
+impl<T: Numeric> Coordinate3 for (T, T, T) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrays() {
+        let a = [3.];
+        assert_eq!(<[f64; 1]>::DIM, 1);
+        assert_eq!(a.add(&[1.]), [4.]);
+
+        let mut b = [1, 2];
+        *b.val_mut(1) = 5;
+        assert_eq!(b.square_length(), 26);
+
+        let c = [1., 2., 2.];
+        assert_eq!(c.square_length(), 9.);
+        assert_eq!(c.min_of_bounds(&[0., 3., 1.]), [0., 2., 1.]);
+
+        let d = <[i32; 4]>::gen(|i| i as i32);
+        assert_eq!(d, [0, 1, 2, 3]);
+        assert_eq!(d.square_distance(&[1, 1, 1, 1]), 6);
+    }
+
+    #[test]
+    fn test_tuples() {
+        let mut p = (1., 2.);
+        *p.val_mut(0) = 4.;
+        assert_eq!(p.sub(&(1., 6.)), (3., -4.));
+        assert_eq!(p.val(1), 2.);
+
+        let q = (1, -2, 3);
+        assert_eq!(<(i32, i32, i32)>::DIM, 3);
+        assert_eq!(q.mult(2), (2, -4, 6));
+        assert_eq!(q.square_length(), 14);
+        assert_eq!(<(i32, i32, i32)>::new_from_value(7), (7, 7, 7));
+        assert_eq!((1, 0, 0).cross(&(0, 1, 0)), (0, 0, 1));
+        assert_eq!([0, 1, 0].cross(&[1, 0, 0]), [0, 0, -1]);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::Coordinate;
+use num_traits::{Float, ToPrimitive};
+
+///rounding of scalars to integral values
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum RoundingMode {
+    ///towards negative infinity
+    Floor,
+    ///towards positive infinity
+    Ceil,
+    ///to nearest, ties to even (banker's rounding)
+    HalfEven,
+    ///to nearest, ties away from zero
+    HalfAwayFromZero,
+    ///to nearest, ties towards positive infinity
+    HalfUp,
+}
+
+impl RoundingMode {
+    ///v rounded to an integral value
+    pub fn round<T: Float>(self, v: T) -> T {
+        match self {
+            RoundingMode::Floor => v.floor(),
+            RoundingMode::Ceil => v.ceil(),
+            RoundingMode::HalfAwayFromZero => v.round(),
+            RoundingMode::HalfUp => {
+                let f = v.floor();
+                //exact for floats, unlike v + 0.5
+                if v - f < (T::one() + T::one()).recip() {
+                    f
+                } else {
+                    f + T::one()
+                }
+            }
+            RoundingMode::HalfEven => {
+                let f = v.floor();
+                //exact for floats
+                let frac = v - f;
+                let half = (T::one() + T::one()).recip();
+                if frac < half {
+                    f
+                } else if frac > half {
+                    f + T::one()
+                } else if (f * half).fract() == T::zero() {
+                    f
+                } else {
+                    f + T::one()
+                }
+            }
+        }
+    }
+}
+
+///pt snapped to the nearest node of a grid of cell_size with the given rounding
+pub fn snap_to_grid_with<C>(pt: &C, cell_size: C::Scalar, mode: RoundingMode) -> C
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    pt.map(|v| mode.round(v / cell_size) * cell_size)
+}
+
+///integer grid index of pt relative to origin with the given rounding - Floor gives the
+///index of the containing cell; None if the dimensions of C and D differ or for
+///non-finite or out of range components
+pub fn quantize_with<C, D>(
+    pt: &C,
+    origin: &C,
+    cell_size: C::Scalar,
+    mode: RoundingMode,
+) -> Option<D>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+    D: Coordinate<Scalar = i64>,
+{
+    if C::DIM != D::DIM {
+        return None;
+    }
+    let mut cell = D::new_origin();
+    for i in 0..C::DIM {
+        *cell.val_mut(i) = mode
+            .round((pt.val(i) - origin.val(i)) / cell_size)
+            .to_i64()?;
+    }
+    Some(cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::{Coord, IntCoord};
+    use RoundingMode::*;
+
+    #[test]
+    fn test_rounding_modes() {
+        let vals = [-2.5, -1.5, -0.5, 0.5, 1.5, 2.5, 2.4, -2.6];
+        let round = |mode: RoundingMode| vals.iter().map(|&v| mode.round(v)).collect::<Vec<f64>>();
+        assert_eq!(round(Floor), vec![-3., -2., -1., 0., 1., 2., 2., -3.]);
+        assert_eq!(round(Ceil), vec![-2., -1., -0., 1., 2., 3., 3., -2.]);
+        assert_eq!(round(HalfEven), vec![-2., -2., -0., 0., 2., 2., 2., -3.]);
+        assert_eq!(
+            round(HalfAwayFromZero),
+            vec![-3., -2., -1., 1., 2., 3., 2., -3.]
+        );
+        assert_eq!(round(HalfUp), vec![-2., -1., -0., 1., 2., 3., 2., -3.]);
+        assert_eq!(HalfUp.round(0.49999999999999994f64), 0.);
+        assert_eq!(HalfEven.round(4503599627370497f64), 4503599627370497.);
+        assert!(HalfEven.round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_snap_and_quantize() {
+        //exact half cell boundaries with a cell size of 0.5
+        let pt = Pt { x: 0.25, y: -0.75 };
+        assert_eq!(snap_to_grid_with(&pt, 0.5, HalfEven), Pt { x: 0., y: -1. });
+        assert_eq!(
+            snap_to_grid_with(&pt, 0.5, HalfAwayFromZero),
+            Pt { x: 0.5, y: -1. }
+        );
+        assert_eq!(snap_to_grid_with(&pt, 0.5, Floor), Pt { x: 0., y: -1. });
+        assert_eq!(snap_to_grid_with(&pt, 0.5, Ceil), Pt { x: 0.5, y: -0.5 });
+
+        let origin = Pt { x: 10., y: 10. };
+        let q = Pt { x: 13., y: 7. };
+        let cell: IntCoord<2> = quantize_with(&q, &origin, 2., Floor).unwrap();
+        assert_eq!(cell, Coord([1, -2]));
+        let node: IntCoord<2> = quantize_with(&q, &origin, 2., HalfEven).unwrap();
+        assert_eq!(node, Coord([2, -2]));
+        let node: IntCoord<2> = quantize_with(&q, &origin, 2., HalfAwayFromZero).unwrap();
+        assert_eq!(node, Coord([2, -2]));
+        let nan = Pt { x: f64::NAN, y: 0. };
+        assert!(quantize_with::<_, IntCoord<2>>(&nan, &origin, 2., Floor).is_none());
+        assert!(quantize_with::<_, IntCoord<3>>(&q, &origin, 2., Floor).is_none());
+        assert!(quantize_with::<_, IntCoord<1>>(&q, &origin, 2., Floor).is_none());
+    }
+}
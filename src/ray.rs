@@ -0,0 +1,111 @@
+use crate::{Ball, Bounds, Coordinate};
+use num_traits::{Float, Zero};
+
+///half line origin + t * direction for t >= 0
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ray<C> {
+    pub origin: C,
+    pub direction: C,
+}
+
+impl<C> Ray<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///new ray from origin and direction
+    pub fn new(origin: C, direction: C) -> Self {
+        Ray { origin, direction }
+    }
+
+    ///point at parameter t
+    pub fn at(&self, t: C::Scalar) -> C {
+        self.origin.add(&self.direction.mult(t))
+    }
+
+    ///entry and exit parameters through ball, entry is 0 if the origin is inside;
+    ///None if the ray misses
+    pub fn intersect_ball(&self, ball: &Ball<C>) -> Option<(C::Scalar, C::Scalar)> {
+        let zero = C::Scalar::zero();
+        let a = self.direction.square_length();
+        let oc = self.origin.sub(&ball.center);
+        let b = oc.dot(&self.direction);
+        let c = oc.square_length() - ball.square_radius();
+        if a == zero {
+            return if c <= zero { Some((zero, zero)) } else { None };
+        }
+        let disc = b * b - a * c;
+        if disc.is_nan() || disc < zero {
+            return None;
+        }
+        let root = disc.sqrt();
+        let (t0, t1) = ((-b - root) / a, (-b + root) / a);
+        if t1 < zero {
+            return None;
+        }
+        Some((t0.max(zero), t1))
+    }
+
+    ///entry and exit parameters through bounds (slab method), entry is 0 if the
+    ///origin is inside; None if the ray misses
+    pub fn intersect_bounds(&self, bounds: &Bounds<C>) -> Option<(C::Scalar, C::Scalar)> {
+        let zero = C::Scalar::zero();
+        let (mut t_min, mut t_max) = (zero, C::Scalar::infinity());
+        for i in 0..C::DIM {
+            let (o, d) = (self.origin.val(i), self.direction.val(i));
+            let (lo, hi) = (bounds.min.val(i), bounds.max.val(i));
+            if d == zero {
+                if o < lo || o > hi {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((lo - o) / d, (hi - o) / d);
+            let (near, far) = if a < b { (a, b) } else { (b, a) };
+            t_min = t_min.max(near);
+            t_max = t_max.min(far);
+            if t_min > t_max {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_ray_ball() {
+        let ball = Ball::new(Pt { x: 5., y: 0. }, 1.);
+        let ray = Ray::new(Pt { x: 0., y: 0. }, Pt { x: 2., y: 0. });
+        assert_eq!(ray.intersect_ball(&ball), Some((2., 3.)));
+        assert_eq!(ray.at(2.), Pt { x: 4., y: 0. });
+        let inside = Ray::new(Pt { x: 5., y: 0. }, Pt { x: 0., y: 1. });
+        assert_eq!(inside.intersect_ball(&ball), Some((0., 1.)));
+        let behind = Ray::new(Pt { x: 0., y: 0. }, Pt { x: -1., y: 0. });
+        assert_eq!(behind.intersect_ball(&ball), None);
+        let miss = Ray::new(Pt { x: 0., y: 2. }, Pt { x: 1., y: 0. });
+        assert_eq!(miss.intersect_ball(&ball), None);
+        let tangent = Ray::new(Pt { x: 0., y: 1. }, Pt { x: 1., y: 0. });
+        assert_eq!(tangent.intersect_ball(&ball), Some((5., 5.)));
+    }
+
+    #[test]
+    fn test_ray_bounds() {
+        let b = Bounds::new(Coord([1., 1., 1.]), Coord([2., 3., 4.]));
+        let ray = Ray::new(Coord([0., 0., 0.]), Coord([1., 1., 1.]));
+        assert_eq!(ray.intersect_bounds(&b), Some((1., 2.)));
+        let axis = Ray::new(Coord([1.5, 2., -1.]), Coord([0., 0., 2.]));
+        assert_eq!(axis.intersect_bounds(&b), Some((1., 2.5)));
+        let outside = Ray::new(Coord([0., 2., 0.]), Coord([0., 0., 1.]));
+        assert_eq!(outside.intersect_bounds(&b), None);
+        let inside = Ray::new(Coord([1.5, 2., 2.]), Coord([-1., 0., 0.]));
+        assert_eq!(inside.intersect_bounds(&b), Some((0., 0.5)));
+        let away = Ray::new(Coord([3., 2., 2.]), Coord([1., 0., 0.]));
+        assert_eq!(away.intersect_bounds(&b), None);
+    }
+}
@@ -0,0 +1,106 @@
+//!serde support - coordinates as sequences of DIM scalars, e.g. json `[x, y, z]`
+use crate::{Coord, Coordinate};
+use bs_num::Numeric;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+///serializes any coordinate as a sequence of its components,
+///for use with `#[serde(serialize_with = "coordinate::serialize_coord")]`
+pub fn serialize_coord<C, S>(coord: &C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: Coordinate,
+    C::Scalar: Serialize,
+    S: Serializer,
+{
+    let mut tup = serializer.serialize_tuple(C::DIM)?;
+    for i in 0..C::DIM {
+        tup.serialize_element(&coord.val(i))?;
+    }
+    tup.end()
+}
+
+///deserializes any coordinate from a sequence of exactly DIM components,
+///for use with `#[serde(deserialize_with = "coordinate::deserialize_coord")]`
+pub fn deserialize_coord<'de, C, D>(deserializer: D) -> Result<C, D::Error>
+where
+    C: Coordinate,
+    C::Scalar: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_tuple(C::DIM, CoordVisitor(PhantomData))
+}
+
+struct CoordVisitor<C>(PhantomData<C>);
+
+impl<'de, C> Visitor<'de> for CoordVisitor<C>
+where
+    C: Coordinate,
+    C::Scalar: Deserialize<'de>,
+{
+    type Value = C;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of {} coordinate components", C::DIM)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<C, A::Error> {
+        let mut coord = C::new_origin();
+        for i in 0..C::DIM {
+            *coord.val_mut(i) = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<C::Scalar>()?.is_some() {
+            return Err(de::Error::invalid_length(C::DIM + 1, &self));
+        }
+        Ok(coord)
+    }
+}
+
+impl<T, const N: usize> Serialize for Coord<T, N>
+where
+    T: Numeric + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_coord(self, serializer)
+    }
+}
+
+impl<'de, T, const N: usize> Deserialize<'de> for Coord<T, N>
+where
+    T: Numeric + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_coord(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_serde_coord() {
+        let c = Coord([1.5, -2.0, 3.0]);
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(json, "[1.5,-2.0,3.0]");
+        assert_eq!(serde_json::from_str::<Coord<f64, 3>>(&json).unwrap(), c);
+        assert_eq!(
+            serde_json::from_str::<Coord<i64, 2>>("[4, -7]").unwrap(),
+            Coord([4, -7])
+        );
+        assert!(serde_json::from_str::<Coord<f64, 3>>("[1.0, 2.0]").is_err());
+        assert!(serde_json::from_str::<Coord<f64, 2>>("[1.0, 2.0, 3.0]").is_err());
+
+        let mut buf = Vec::new();
+        let mut ser = serde_json::Serializer::new(&mut buf);
+        serialize_coord(&Pt { x: 3, y: 4 }, &mut ser).unwrap();
+        assert_eq!(buf, b"[3,4]");
+        let mut de = serde_json::Deserializer::from_str("[5, 6]");
+        let pt: Pt<i32> = deserialize_coord(&mut de).unwrap();
+        assert_eq!(pt, Pt { x: 5, y: 6 });
+    }
+}
@@ -0,0 +1,261 @@
+//!float coordinates of 2 to 4 dimensions stored in four aligned lanes so that
+//!`add`, `sub`, `mult`, `dot`, `square_length` and `square_distance` run as packed
+//!SSE2 operations on x86_64 (plain lane loops elsewhere). Unused lanes hold zero and
+//!results match the generic `Coordinate` methods bit for bit
+use crate::{Coordinate, Coordinate3};
+
+#[cfg(target_arch = "x86_64")]
+mod lanes {
+    use std::arch::x86_64::*;
+
+    //SSE2 is part of the x86_64 baseline, so the intrinsics are always available
+
+    pub fn add_f32(a: &[f32; 4], b: &[f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let r = _mm_add_ps(_mm_loadu_ps(a.as_ptr()), _mm_loadu_ps(b.as_ptr()));
+            _mm_storeu_ps(out.as_mut_ptr(), r);
+        }
+        out
+    }
+
+    pub fn sub_f32(a: &[f32; 4], b: &[f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let r = _mm_sub_ps(_mm_loadu_ps(a.as_ptr()), _mm_loadu_ps(b.as_ptr()));
+            _mm_storeu_ps(out.as_mut_ptr(), r);
+        }
+        out
+    }
+
+    pub fn mul_f32(a: &[f32; 4], b: &[f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let r = _mm_mul_ps(_mm_loadu_ps(a.as_ptr()), _mm_loadu_ps(b.as_ptr()));
+            _mm_storeu_ps(out.as_mut_ptr(), r);
+        }
+        out
+    }
+
+    pub fn add_f64(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let p = out.as_mut_ptr();
+            _mm_storeu_pd(
+                p,
+                _mm_add_pd(_mm_loadu_pd(a.as_ptr()), _mm_loadu_pd(b.as_ptr())),
+            );
+            _mm_storeu_pd(
+                p.add(2),
+                _mm_add_pd(
+                    _mm_loadu_pd(a.as_ptr().add(2)),
+                    _mm_loadu_pd(b.as_ptr().add(2)),
+                ),
+            );
+        }
+        out
+    }
+
+    pub fn sub_f64(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let p = out.as_mut_ptr();
+            _mm_storeu_pd(
+                p,
+                _mm_sub_pd(_mm_loadu_pd(a.as_ptr()), _mm_loadu_pd(b.as_ptr())),
+            );
+            _mm_storeu_pd(
+                p.add(2),
+                _mm_sub_pd(
+                    _mm_loadu_pd(a.as_ptr().add(2)),
+                    _mm_loadu_pd(b.as_ptr().add(2)),
+                ),
+            );
+        }
+        out
+    }
+
+    pub fn mul_f64(a: &[f64; 4], b: &[f64; 4]) -> [f64; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let p = out.as_mut_ptr();
+            _mm_storeu_pd(
+                p,
+                _mm_mul_pd(_mm_loadu_pd(a.as_ptr()), _mm_loadu_pd(b.as_ptr())),
+            );
+            _mm_storeu_pd(
+                p.add(2),
+                _mm_mul_pd(
+                    _mm_loadu_pd(a.as_ptr().add(2)),
+                    _mm_loadu_pd(b.as_ptr().add(2)),
+                ),
+            );
+        }
+        out
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+mod lanes {
+    macro_rules! lane_op {
+        ($name:ident, $t:ty, $op:tt) => {
+            pub fn $name(a: &[$t; 4], b: &[$t; 4]) -> [$t; 4] {
+                [a[0] $op b[0], a[1] $op b[1], a[2] $op b[2], a[3] $op b[3]]
+            }
+        };
+    }
+
+    lane_op!(add_f32, f32, +);
+    lane_op!(sub_f32, f32, -);
+    lane_op!(mul_f32, f32, *);
+    lane_op!(add_f64, f64, +);
+    lane_op!(sub_f64, f64, -);
+    lane_op!(mul_f64, f64, *);
+}
+
+macro_rules! simd_coord {
+    ($name:ident, $t:ty, $dim:expr, $align:tt, $add:ident, $sub:ident, $mul:ident) => {
+        #[doc = concat!(stringify!($dim), "D `", stringify!($t), "` coordinate in four packed lanes")]
+        #[derive(Copy, Clone, PartialEq, Default, Debug)]
+        #[repr(C, align($align))]
+        pub struct $name([$t; 4]);
+
+        impl $name {
+            ///new coordinate from component values
+            pub fn new(vals: [$t; $dim]) -> Self {
+                <Self as Coordinate>::gen(|i| vals[i])
+            }
+
+            ///sum of the lane products, in component order like the generic fold
+            #[inline]
+            fn lane_sum(m: [$t; 4]) -> $t {
+                (0..$dim).fold(0.0, |acc, i| acc + m[i])
+            }
+
+            ///zeroes the unused lanes again (0 * inf is NaN)
+            #[inline]
+            fn clear_padding(mut self) -> Self {
+                for v in self.0.iter_mut().skip($dim) {
+                    *v = 0.0;
+                }
+                self
+            }
+        }
+
+        impl Coordinate for $name {
+            type Scalar = $t;
+            const DIM: usize = $dim;
+
+            fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+                $name(std::array::from_fn(
+                    |i| if i < $dim { val_fn(i) } else { 0.0 },
+                ))
+            }
+
+            fn val(&self, i: usize) -> Self::Scalar {
+                assert!(i < $dim, "component index out of range");
+                self.0[i]
+            }
+
+            fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+                assert!(i < $dim, "component index out of range");
+                &mut self.0[i]
+            }
+
+            #[inline]
+            fn add(&self, other: &Self) -> Self {
+                $name(lanes::$add(&self.0, &other.0))
+            }
+
+            #[inline]
+            fn sub(&self, other: &Self) -> Self {
+                $name(lanes::$sub(&self.0, &other.0))
+            }
+
+            #[inline]
+            fn mult(&self, k: Self::Scalar) -> Self {
+                $name(lanes::$mul(&self.0, &[k; 4])).clear_padding()
+            }
+
+            #[inline]
+            fn dot(&self, other: &Self) -> Self::Scalar {
+                Self::lane_sum(lanes::$mul(&self.0, &other.0))
+            }
+
+            #[inline]
+            fn square_length(&self) -> Self::Scalar {
+                self.dot(self)
+            }
+
+            #[inline]
+            fn square_distance(&self, other: &Self) -> Self::Scalar {
+                self.sub(other).square_length()
+            }
+        }
+    };
+}
+
+simd_coord!(Coord2f, f32, 2, 16, add_f32, sub_f32, mul_f32);
+simd_coord!(Coord3f, f32, 3, 16, add_f32, sub_f32, mul_f32);
+simd_coord!(Coord4f, f32, 4, 16, add_f32, sub_f32, mul_f32);
+simd_coord!(Coord2d, f64, 2, 32, add_f64, sub_f64, mul_f64);
+simd_coord!(Coord3d, f64, 3, 32, add_f64, sub_f64, mul_f64);
+simd_coord!(Coord4d, f64, 4, 32, add_f64, sub_f64, mul_f64);
+
+impl Coordinate3 for Coord3f {}
+impl Coordinate3 for Coord3d {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift;
+    use crate::Coord;
+
+    #[test]
+    fn test_simd_matches_generic() {
+        let mut rng = XorShift::new(5);
+        let mut r = || rng.next_f64() * 200. - 100.;
+        for _ in 0..100 {
+            let (a, b, k) = ([r(), r(), r()], [r(), r(), r()], r());
+            let (sa, sb) = (Coord3d::new(a), Coord3d::new(b));
+            let (ga, gb) = (Coord(a), Coord(b));
+            assert_eq!(sa.add(&sb).to_vec(), ga.add(&gb).to_vec());
+            assert_eq!(sa.sub(&sb).to_vec(), ga.sub(&gb).to_vec());
+            assert_eq!(sa.mult(k).to_vec(), ga.mult(k).to_vec());
+            assert_eq!(sa.dot(&sb), ga.dot(&gb));
+            assert_eq!(sa.square_distance(&sb), ga.square_distance(&gb));
+            assert_eq!(sa.cross(&sb).to_vec(), ga.cross(&gb).to_vec());
+
+            let (fa, fb) = ([r() as f32, r() as f32], [r() as f32, r() as f32]);
+            let (sa, sb) = (Coord2f::new(fa), Coord2f::new(fb));
+            assert_eq!(
+                sa.square_distance(&sb),
+                Coord(fa).square_distance(&Coord(fb))
+            );
+            assert_eq!(sa.add(&sb).to_vec(), Coord(fa).add(&Coord(fb)).to_vec());
+        }
+    }
+
+    #[test]
+    fn test_simd_coords() {
+        let a = Coord4f::new([1., 2., 3., 4.]);
+        assert_eq!(a.square_length(), 30.);
+        assert_eq!(Coord4d::DIM, 4);
+        assert_eq!(std::mem::align_of::<Coord4d>(), 32);
+        let b = Coord2d::new([3., 4.]);
+        assert_eq!(b.mult(2.), Coord2d::new([6., 8.]));
+        //unused lanes stay zero
+        let inf = Coord3f::new([1., -2., 3.]).mult(f32::INFINITY);
+        assert_eq!(inf.square_length(), f32::INFINITY);
+        let mut c = Coord3d::new([0., 0., 0.]);
+        *c.val_mut(2) = 2.;
+        assert_eq!((c.val(2), c.square_length()), (2., 4.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_padding_lane_out_of_range() {
+        //lane 3 exists but is not a component
+        Coord3d::new([1., 2., 3.]).val(3);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Coordinate;
+use num_traits::{Float, One, Zero};
+
+///square distance from pt to the segment [a, b]
+pub(crate) fn square_segment_distance<C>(pt: &C, a: &C, b: &C) -> C::Scalar
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let ab = b.sub(a);
+    let ap = pt.sub(a);
+    let len2 = ab.square_length();
+    if len2 == C::Scalar::zero() {
+        return ap.square_length();
+    }
+    let mut dot = C::Scalar::zero();
+    for i in 0..C::DIM {
+        dot = dot + ab.val(i) * ap.val(i);
+    }
+    let t = (dot / len2).max(C::Scalar::zero()).min(C::Scalar::one());
+    pt.square_distance(&a.add(&ab.mult(t)))
+}
+
+///indices (ascending) of points retained by Douglas-Peucker simplification,
+///for carrying per-point attributes along
+pub fn douglas_peucker_indices<C>(points: &[C], tolerance: C::Scalar) -> Vec<usize>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let n = points.len();
+    if n < 3 {
+        return (0..n).collect();
+    }
+    let tol2 = tolerance * tolerance;
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+
+    let mut stack = vec![(0, n - 1)];
+    while let Some((i, j)) = stack.pop() {
+        let mut index = i;
+        let mut dmax = C::Scalar::zero();
+        for k in (i + 1)..j {
+            let d = square_segment_distance(&points[k], &points[i], &points[j]);
+            if d > dmax {
+                index = k;
+                dmax = d;
+            }
+        }
+        if dmax > tol2 {
+            keep[index] = true;
+            stack.push((i, index));
+            stack.push((index, j));
+        }
+    }
+    (0..n).filter(|&i| keep[i]).collect()
+}
+
+///Douglas-Peucker simplification - keeps end points and drops points
+///within tolerance of the simplified line
+pub fn douglas_peucker<C>(points: &[C], tolerance: C::Scalar) -> Vec<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    douglas_peucker_indices(points, tolerance)
+        .into_iter()
+        .map(|i| points[i])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_douglas_peucker() {
+        let pts = [
+            Pt { x: 0., y: 0. },
+            Pt { x: 1., y: 0.1 },
+            Pt { x: 2., y: -0.1 },
+            Pt { x: 3., y: 5. },
+            Pt { x: 4., y: 6. },
+            Pt { x: 5., y: 7. },
+        ];
+        assert_eq!(
+            douglas_peucker(&pts, 0.5),
+            vec![pts[0], pts[2], pts[3], pts[5]]
+        );
+        assert_eq!(douglas_peucker_indices(&pts, 0.5), vec![0, 2, 3, 5]);
+        assert_eq!(douglas_peucker(&pts, 100.), vec![pts[0], pts[5]]);
+        assert_eq!(douglas_peucker_indices(&pts, 100.), vec![0, 5]);
+        assert_eq!(douglas_peucker(&pts[..2], 100.), pts[..2].to_vec());
+        assert_eq!(square_segment_distance(&pts[3], &pts[0], &pts[0]), 34.);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::Coordinate;
+use num_traits::{Float, ToPrimitive, Zero};
+use std::collections::HashMap;
+
+///uniform grid hash of points for fixed radius neighbour queries;
+///queries are fastest when the radius is close to the cell size
+#[derive(Clone, Debug)]
+pub struct SpatialHash<C: Coordinate> {
+    cell_size: C::Scalar,
+    points: Vec<C>,
+    cells: HashMap<Vec<i64>, Vec<usize>>,
+    //points without a grid cell (non-finite or beyond the i64 cell range)
+    unhashed: Vec<usize>,
+}
+
+impl<C> SpatialHash<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///empty hash with the given cell size
+    pub fn new(cell_size: C::Scalar) -> Self {
+        SpatialHash {
+            cell_size,
+            points: Vec::new(),
+            cells: HashMap::new(),
+            unhashed: Vec::new(),
+        }
+    }
+
+    ///hash of points, indices follow the slice order
+    pub fn from_points(points: &[C], cell_size: C::Scalar) -> Self {
+        let mut hash = SpatialHash::new(cell_size);
+        for pt in points {
+            hash.insert(*pt);
+        }
+        hash
+    }
+
+    fn cell_of(&self, pt: &C) -> Option<Vec<i64>> {
+        (0..C::DIM).map(|i| self.axis_cell(pt.val(i))).collect()
+    }
+
+    fn axis_cell(&self, v: C::Scalar) -> Option<i64> {
+        (v / self.cell_size).floor().to_i64()
+    }
+
+    ///adds a point, returns its index; non-finite points keep their index but
+    ///are never within radius of a query
+    pub fn insert(&mut self, pt: C) -> usize {
+        let index = self.points.len();
+        match self.cell_of(&pt) {
+            Some(key) => self.cells.entry(key).or_default().push(index),
+            None => self.unhashed.push(index),
+        }
+        self.points.push(pt);
+        index
+    }
+    ///number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    ///checks if empty
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    ///point at index
+    pub fn point(&self, index: usize) -> &C {
+        &self.points[index]
+    }
+
+    ///all points in insertion order
+    pub fn points(&self) -> &[C] {
+        &self.points
+    }
+
+    ///indices of points within radius of query (inclusive); boxes spanning more
+    ///cells than there are points, including an infinite radius, scan all points
+    pub fn within_radius(&self, query: &C, radius: C::Scalar) -> Vec<usize> {
+        let mut found = Vec::new();
+        if self.points.is_empty() || radius.is_nan() || radius < C::Scalar::zero() {
+            return found;
+        }
+        let r2 = radius * radius;
+        let within = |k: &usize| self.points[*k].square_distance(query) <= r2;
+        let cell_range = |i: usize| {
+            let lo = ((query.val(i) - radius) / self.cell_size).floor();
+            let hi = ((query.val(i) + radius) / self.cell_size).floor();
+            (lo, hi)
+        };
+        let count = (0..C::DIM).fold(C::Scalar::one(), |acc, i| {
+            let (lo, hi) = cell_range(i);
+            acc * (hi - lo + C::Scalar::one())
+        });
+        let bounds: Option<Vec<(i64, i64)>> = (0..C::DIM)
+            .map(|i| {
+                let (lo, hi) = cell_range(i);
+                Some((lo.to_i64()?, hi.to_i64()?))
+            })
+            .collect();
+        let bounds = match bounds {
+            Some(bounds) if count <= num_traits::cast(self.points.len()).unwrap() => bounds,
+            _ => {
+                found.extend((0..self.points.len()).filter(within));
+                return found;
+            }
+        };
+        let lo: Vec<i64> = bounds.iter().map(|b| b.0).collect();
+        let hi: Vec<i64> = bounds.iter().map(|b| b.1).collect();
+        //visit every cell of the query box in odometer order
+        let mut key = lo.clone();
+        loop {
+            if let Some(indices) = self.cells.get(&key) {
+                found.extend(indices.iter().filter(|k| within(k)));
+            }
+            let mut axis = 0;
+            while axis < C::DIM && key[axis] == hi[axis] {
+                key[axis] = lo[axis];
+                axis += 1;
+            }
+            if axis == C::DIM {
+                break;
+            }
+            key[axis] += 1;
+        }
+        found.extend(self.unhashed.iter().filter(|k| within(k)));
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_spatial_hash() {
+        let pts: Vec<_> = (0..100)
+            .map(|i| Pt {
+                x: (i % 10) as f64 - 4.5,
+                y: (i / 10) as f64 * 0.5,
+            })
+            .collect();
+        let hash = SpatialHash::from_points(&pts, 1.);
+        assert_eq!(hash.len(), 100);
+
+        let q = Pt { x: 0.2, y: 2.1 };
+        for &r in [0.3, 1., 2.7].iter() {
+            let mut found = hash.within_radius(&q, r);
+            found.sort_unstable();
+            let expect: Vec<_> = (0..pts.len())
+                .filter(|&i| pts[i].square_distance(&q) <= r * r)
+                .collect();
+            assert_eq!(found, expect);
+        }
+        assert!(hash.within_radius(&q, -1.).is_empty());
+
+        //huge and infinite radii scan the points instead of the cells
+        for &r in &[1e12, f64::MAX, f64::INFINITY] {
+            assert_eq!(hash.within_radius(&q, r).len(), 100);
+        }
+
+        //non-finite points keep their index but never match
+        let mut hash = SpatialHash::from_points(&pts[..3], 1.);
+        assert_eq!(hash.insert(Pt { x: f64::NAN, y: 0. }), 3);
+        assert_eq!(hash.insert(Pt { x: 1e300, y: 0. }), 4);
+        assert_eq!(hash.within_radius(&Pt { x: -4.5, y: 0. }, 0.5), vec![0]);
+        let mut found = hash.within_radius(&Pt { x: -4.5, y: 0. }, 2.);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2]);
+        let mut found = hash.within_radius(&Pt { x: -4.5, y: 0. }, f64::INFINITY);
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1, 2, 4]);
+        assert_eq!(hash.within_radius(&Pt { x: 1e300, y: 0. }, 1.), vec![4]);
+        assert!(SpatialHash::<Pt<f64>>::new(1.)
+            .within_radius(&q, 1.)
+            .is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+//!per-axis distribution summaries of point sets
+use crate::iter::CoordIter;
+use crate::Coordinate;
+use num_traits::{Float, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+
+///equal width histogram of values in [min, max]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Histogram<T> {
+    pub min: T,
+    pub max: T,
+    pub counts: Vec<usize>,
+}
+
+impl<T: Float> Histogram<T> {
+    ///width of each bin
+    pub fn bin_width(&self) -> T {
+        (self.max - self.min) / num_traits::cast(self.counts.len()).unwrap()
+    }
+}
+
+///sorted non-NaN values on an axis
+fn axis_values<C>(points: &[C], axis: usize) -> Vec<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let mut vals: Vec<_> = points
+        .iter()
+        .map(|p| p.val(axis))
+        .filter(|v| !v.is_nan())
+        .collect();
+    vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    vals
+}
+
+///quantiles (each q in [0, 1]) of values on an axis, linearly interpolated
+///between order statistics; NaN values are ignored, empty if there are none
+pub fn quantiles<C>(points: &[C], axis: usize, qs: &[C::Scalar]) -> Vec<C::Scalar>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let vals = axis_values(points, axis);
+    if vals.is_empty() {
+        return Vec::new();
+    }
+    let last: C::Scalar = num_traits::cast(vals.len() - 1).unwrap();
+    qs.iter()
+        .map(|&q| {
+            let h = q.max(C::Scalar::zero()).min(C::Scalar::one()) * last;
+            let lo = h.floor();
+            let i = lo.to_usize().unwrap_or(0);
+            let j = (i + 1).min(vals.len() - 1);
+            vals[i] + (h - lo) * (vals[j] - vals[i])
+        })
+        .collect()
+}
+
+///histogram of values on an axis over their range, the last bin includes the max;
+///None if there are no (non-NaN) values or no bins
+pub fn histogram<C>(points: &[C], axis: usize, bins: usize) -> Option<Histogram<C::Scalar>>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let vals = axis_values(points, axis);
+    if vals.is_empty() || bins == 0 {
+        return None;
+    }
+    let (min, max) = (vals[0], vals[vals.len() - 1]);
+    let mut hist = Histogram {
+        min,
+        max,
+        counts: vec![0; bins],
+    };
+    let width = hist.bin_width();
+    for v in vals {
+        let bin = if width > C::Scalar::zero() {
+            ((v - min) / width).to_usize().unwrap_or(0).min(bins - 1)
+        } else {
+            0
+        };
+        hist.counts[bin] += 1;
+    }
+    Some(hist)
+}
+
+///positional error introduced by storing coordinates at a lower precision
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DowncastStats<C> {
+    ///subtracted before the downcast, add it back when reading (origin if not recentered)
+    pub offset: C,
+    ///largest euclidean error
+    pub max_error: f64,
+    ///mean euclidean error
+    pub mean_error: f64,
+}
+
+///f64 to f32 coordinates with the error introduced - optionally recentered about the
+///bounds center first, which keeps precision for data far from the origin
+pub fn downcast_report<C, D>(points: &[C], recenter: bool) -> (Vec<D>, DowncastStats<C>)
+where
+    C: Coordinate<Scalar = f64>,
+    D: Coordinate<Scalar = f32>,
+{
+    let offset = match points.iter().copied().filter_finite().bounds() {
+        Some(b) if recenter => b.min.add(&b.max).mult(0.5),
+        _ => C::new_origin(),
+    };
+    let mut stats = DowncastStats {
+        offset,
+        max_error: 0.,
+        mean_error: 0.,
+    };
+    let out: Vec<D> = points
+        .iter()
+        .map(|p| {
+            let local = p.sub(&offset);
+            let q = D::gen(|i| local.val(i) as f32);
+            let back = C::gen(|i| q.val(i) as f64).add(&offset);
+            let err = back.square_distance(p).sqrt();
+            stats.max_error = stats.max_error.max(err);
+            stats.mean_error += err;
+            q
+        })
+        .collect();
+    if !out.is_empty() {
+        stats.mean_error /= out.len() as f64;
+    }
+    (out, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_quantiles() {
+        let pts: Vec<_> = [3., 1., 4., 1., 5., f64::NAN, 9., 2.]
+            .iter()
+            .map(|&x| Pt { x, y: -x })
+            .collect();
+        assert_eq!(quantiles(&pts, 0, &[0., 0.5, 1.]), vec![1., 3., 9.]);
+        assert_eq!(quantiles(&pts, 0, &[0.25]), vec![1.5]);
+        assert_eq!(quantiles(&pts, 1, &[0., 2.]), vec![-9., -1.]);
+        assert!(quantiles::<Pt<f64>>(&[], 0, &[0.5]).is_empty());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let pts: Vec<_> = (0..=10).map(|i| Pt { x: i as f64, y: 1. }).collect();
+        let hist = histogram(&pts, 0, 5).unwrap();
+        assert_eq!((hist.min, hist.max, hist.bin_width()), (0., 10., 2.));
+        assert_eq!(hist.counts, vec![2, 2, 2, 2, 3]);
+
+        let flat = histogram(&pts, 1, 3).unwrap();
+        assert_eq!(flat.counts, vec![11, 0, 0]);
+        assert_eq!(flat.bin_width(), 0.);
+        assert!(histogram(&pts, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_downcast_report() {
+        //utm-like eastings / northings with millimeter detail
+        let pts: Vec<_> = (0..100)
+            .map(|i| Pt {
+                x: 500_000. + i as f64 * 0.001,
+                y: 4_000_000.123 + i as f64,
+            })
+            .collect();
+        let (raw, raw_stats): (Vec<Pt<f32>>, _) = downcast_report(&pts, false);
+        let (local, stats): (Vec<Pt<f32>>, _) = downcast_report(&pts, true);
+        assert_eq!((raw.len(), local.len()), (100, 100));
+        assert_eq!(raw_stats.offset, Pt { x: 0., y: 0. });
+        assert!(raw_stats.max_error > 0.01);
+        assert!(stats.max_error < 1e-4 && stats.mean_error <= stats.max_error);
+        let back = Pt {
+            x: local[7].x as f64,
+            y: local[7].y as f64,
+        }
+        .add(&stats.offset);
+        assert!(back.square_distance(&pts[7]).sqrt() <= stats.max_error);
+
+        let (none, empty): (Vec<Pt<f32>>, _) = downcast_report::<Pt<f64>, _>(&[], true);
+        assert!(none.is_empty() && empty.max_error == 0.);
+    }
+}
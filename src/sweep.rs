@@ -0,0 +1,341 @@
+//!Bentley-Ottmann sweep reporting all intersections among a set of segments
+//!on the first two dimensions
+use crate::algorithms::segment_intersection;
+use crate::predicates::orient2d;
+use crate::Coordinate;
+use num_traits::Float;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+///sweep event - a segment end point or a scheduled crossing of two segments
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Kind {
+    Start(usize),
+    End(usize),
+    Cross(usize, usize),
+}
+
+struct Event {
+    at: [f64; 2],
+    kind: Kind,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        lex(self.at, other.at)
+    }
+}
+
+///sweep order - x then y
+fn lex(a: [f64; 2], b: [f64; 2]) -> Ordering {
+    a[0].partial_cmp(&b[0])
+        .unwrap_or(Ordering::Equal)
+        .then(a[1].partial_cmp(&b[1]).unwrap_or(Ordering::Equal))
+}
+
+struct Sweep<'a, C: Coordinate> {
+    segments: &'a [(C, C)],
+    //end points in sweep order, as f64
+    ends: Vec<([f64; 2], [f64; 2])>,
+    queue: BinaryHeap<Reverse<Event>>,
+    //segments crossing the sweep line ordered bottom to top
+    status: Vec<usize>,
+    scheduled: HashSet<(usize, usize)>,
+    reported: HashSet<(usize, usize)>,
+    found: Vec<(usize, usize, C)>,
+}
+
+///all pairs of intersecting segments as (i, j, point) with i < j, sorted by (i, j);
+///touches and collinear overlaps are included, the point is that of
+///`segment_intersection` for the pair. Segments with non finite end points are ignored.
+///
+///End points are compared with exact orientation predicates; computed crossing
+///points that round to the left of the sweep are processed at the sweep position.
+pub fn intersections<C>(segments: &[(C, C)]) -> Vec<(usize, usize, C)>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let xy = |c: &C| [to_f64(c.val(0)), to_f64(c.val(1))];
+    let ends = segments
+        .iter()
+        .map(|(a, b)| {
+            let (a, b) = (xy(a), xy(b));
+            if lex(a, b) == Ordering::Greater {
+                (b, a)
+            } else {
+                (a, b)
+            }
+        })
+        .collect();
+    let mut sweep = Sweep {
+        segments,
+        ends,
+        queue: BinaryHeap::new(),
+        status: Vec::new(),
+        scheduled: HashSet::new(),
+        reported: HashSet::new(),
+        found: Vec::new(),
+    };
+    for (s, &(a, b)) in sweep.ends.iter().enumerate() {
+        if a.iter().chain(&b).all(|v| v.is_finite()) {
+            sweep.queue.push(Reverse(Event {
+                at: a,
+                kind: Kind::Start(s),
+            }));
+            sweep.queue.push(Reverse(Event {
+                at: b,
+                kind: Kind::End(s),
+            }));
+        }
+    }
+    while let Some(Reverse(event)) = sweep.queue.pop() {
+        let mut group = vec![event.kind];
+        while let Some(Reverse(next)) = sweep.queue.peek() {
+            if lex(next.at, event.at) != Ordering::Equal {
+                break;
+            }
+            group.push(next.kind);
+            sweep.queue.pop();
+        }
+        sweep.handle(event.at, &group);
+    }
+    let mut found = sweep.found;
+    found.sort_by_key(|f| (f.0, f.1));
+    found
+}
+
+fn to_f64<T: Float>(v: T) -> f64 {
+    v.to_f64().unwrap_or(f64::NAN)
+}
+
+impl<'a, C> Sweep<'a, C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///segment s passes below p
+    fn below(&self, s: usize, p: [f64; 2]) -> bool {
+        let (a, b) = self.ends[s];
+        if a[0] == b[0] {
+            return b[1] < p[1];
+        }
+        orient2d(&a, &b, &p) > 0.
+    }
+
+    ///segment s passes through p, up to the rounding of computed crossing points
+    fn contains(&self, s: usize, p: [f64; 2]) -> bool {
+        let (a, b) = self.ends[s];
+        if lex(a, p) == Ordering::Greater || lex(p, b) == Ordering::Greater {
+            return false;
+        }
+        let len = (b[0] - a[0]).hypot(b[1] - a[1]);
+        let scale = [a, b, p]
+            .iter()
+            .fold(0f64, |m, q| m.max(q[0].abs()).max(q[1].abs()));
+        orient2d(&a, &b, &p).abs() <= 64. * f64::EPSILON * scale * len
+    }
+
+    ///segments through p, taking the place of the sweep line at p
+    fn handle(&mut self, p: [f64; 2], group: &[Kind]) {
+        let mut upper = Vec::new();
+        let mut involved = Vec::new();
+        for kind in group {
+            match *kind {
+                Kind::Start(s) => upper.push(s),
+                Kind::End(s) => involved.push(s),
+                Kind::Cross(i, j) => involved.extend([i, j]),
+            }
+        }
+        //the run of status segments through p - widened over the crossing
+        //segments whose rounded crossing point may fall to either side
+        let members = involved.clone();
+        let through = |s: &usize| members.contains(s) || self.contains(*s, p);
+        let lo = self.status.partition_point(|&s| self.below(s, p));
+        let above = self.status[lo..].iter().take_while(|s| through(s));
+        let below = self.status[..lo].iter().rev().take_while(|s| through(s));
+        let run: Vec<usize> = above.chain(below).copied().collect();
+        involved.extend(run);
+        involved.extend(&upper);
+        involved.sort_unstable();
+        involved.dedup();
+
+        for (k, &i) in involved.iter().enumerate() {
+            for &j in &involved[k + 1..] {
+                self.report(i, j);
+            }
+        }
+
+        //segments continuing to the right of p, reinserted in slope order
+        let ends = &self.ends;
+        let continuing: Vec<usize> = {
+            let mut cont: Vec<usize> = involved
+                .iter()
+                .copied()
+                .filter(|&s| lex(ends[s].1, p) == Ordering::Greater)
+                .collect();
+            cont.sort_by(|&a, &b| slope_order(ends[a], ends[b]).then(a.cmp(&b)));
+            cont
+        };
+        self.status.retain(|s| involved.binary_search(s).is_err());
+        let lo = self.status.partition_point(|&s| self.below(s, p));
+        let m = continuing.len();
+        self.status.splice(lo..lo, continuing);
+
+        if m == 0 {
+            if lo > 0 && lo < self.status.len() {
+                self.check(self.status[lo - 1], self.status[lo], p);
+            }
+        } else {
+            if lo > 0 {
+                self.check(self.status[lo - 1], self.status[lo], p);
+            }
+            if lo + m < self.status.len() {
+                self.check(self.status[lo + m - 1], self.status[lo + m], p);
+            }
+        }
+    }
+
+    ///schedules the crossing of neighbours a and b - no earlier than p so
+    ///rounded crossing points cannot move backwards
+    fn check(&mut self, a: usize, b: usize, p: [f64; 2]) {
+        let key = (a.min(b), a.max(b));
+        if self.scheduled.contains(&key) || self.reported.contains(&key) {
+            return;
+        }
+        let ((a0, a1), (b0, b1)) = (&self.segments[key.0], &self.segments[key.1]);
+        if let Some(q) = segment_intersection(a0, a1, b0, b1) {
+            //the exact crossing lies in both bounding boxes, e.g. on the x of a vertical
+            let (ea, eb) = (self.ends[key.0], self.ends[key.1]);
+            let q: [f64; 2] = std::array::from_fn(|k| {
+                let lo = ea.0[k].min(ea.1[k]).max(eb.0[k].min(eb.1[k]));
+                let hi = ea.0[k].max(ea.1[k]).min(eb.0[k].max(eb.1[k]));
+                to_f64(q.val(k)).max(lo).min(hi)
+            });
+            let at = if lex(q, p) == Ordering::Less { p } else { q };
+            self.scheduled.insert(key);
+            self.queue.push(Reverse(Event {
+                at,
+                kind: Kind::Cross(key.0, key.1),
+            }));
+        }
+    }
+
+    fn report(&mut self, i: usize, j: usize) {
+        if !self.reported.insert((i, j)) {
+            return;
+        }
+        let ((a0, a1), (b0, b1)) = (&self.segments[i], &self.segments[j]);
+        if let Some(pt) = segment_intersection(a0, a1, b0, b1) {
+            self.found.push((i, j, pt));
+        }
+    }
+}
+
+///bottom to top order of segments leaving a common point - by direction,
+///vertical segments last
+fn slope_order(a: ([f64; 2], [f64; 2]), b: ([f64; 2], [f64; 2])) -> Ordering {
+    let da = [a.1[0] - a.0[0], a.1[1] - a.0[1]];
+    let db = [b.1[0] - b.0[0], b.1[1] - b.0[1]];
+    let turn = orient2d(&[0., 0.], &da, &db);
+    if turn > 0. {
+        Ordering::Less
+    } else if turn < 0. {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::find_self_intersections;
+    use crate::rng::XorShift;
+    use crate::tests::Pt;
+
+    fn brute(segments: &[(Pt<f64>, Pt<f64>)]) -> Vec<(usize, usize, Pt<f64>)> {
+        let mut found = Vec::new();
+        for i in 0..segments.len() {
+            for j in (i + 1)..segments.len() {
+                let ((a0, a1), (b0, b1)) = (&segments[i], &segments[j]);
+                if let Some(pt) = segment_intersection(a0, a1, b0, b1) {
+                    found.push((i, j, pt));
+                }
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn test_sweep_intersections() {
+        let p = |x: f64, y: f64| Pt { x, y };
+        let segments = [
+            (p(0., 0.), p(4., 4.)),
+            (p(0., 4.), p(4., 0.)),
+            (p(2., -1.), p(2., 5.)),
+            (p(5., 5.), p(6., 6.)),
+            (p(3., 3.), p(5., 5.)),
+            (p(-1., 2.), p(1., 2.)),
+        ];
+        assert_eq!(
+            intersections(&segments),
+            vec![
+                (0, 1, p(2., 2.)),
+                (0, 2, p(2., 2.)),
+                (0, 4, p(3., 3.)),
+                (1, 2, p(2., 2.)),
+                (3, 4, p(5., 5.)),
+            ]
+        );
+        assert!(intersections::<Pt<f64>>(&[]).is_empty());
+        let nan = [(p(f64::NAN, 0.), p(1., 1.)), (p(0., 1.), p(1., 0.))];
+        assert!(intersections(&nan).is_empty());
+
+        //edges of a bowtie ring agree with find_self_intersections
+        let ring = [p(0., 0.), p(2., 2.), p(2., 0.), p(0., 2.)];
+        let edges: Vec<_> = (0..4).map(|i| (ring[i], ring[(i + 1) % 4])).collect();
+        let crossings: Vec<_> = intersections(&edges)
+            .into_iter()
+            .filter(|&(i, j, _)| j != i + 1 && !(i == 0 && j == 3))
+            .collect();
+        assert_eq!(crossings, find_self_intersections(&ring));
+    }
+
+    #[test]
+    fn test_sweep_matches_brute_force() {
+        let mut rng = XorShift::new(7);
+        let mut coord = || (rng.next_f64() * 20.).round() / 2.;
+        for _ in 0..20 {
+            let segments: Vec<_> = (0..40)
+                .map(|_| {
+                    (
+                        Pt {
+                            x: coord(),
+                            y: coord(),
+                        },
+                        Pt {
+                            x: coord(),
+                            y: coord(),
+                        },
+                    )
+                })
+                .collect();
+            assert_eq!(intersections(&segments), brute(&segments));
+        }
+    }
+}
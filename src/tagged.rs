@@ -0,0 +1,100 @@
+use crate::Coordinate;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+///coordinate tagged with a space marker (e.g. `struct Screen;`, `struct World;`);
+///zero-cost wrapper - points in different spaces are distinct types
+#[repr(transparent)]
+pub struct Tagged<C: Coordinate, S> {
+    coord: C,
+    space: PhantomData<S>,
+}
+
+impl<C: Coordinate, S> Tagged<C, S> {
+    ///tags coordinate with space S
+    pub fn new(coord: C) -> Self {
+        Tagged {
+            coord,
+            space: PhantomData,
+        }
+    }
+
+    ///untagged coordinate
+    pub fn into_inner(self) -> C {
+        self.coord
+    }
+
+    ///reinterprets the same values as belonging to space T
+    pub fn retag<T>(self) -> Tagged<C, T> {
+        Tagged::new(self.coord)
+    }
+
+    ///maps coordinate from space S into space T
+    pub fn transform_into<T>(self, transform: impl Fn(&C) -> C) -> Tagged<C, T> {
+        Tagged::new(transform(&self.coord))
+    }
+}
+
+impl<C: Coordinate, S> Clone for Tagged<C, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: Coordinate, S> Copy for Tagged<C, S> {}
+
+impl<C: Coordinate, S> PartialEq for Tagged<C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.coord == other.coord
+    }
+}
+
+impl<C: Coordinate, S> Debug for Tagged<C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} in {}", self.coord, std::any::type_name::<S>())
+    }
+}
+
+impl<C: Coordinate, S> Coordinate for Tagged<C, S> {
+    type Scalar = C::Scalar;
+    const DIM: usize = C::DIM;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        Tagged::new(C::gen(val_fn))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        self.coord.val(i)
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        self.coord.val_mut(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    struct World;
+    struct Screen;
+
+    #[test]
+    fn test_tagged() {
+        let w: Tagged<Pt<i32>, World> = Tagged::new(Pt { x: 3, y: 4 });
+        let v = Tagged::<_, World>::new(Pt { x: 1, y: 1 });
+        assert_eq!(w.add(&v).into_inner(), Pt { x: 4, y: 5 });
+        assert_eq!(w.square_length(), 25);
+
+        let s: Tagged<Pt<i32>, Screen> = w.transform_into(|p| p.mult(2));
+        assert_eq!(s.into_inner(), Pt { x: 6, y: 8 });
+        let r: Tagged<Pt<i32>, Screen> = w.retag();
+        assert_eq!(r.into_inner(), w.into_inner());
+        assert!(format!("{:?}", r).ends_with("Screen"));
+        assert_eq!(
+            std::mem::size_of::<Tagged<Pt<i32>, World>>(),
+            std::mem::size_of::<Pt<i32>>()
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use crate::iter::CoordIter;
+use crate::{Coordinate, Coordinate3, CoordinateFloat};
+use num_traits::{Float, One, Zero};
+
+///affine transform x -> M x + t in DIM dimensions
+#[derive(Clone, PartialEq, Debug)]
+pub struct Transform<C: Coordinate> {
+    ///row major DIM x DIM linear part
+    matrix: Vec<C::Scalar>,
+    offset: C,
+}
+
+impl<C: Coordinate> Transform<C> {
+    ///transform from a row major DIM x DIM matrix and a translation
+    pub fn new(matrix: Vec<C::Scalar>, offset: C) -> Self {
+        assert_eq!(matrix.len(), C::DIM * C::DIM);
+        Transform { matrix, offset }
+    }
+
+    ///identity transform
+    pub fn identity() -> Self {
+        Self::scaling(C::new_from_value(C::Scalar::one()))
+    }
+
+    ///translation by offset
+    pub fn translation(offset: C) -> Self {
+        Transform {
+            offset,
+            ..Self::identity()
+        }
+    }
+
+    ///per axis scaling by factors
+    pub fn scaling(factors: C) -> Self {
+        let n = C::DIM;
+        let matrix = (0..n * n)
+            .map(|k| {
+                if k / n == k % n {
+                    factors.val(k / n)
+                } else {
+                    C::Scalar::zero()
+                }
+            })
+            .collect();
+        Transform {
+            matrix,
+            offset: C::new_origin(),
+        }
+    }
+
+    ///element of the linear part at row i, column j
+    pub fn matrix(&self, i: usize, j: usize) -> C::Scalar {
+        self.matrix[i * C::DIM + j]
+    }
+
+    ///translation part
+    pub fn offset(&self) -> &C {
+        &self.offset
+    }
+
+    ///transformed point
+    pub fn apply(&self, pt: &C) -> C {
+        C::gen(|i| {
+            (0..C::DIM).fold(self.offset.val(i), |acc, j| {
+                acc + self.matrix(i, j) * pt.val(j)
+            })
+        })
+    }
+
+    ///transformed points
+    pub fn apply_all(&self, pts: &[C]) -> Vec<C> {
+        pts.iter().map(|p| self.apply(p)).collect()
+    }
+
+    ///self followed by next
+    pub fn then(&self, next: &Self) -> Self {
+        let n = C::DIM;
+        let matrix = (0..n * n)
+            .map(|k| {
+                let (i, j) = (k / n, k % n);
+                (0..n).fold(C::Scalar::zero(), |acc, m| {
+                    acc + next.matrix(i, m) * self.matrix(m, j)
+                })
+            })
+            .collect();
+        Transform {
+            matrix,
+            offset: next.apply(&self.offset),
+        }
+    }
+}
+
+impl<C> Transform<C>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///counter clockwise rotation by angle (radians) in the plane of axes 0 and 1,
+    ///other axes are kept
+    pub fn rotation2d(angle: C::Scalar) -> Self {
+        let mut t = Self::identity();
+        if C::DIM >= 2 {
+            let (s, c) = angle.sin_cos();
+            let n = C::DIM;
+            t.matrix[0] = c;
+            t.matrix[1] = -s;
+            t.matrix[n] = s;
+            t.matrix[n + 1] = c;
+        }
+        t
+    }
+
+    ///inverse transform, None if the linear part is singular
+    pub fn inverse(&self) -> Option<Self> {
+        let n = C::DIM;
+        //gauss jordan with partial pivoting on [M | I]
+        let mut a = self.matrix.clone();
+        let mut inv = Self::identity().matrix;
+        for col in 0..n {
+            let pivot = (col..n).max_by(|&i, &j| {
+                let (x, y) = (a[i * n + col].abs(), a[j * n + col].abs());
+                x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+            let p = a[pivot * n + col];
+            if p == C::Scalar::zero() || !p.is_finite() {
+                return None;
+            }
+            for j in 0..n {
+                a.swap(col * n + j, pivot * n + j);
+                inv.swap(col * n + j, pivot * n + j);
+            }
+            let k = p.recip();
+            for j in 0..n {
+                a[col * n + j] = a[col * n + j] * k;
+                inv[col * n + j] = inv[col * n + j] * k;
+            }
+            for i in (0..n).filter(|&i| i != col) {
+                let f = a[i * n + col];
+                for j in 0..n {
+                    a[i * n + j] = a[i * n + j] - f * a[col * n + j];
+                    inv[i * n + j] = inv[i * n + j] - f * inv[col * n + j];
+                }
+            }
+        }
+        let linear = Transform {
+            matrix: inv,
+            offset: C::new_origin(),
+        };
+        let offset = C::new_origin().sub(&linear.apply(&self.offset));
+        Some(Transform { offset, ..linear })
+    }
+}
+
+impl<C> Transform<C>
+where
+    C: Coordinate3,
+    C::Scalar: Float,
+{
+    ///right handed rotation by angle (radians) about an axis through the origin,
+    ///None for a zero axis
+    pub fn rotation_about_axis(axis: &C, angle: C::Scalar) -> Option<Self> {
+        let u = axis.normalize()?;
+        let (s, c) = angle.sin_cos();
+        let t = C::Scalar::one() - c;
+        let (x, y, z) = (u.val(0), u.val(1), u.val(2));
+        let mut r = Self::identity();
+        let n = C::DIM;
+        let rows = [
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                r.matrix[i * n + j] = v;
+            }
+        }
+        Some(r)
+    }
+}
+
+///points centered on their bounds and uniformly scaled into [-1, 1]^DIM (the longest
+///axis spans it, aspect is kept) with the inverse transform back to the input
+pub fn normalize_to_unit<C>(points: &[C]) -> (Vec<C>, Transform<C>)
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    let bounds = match points.iter().copied().filter_finite().bounds() {
+        Some(b) => b,
+        None => return (points.to_vec(), Transform::identity()),
+    };
+    let two = C::Scalar::one() + C::Scalar::one();
+    let center = bounds.min.add(&bounds.max).mult(two.recip());
+    let half = bounds
+        .max
+        .sub(&bounds.min)
+        .fold(C::Scalar::zero(), |acc, v| acc.max(v / two));
+    let half = if half > C::Scalar::zero() {
+        half
+    } else {
+        C::Scalar::one()
+    };
+    let k = half.recip();
+    let out = points.iter().map(|p| p.sub(&center).mult(k)).collect();
+    let inverse = Transform::scaling(C::new_from_value(half)).then(&Transform::translation(center));
+    (out, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_transform() {
+        let t = Transform::scaling(Pt { x: 2., y: 3. })
+            .then(&Transform::translation(Pt { x: 1., y: -1. }));
+        assert_eq!(t.apply(&Pt { x: 1., y: 1. }), Pt { x: 3., y: 2. });
+        assert_eq!(
+            (t.matrix(0, 0), t.matrix(0, 1), *t.offset()),
+            (2., 0., Pt { x: 1., y: -1. })
+        );
+        let id = Transform::<Pt<i32>>::identity();
+        assert_eq!(id.apply(&Pt { x: 4, y: -5 }), Pt { x: 4, y: -5 });
+        let swap = Transform::new(vec![0, 1, 1, 0], Pt { x: 0, y: 0 });
+        assert_eq!(swap.then(&swap), id);
+    }
+
+    #[test]
+    fn test_rotation_and_inverse() {
+        let near = |a: &Pt<f64>, b: &Pt<f64>| a.square_distance(b) < 1e-24;
+        let r = Transform::rotation2d(std::f64::consts::FRAC_PI_2);
+        assert!(near(&r.apply(&Pt { x: 1., y: 0. }), &Pt { x: 0., y: 1. }));
+        let t = Transform::scaling(Pt { x: 2., y: 0.5 })
+            .then(&r)
+            .then(&Transform::translation(Pt { x: 3., y: -4. }));
+        let inv = t.inverse().unwrap();
+        let pts = [Pt { x: 1., y: 2. }, Pt { x: -7., y: 0.25 }];
+        for (p, q) in pts.iter().zip(t.apply_all(&pts)) {
+            assert!(near(&inv.apply(&q), p));
+        }
+        assert_eq!(Transform::scaling(Pt { x: 1., y: 0. }).inverse(), None);
+
+        let z = Transform::rotation_about_axis(&Coord([0., 0., 2.]), std::f64::consts::FRAC_PI_2)
+            .unwrap();
+        let p = z.apply(&Coord([1., 0., 5.]));
+        assert!(p.square_distance(&Coord([0., 1., 5.])) < 1e-24);
+        let spin =
+            Transform::rotation_about_axis(&Coord([1., 1., 1.]), 2. * std::f64::consts::FRAC_PI_3)
+                .unwrap();
+        assert!(
+            spin.apply(&Coord([1., 0., 0.]))
+                .square_distance(&Coord([0., 1., 0.]))
+                < 1e-24
+        );
+        assert!(
+            spin.inverse()
+                .unwrap()
+                .then(&spin)
+                .apply(&Coord([3., -2., 1.]))
+                .square_distance(&Coord([3., -2., 1.]))
+                < 1e-24
+        );
+        assert_eq!(
+            Transform::rotation_about_axis(&Coord([0., 0., 0.]), 1.),
+            None
+        );
+    }
+
+    #[test]
+    fn test_normalize_to_unit() {
+        let pts = [
+            Pt { x: 10., y: 100. },
+            Pt { x: 30., y: 110. },
+            Pt { x: 20., y: 90. },
+        ];
+        let (unit, inverse) = normalize_to_unit(&pts);
+        assert_eq!(unit[0], Pt { x: -1., y: 0. });
+        assert_eq!(unit[1], Pt { x: 1., y: 1. });
+        assert_eq!(unit[2], Pt { x: 0., y: -1. });
+        for (u, p) in unit.iter().zip(&pts) {
+            assert!(inverse.apply(u).square_distance(p) < 1e-24);
+        }
+        let (same, id) = normalize_to_unit(&[Pt { x: 5., y: 5. }]);
+        assert_eq!(
+            (same, id.apply(&Pt { x: 0., y: 0. })),
+            (vec![Pt { x: 0., y: 0. }], Pt { x: 5., y: 5. })
+        );
+    }
+}
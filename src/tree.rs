@@ -0,0 +1,649 @@
+use crate::{Bounds, Coordinate};
+use num_traits::{Float, One};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+///depth past which nodes stop splitting - keeps duplicate points from recursing
+const MAX_DEPTH: usize = 24;
+
+///region tree of points with values - a quadtree in 2D, an octree in 3D. Each node
+///holds up to capacity points before splitting into its 2^DIM `subdivide` children;
+///points outside the root bounds are rejected
+#[derive(Clone, Debug)]
+pub struct NTree<C: Coordinate, V> {
+    nodes: Vec<Node<C, V>>,
+    capacity: usize,
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<C: Coordinate, V> {
+    bounds: Bounds<C>,
+    depth: usize,
+    items: Vec<(C, V)>,
+    ///index of the first of the 2^DIM contiguous children
+    children: Option<usize>,
+}
+
+///child of bounds holding pt - the upper half on dimensions where pt is at or past
+///the center, matching the `subdivide` order
+fn child_index<C: Coordinate>(bounds: &Bounds<C>, pt: &C) -> usize {
+    let center = bounds.center();
+    (0..C::DIM)
+        .filter(|&k| pt.val(k) >= center.val(k))
+        .fold(0, |acc, k| acc | 1 << k)
+}
+
+impl<C: Coordinate, V> NTree<C, V> {
+    ///empty tree over bounds splitting nodes past capacity points
+    pub fn new(bounds: Bounds<C>, capacity: usize) -> Self {
+        NTree {
+            nodes: vec![Node {
+                bounds,
+                depth: 0,
+                items: Vec::new(),
+                children: None,
+            }],
+            capacity: capacity.max(1),
+            len: 0,
+        }
+    }
+
+    ///bounds of the root
+    pub fn bounds(&self) -> &Bounds<C> {
+        &self.nodes[0].bounds
+    }
+
+    ///number of points
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///checks if empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn leaf_of(&self, pt: &C) -> usize {
+        let mut n = 0;
+        while let Some(first) = self.nodes[n].children {
+            n = first + child_index(&self.nodes[n].bounds, pt);
+        }
+        n
+    }
+
+    ///adds pt with value, false if pt is outside the root bounds
+    pub fn insert(&mut self, pt: C, value: V) -> bool {
+        if !self.bounds().contains(&pt) {
+            return false;
+        }
+        let n = self.leaf_of(&pt);
+        self.nodes[n].items.push((pt, value));
+        self.len += 1;
+        self.split_if_full(n);
+        true
+    }
+
+    fn split_if_full(&mut self, n: usize) {
+        let node = &self.nodes[n];
+        if node.items.len() <= self.capacity || node.depth >= MAX_DEPTH {
+            return;
+        }
+        let (bounds, depth) = (node.bounds, node.depth + 1);
+        let first = self.nodes.len();
+        for b in bounds.subdivide() {
+            self.nodes.push(Node {
+                bounds: b,
+                depth,
+                items: Vec::new(),
+                children: None,
+            });
+        }
+        self.nodes[n].children = Some(first);
+        for (pt, value) in std::mem::take(&mut self.nodes[n].items) {
+            self.nodes[first + child_index(&bounds, &pt)]
+                .items
+                .push((pt, value));
+        }
+        for k in first..first + (1 << C::DIM) {
+            self.split_if_full(k);
+        }
+    }
+
+    ///removes a point equal to pt and returns its value, None if there is none;
+    ///emptied nodes are kept
+    pub fn remove(&mut self, pt: &C) -> Option<V> {
+        if !self.bounds().contains(pt) {
+            return None;
+        }
+        let n = self.leaf_of(pt);
+        let items = &mut self.nodes[n].items;
+        let i = items.iter().position(|(p, _)| p == pt)?;
+        self.len -= 1;
+        Some(items.swap_remove(i).1)
+    }
+
+    ///points inside or on the boundary of query
+    pub fn range(&self, query: &Bounds<C>) -> Vec<(&C, &V)> {
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.bounds.intersects(query) {
+                continue;
+            }
+            match node.children {
+                Some(first) => stack.extend(first..first + (1 << C::DIM)),
+                None => found.extend(
+                    node.items
+                        .iter()
+                        .filter(|(p, _)| query.contains(p))
+                        .map(|(p, v)| (p, v)),
+                ),
+            }
+        }
+        found
+    }
+
+    ///nearest point to query with its value and square distance, None if empty;
+    ///nodes are visited nearest first and skipped once further than the best
+    pub fn nearest(&self, query: &C) -> Option<(&C, &V, C::Scalar)> {
+        let target = Bounds::from_point(*query);
+        let mut best: Option<(&C, &V, C::Scalar)> = None;
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if let Some((_, _, d)) = best {
+                if node.bounds.min_square_distance(&target) > d {
+                    continue;
+                }
+            }
+            for (p, v) in &node.items {
+                let d = p.square_distance(query);
+                if best.map_or(true, |b| d < b.2) {
+                    best = Some((p, v, d));
+                }
+            }
+            if let Some(first) = node.children {
+                let mut kids: Vec<_> = (first..first + (1 << C::DIM))
+                    .map(|k| (self.nodes[k].bounds.min_square_distance(&target), k))
+                    .collect();
+                //nearest last so it is popped first
+                kids.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+                stack.extend(kids.into_iter().map(|(_, k)| k));
+            }
+        }
+        best
+    }
+
+    ///points with their values and square distances in order of increasing distance
+    ///from query - best first over a heap of nodes & points, so stopping early
+    ///skips the rest of the tree
+    pub fn nearest_iter(&self, query: C) -> NearestIter<'_, C, V> {
+        let mut heap = BinaryHeap::new();
+        heap.push(HeapEntry {
+            dist: self.nodes[0]
+                .bounds
+                .min_square_distance(&Bounds::from_point(query)),
+            node: 0,
+            item: None,
+        });
+        NearestIter {
+            tree: self,
+            query,
+            heap,
+        }
+    }
+
+    ///all points with their values
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &V)> + '_ {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.items.iter().map(|(p, v)| (p, v)))
+    }
+}
+
+///iterator of `NTree::nearest_iter`
+pub struct NearestIter<'a, C: Coordinate, V> {
+    tree: &'a NTree<C, V>,
+    query: C,
+    heap: BinaryHeap<HeapEntry<C::Scalar>>,
+}
+
+///node (item None) or point keyed by square distance, nearest on top of the heap;
+///points go before nodes at equal distance
+struct HeapEntry<T> {
+    dist: T,
+    node: usize,
+    item: Option<usize>,
+}
+
+impl<T: PartialOrd> Ord for HeapEntry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .partial_cmp(&self.dist)
+            .unwrap_or(Ordering::Equal)
+            .then(self.item.is_some().cmp(&other.item.is_some()))
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for HeapEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> PartialEq for HeapEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for HeapEntry<T> {}
+
+impl<'a, C: Coordinate, V> Iterator for NearestIter<'a, C, V> {
+    type Item = (&'a C, &'a V, C::Scalar);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let target = Bounds::from_point(self.query);
+        while let Some(entry) = self.heap.pop() {
+            let node = &self.tree.nodes[entry.node];
+            if let Some(i) = entry.item {
+                let (p, v) = &node.items[i];
+                return Some((p, v, entry.dist));
+            }
+            for (i, (p, _)) in node.items.iter().enumerate() {
+                self.heap.push(HeapEntry {
+                    dist: p.square_distance(&self.query),
+                    node: entry.node,
+                    item: Some(i),
+                });
+            }
+            if let Some(first) = node.children {
+                for k in first..first + (1 << C::DIM) {
+                    self.heap.push(HeapEntry {
+                        dist: self.tree.nodes[k].bounds.min_square_distance(&target),
+                        node: k,
+                        item: None,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+///loose region tree of objects with bounds for broad phase collision - cells are
+///inflated by a looseness factor about their center, so an object lives in the
+///deepest cell holding its center whose loose bounds enclose it; moving an object
+///within its cell is a plain update. Objects with a center outside the root stay
+///in the root
+#[derive(Clone, Debug)]
+pub struct LooseTree<C: Coordinate, V> {
+    nodes: Vec<LooseNode<C>>,
+    objects: Vec<Option<LooseObject<C, V>>>,
+    free: Vec<usize>,
+    looseness: C::Scalar,
+    max_depth: usize,
+}
+
+#[derive(Clone, Debug)]
+struct LooseNode<C: Coordinate> {
+    bounds: Bounds<C>,
+    loose: Bounds<C>,
+    depth: usize,
+    ids: Vec<usize>,
+    children: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct LooseObject<C: Coordinate, V> {
+    bounds: Bounds<C>,
+    value: V,
+    node: usize,
+}
+
+impl<C, V> LooseTree<C, V>
+where
+    C: Coordinate,
+    C::Scalar: Float,
+{
+    ///empty tree over bounds with cells inflated by looseness (at least 1, 2 is
+    ///usual) down to max_depth levels
+    pub fn new(bounds: Bounds<C>, looseness: C::Scalar, max_depth: usize) -> Self {
+        let looseness = looseness.max(C::Scalar::one());
+        let mut tree = LooseTree {
+            nodes: Vec::new(),
+            objects: Vec::new(),
+            free: Vec::new(),
+            looseness,
+            max_depth,
+        };
+        tree.push_node(bounds, 0);
+        tree
+    }
+
+    fn push_node(&mut self, bounds: Bounds<C>, depth: usize) {
+        let margin = bounds
+            .half_extents()
+            .mult(self.looseness - C::Scalar::one());
+        self.nodes.push(LooseNode {
+            bounds,
+            loose: bounds.inflate_per_axis(&margin),
+            depth,
+            ids: Vec::new(),
+            children: None,
+        });
+    }
+
+    ///number of objects
+    pub fn len(&self) -> usize {
+        self.objects.len() - self.free.len()
+    }
+
+    ///checks if empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///bounds and value of object id
+    pub fn get(&self, id: usize) -> Option<(&Bounds<C>, &V)> {
+        self.objects
+            .get(id)?
+            .as_ref()
+            .map(|o| (&o.bounds, &o.value))
+    }
+
+    ///node to hold bounds - the deepest level whose loose cells enclose an object
+    ///of that size, then the cell holding its center; nodes are made on the way
+    fn target_node(&mut self, bounds: &Bounds<C>) -> usize {
+        let center = bounds.center();
+        if !self.nodes[0].bounds.contains(&center) {
+            return 0;
+        }
+        let half = bounds.half_extents();
+        let slack = self.looseness - C::Scalar::one();
+        let two = C::Scalar::one() + C::Scalar::one();
+        let mut n = 0;
+        let mut cell_half = self.nodes[0].bounds.half_extents().mult(two.recip());
+        while self.nodes[n].depth < self.max_depth
+            && half.all_comp(&cell_half, |h, c| h <= c * slack)
+        {
+            let first = match self.nodes[n].children {
+                Some(first) => first,
+                None => {
+                    let first = self.nodes.len();
+                    let depth = self.nodes[n].depth + 1;
+                    for b in self.nodes[n].bounds.subdivide() {
+                        self.push_node(b, depth);
+                    }
+                    self.nodes[n].children = Some(first);
+                    first
+                }
+            };
+            n = first + child_index(&self.nodes[n].bounds, &center);
+            cell_half = cell_half.mult(two.recip());
+        }
+        n
+    }
+
+    ///adds an object with bounds and value, returns its id; ids of removed objects
+    ///are reused
+    pub fn insert(&mut self, bounds: Bounds<C>, value: V) -> usize {
+        let node = self.target_node(&bounds);
+        let object = LooseObject {
+            bounds,
+            value,
+            node,
+        };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.objects[id] = Some(object);
+                id
+            }
+            None => {
+                self.objects.push(Some(object));
+                self.objects.len() - 1
+            }
+        };
+        self.nodes[node].ids.push(id);
+        id
+    }
+
+    fn unlink(&mut self, id: usize, node: usize) {
+        let ids = &mut self.nodes[node].ids;
+        if let Some(i) = ids.iter().position(|&k| k == id) {
+            ids.swap_remove(i);
+        }
+    }
+
+    ///removes object id and returns its value
+    pub fn remove(&mut self, id: usize) -> Option<V> {
+        let object = self.objects.get_mut(id)?.take()?;
+        self.unlink(id, object.node);
+        self.free.push(id);
+        Some(object.value)
+    }
+
+    ///moves object id so its bounds are centered on center, false if there is no
+    ///such object; stays in place while the object remains in its cell
+    pub fn relocate(&mut self, id: usize, center: C) -> bool {
+        let (bounds, node) = match self.objects.get(id) {
+            Some(Some(o)) => (o.bounds, o.node),
+            _ => return false,
+        };
+        let moved = Bounds::from_center_half_extents(center, bounds.half_extents());
+        //below the root the depth only depends on the size, so the node holds on
+        //while the center stays in its cell
+        let stays = node != 0 && self.nodes[node].bounds.contains(&center);
+        let node = if stays {
+            node
+        } else {
+            self.unlink(id, node);
+            let target = self.target_node(&moved);
+            self.nodes[target].ids.push(id);
+            target
+        };
+        if let Some(o) = self.objects[id].as_mut() {
+            o.bounds = moved;
+            o.node = node;
+        }
+        true
+    }
+
+    ///ids of objects whose bounds intersect query
+    pub fn query(&self, query: &Bounds<C>) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            //the root also holds objects centered outside it
+            if n != 0 && !node.loose.intersects(query) {
+                continue;
+            }
+            found.extend(node.ids.iter().copied().filter(|&id| {
+                self.objects[id]
+                    .as_ref()
+                    .map_or(false, |o| o.bounds.intersects(query))
+            }));
+            if let Some(first) = node.children {
+                stack.extend(first..first + (1 << C::DIM));
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::XorShift;
+    use crate::tests::Pt;
+    use crate::Coord;
+
+    #[test]
+    fn test_ntree() {
+        let mut rng = XorShift::new(7);
+        let pts: Vec<_> = (0..500)
+            .map(|_| Pt {
+                x: rng.next_f64() * 100.,
+                y: rng.next_f64() * 50.,
+            })
+            .collect();
+        let bounds = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 100., y: 50. });
+        let mut tree = NTree::new(bounds, 8);
+        for (i, p) in pts.iter().enumerate() {
+            assert!(tree.insert(*p, i));
+        }
+        assert!(!tree.insert(Pt { x: -1., y: 0. }, 0));
+        assert_eq!(tree.len(), 500);
+        assert_eq!(tree.iter().count(), 500);
+
+        let query = Bounds::new(Pt { x: 20., y: 10. }, Pt { x: 45., y: 30. });
+        let mut found: Vec<_> = tree.range(&query).into_iter().map(|(_, &i)| i).collect();
+        found.sort_unstable();
+        let expect: Vec<_> = (0..500).filter(|&i| query.contains(&pts[i])).collect();
+        assert_eq!(found, expect);
+
+        for q in [Pt { x: 50., y: 25. }, Pt { x: -10., y: 70. }, pts[42]] {
+            let (p, &i, d) = tree.nearest(&q).unwrap();
+            let brute = pts
+                .iter()
+                .map(|p| p.square_distance(&q))
+                .fold(f64::INFINITY, f64::min);
+            assert_eq!((d, *p), (brute, pts[i]));
+        }
+
+        assert_eq!(tree.remove(&pts[42]), Some(42));
+        assert_eq!(tree.remove(&pts[42]), None);
+        assert_eq!(tree.len(), 499);
+        assert_ne!(tree.nearest(&pts[42]).unwrap().1, &42);
+    }
+
+    #[test]
+    fn test_nearest_iter() {
+        let mut rng = XorShift::new(11);
+        let bounds = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 10., y: 10. });
+        let mut tree = NTree::new(bounds, 4);
+        let pts: Vec<_> = (0..300)
+            .map(|_| Pt {
+                x: rng.next_f64() * 10.,
+                y: rng.next_f64() * 10.,
+            })
+            .collect();
+        for (i, p) in pts.iter().enumerate() {
+            tree.insert(*p, i);
+        }
+        let q = Pt { x: 3.3, y: 7.1 };
+        let mut brute: Vec<_> = pts.iter().map(|p| p.square_distance(&q)).collect();
+        brute.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let dists: Vec<_> = tree.nearest_iter(q).map(|(_, _, d)| d).collect();
+        assert_eq!(dists, brute);
+        let (p, &i, d) = tree.nearest_iter(q).next().unwrap();
+        assert_eq!((*p, d), (pts[i], brute[0]));
+        assert_eq!(tree.nearest(&q).map(|r| r.2), Some(brute[0]));
+        //outside the root bounds
+        let far = Pt { x: -5., y: 20. };
+        let first: Vec<_> = tree.nearest_iter(far).take(3).map(|r| r.2).collect();
+        assert!(first.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(tree.nearest(&far).map(|r| r.2), Some(first[0]));
+
+        let empty = NTree::<Pt<f64>, ()>::new(bounds, 4);
+        assert_eq!(empty.nearest_iter(q).count(), 0);
+    }
+
+    #[test]
+    fn test_ntree_duplicates_and_octree() {
+        //coincident points stop splitting at the depth limit
+        let mut tree = NTree::new(Bounds::new(Coord([0, 0, 0]), Coord([8, 8, 8])), 2);
+        for i in 0..10 {
+            tree.insert(Coord([3, 3, 3]), i);
+        }
+        tree.insert(Coord([8, 0, 8]), 10);
+        assert_eq!(tree.len(), 11);
+        let corner = Bounds::new(Coord([5, 0, 5]), Coord([8, 1, 8]));
+        assert_eq!(tree.range(&corner), vec![(&Coord([8, 0, 8]), &10)]);
+        assert_eq!(tree.nearest(&Coord([7, 1, 7])).map(|r| *r.1), Some(10));
+        assert_eq!(tree.nearest(&Coord([0, 0, 0])).map(|r| r.2), Some(27));
+
+        let empty = NTree::<Pt<f64>, ()>::new(Bounds::from_point(Pt { x: 0., y: 0. }), 4);
+        assert!(empty.is_empty() && empty.nearest(&Pt { x: 1., y: 1. }).is_none());
+    }
+
+    #[test]
+    fn test_loose_tree() {
+        let world = Bounds::new(Pt { x: 0., y: 0. }, Pt { x: 64., y: 64. });
+        let mut tree = LooseTree::new(world, 2., 5);
+        let boxed = |x: f64, y: f64, h: f64| {
+            Bounds::from_center_half_extents(Pt { x, y }, Pt { x: h, y: h })
+        };
+        let small = tree.insert(boxed(10., 10., 0.5), "small");
+        let big = tree.insert(boxed(40., 40., 20.), "big");
+        let outside = tree.insert(boxed(-50., 5., 1.), "outside");
+        assert_eq!(tree.len(), 3);
+        //small objects sink to deep cells, big ones stay shallow
+        assert_eq!(
+            tree.nodes[tree.objects[small].as_ref().unwrap().node].depth,
+            5
+        );
+        assert!(tree.nodes[tree.objects[big].as_ref().unwrap().node].depth <= 1);
+
+        let sorted = |mut ids: Vec<usize>| {
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(tree.query(&boxed(10., 10., 1.)), vec![small]);
+        assert_eq!(sorted(tree.query(&boxed(30., 30., 1.))), vec![big]);
+        assert_eq!(tree.query(&boxed(-50., 5., 0.1)), vec![outside]);
+        assert!(tree.query(&boxed(60., 2., 1.)).is_empty());
+
+        //a small step keeps the node, a long one moves it
+        let node = tree.objects[small].as_ref().unwrap().node;
+        assert!(tree.relocate(small, Pt { x: 10.2, y: 10.1 }));
+        assert_eq!(tree.objects[small].as_ref().unwrap().node, node);
+        assert_eq!(tree.get(small).unwrap().0, &boxed(10.2, 10.1, 0.5));
+        assert!(tree.relocate(small, Pt { x: 60., y: 2. }));
+        assert_ne!(tree.objects[small].as_ref().unwrap().node, node);
+        assert!(tree.query(&boxed(10., 10., 1.)).is_empty());
+        assert_eq!(tree.query(&boxed(60., 2., 1.)), vec![small]);
+        assert!(tree.relocate(outside, Pt { x: 30., y: 30. }));
+        assert_eq!(
+            sorted(tree.query(&boxed(30., 30., 1.))),
+            sorted(vec![big, outside])
+        );
+
+        assert_eq!(tree.remove(big), Some("big"));
+        assert_eq!(tree.remove(big), None);
+        assert!(!tree.relocate(big, Pt { x: 1., y: 1. }));
+        assert_eq!(tree.insert(boxed(5., 5., 1.), "reused"), big);
+        assert_eq!(tree.len(), 3);
+
+        //many movers stay consistent with a brute force check
+        let mut rng = XorShift::new(3);
+        let mut tree = LooseTree::new(world, 2., 4);
+        let mut centers: Vec<_> = (0..200)
+            .map(|_| Pt {
+                x: rng.next_f64() * 64.,
+                y: rng.next_f64() * 64.,
+            })
+            .collect();
+        for c in &centers {
+            tree.insert(boxed(c.x, c.y, 0.7), ());
+        }
+        for _ in 0..5 {
+            for (id, c) in centers.iter_mut().enumerate() {
+                *c = c.add(&Pt {
+                    x: rng.next_f64() * 4. - 2.,
+                    y: rng.next_f64() * 4. - 2.,
+                });
+                assert!(tree.relocate(id, *c));
+            }
+            let q = boxed(rng.next_f64() * 64., rng.next_f64() * 64., 6.);
+            let expect: Vec<_> = (0..centers.len())
+                .filter(|&id| boxed(centers[id].x, centers[id].y, 0.7).intersects(&q))
+                .collect();
+            assert_eq!(sorted(tree.query(&q)), expect);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+///units in the last place - number of representable floats between two values
+pub trait Ulps: Copy {
+    ///ulps between self & other, u64::MAX if either value is NaN
+    fn ulps(self, other: Self) -> u64;
+}
+
+macro_rules! impl_ulps {
+    ($t:ty, $i:ty) => {
+        impl Ulps for $t {
+            fn ulps(self, other: Self) -> u64 {
+                if self.is_nan() || other.is_nan() {
+                    return u64::MAX;
+                }
+                //map bit patterns onto a monotonic signed scale, -0 and +0 both map to 0
+                let key = |v: $t| {
+                    let i = v.to_bits() as $i;
+                    if i < 0 {
+                        <$i>::MIN - i
+                    } else {
+                        i
+                    }
+                };
+                (key(self) as i128 - key(other) as i128).unsigned_abs() as u64
+            }
+        }
+    };
+}
+
+impl_ulps!(f32, i32);
+impl_ulps!(f64, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ulps() {
+        assert_eq!(1.0f64.ulps(1.0), 0);
+        assert_eq!(0.0f64.ulps(-0.0), 0);
+        assert_eq!(1.0f64.ulps(1.0 + f64::EPSILON), 1);
+        assert_eq!((1.0f32 + f32::EPSILON).ulps(1.0), 1);
+        assert_eq!(f64::from_bits(1).ulps(-f64::from_bits(1)), 2);
+        assert_eq!(f64::NAN.ulps(1.0), u64::MAX);
+        assert_eq!(f64::MAX.ulps(-f64::MAX), 2 * f64::MAX.to_bits());
+    }
+}
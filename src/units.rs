@@ -0,0 +1,125 @@
+use crate::Coordinate;
+use num_traits::Float;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+///unit of length tag
+pub trait Unit: Copy + Clone + PartialEq + Debug {
+    ///unit symbol
+    const SYMBOL: &'static str;
+    ///length of one unit in meters
+    const METERS: f64;
+}
+
+macro_rules! length_unit {
+    ($name:ident, $sym:expr, $meters:expr) => {
+        #[doc = concat!("length unit - ", $sym)]
+        #[derive(Copy, Clone, PartialEq, Debug)]
+        pub struct $name;
+
+        impl Unit for $name {
+            const SYMBOL: &'static str = $sym;
+            const METERS: f64 = $meters;
+        }
+    };
+}
+
+length_unit!(Millimeter, "mm", 0.001);
+length_unit!(Meter, "m", 1.0);
+length_unit!(Kilometer, "km", 1000.0);
+length_unit!(Foot, "ft", 0.3048);
+length_unit!(Mile, "mi", 1609.344);
+
+///coordinate tagged with a unit of length - mixing units in any
+///`Coordinate` operation (e.g. meters + feet) fails to compile
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Measured<C: Coordinate, U: Unit> {
+    coord: C,
+    unit: PhantomData<U>,
+}
+
+impl<C: Coordinate, U: Unit> Measured<C, U> {
+    ///tags coordinate with unit U
+    pub fn new(coord: C) -> Self {
+        Measured {
+            coord,
+            unit: PhantomData,
+        }
+    }
+
+    ///untagged coordinate
+    pub fn into_inner(self) -> C {
+        self.coord
+    }
+
+    ///rescales coordinate values from unit U to unit V
+    pub fn convert<V: Unit>(&self) -> Measured<C, V>
+    where
+        C::Scalar: Float,
+    {
+        let factor: C::Scalar = num_traits::cast(U::METERS / V::METERS).unwrap();
+        Measured::new(self.coord.mult(factor))
+    }
+}
+
+impl<C: Coordinate, U: Unit> Coordinate for Measured<C, U> {
+    type Scalar = C::Scalar;
+    const DIM: usize = C::DIM;
+
+    fn gen(val_fn: impl Fn(usize) -> Self::Scalar) -> Self {
+        Measured::new(C::gen(val_fn))
+    }
+
+    fn val(&self, i: usize) -> Self::Scalar {
+        self.coord.val(i)
+    }
+
+    fn val_mut(&mut self, i: usize) -> &mut Self::Scalar {
+        self.coord.val_mut(i)
+    }
+}
+
+#[cfg(feature = "uom")]
+impl<C: Coordinate<Scalar = f64>, U: Unit> Measured<C, U> {
+    ///value in ith dim as a `uom` length quantity
+    pub fn length(&self, i: usize) -> uom::si::f64::Length {
+        uom::si::f64::Length::new::<uom::si::length::meter>(self.val(i) * U::METERS)
+    }
+
+    ///creates coordinate from `uom` length quantities in each dimension
+    pub fn from_lengths(val_fn: impl Fn(usize) -> uom::si::f64::Length) -> Self {
+        Self::gen(|i| val_fn(i).get::<uom::si::length::meter>() / U::METERS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Pt;
+
+    #[test]
+    fn test_measured() {
+        let a: Measured<Pt<f64>, Foot> = Measured::new(Pt { x: 10., y: 20. });
+        let b = Measured::<_, Foot>::new(Pt { x: 1., y: 2. });
+        assert_eq!(a.add(&b).into_inner(), Pt { x: 11., y: 22. });
+
+        let m = a.convert::<Meter>();
+        assert!((m.val(0) - 3.048).abs() < 1e-12);
+        assert!((m.val(1) - 6.096).abs() < 1e-12);
+        let back = m.convert::<Foot>();
+        assert!(back.sub(&a).square_length() < 1e-20);
+        assert_eq!(Kilometer::SYMBOL, "km");
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn test_measured_uom() {
+        use uom::si::length::{foot, meter};
+        let a = Measured::<Pt<f64>, Kilometer>::new(Pt { x: 1.5, y: 2. });
+        assert!((a.length(0).get::<meter>() - 1500.).abs() < 1e-9);
+        let b = Measured::<Pt<f64>, Meter>::from_lengths(|i| {
+            uom::si::f64::Length::new::<foot>(i as f64 + 1.)
+        });
+        assert!((b.val(1) - 0.6096).abs() < 1e-12);
+    }
+}